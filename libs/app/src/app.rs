@@ -1,5 +1,7 @@
-use gfx::{Commands, CHAR_ADVANCE_H, CHAR_SPACING_H, CHAR_SPACING};
-use platform_types::{Button, Input, Speaker, SFX, unscaled, command};
+use gfx::{Commands, CHAR_ADVANCE_H, CHAR_ADVANCE_W, CHAR_SPACING_H, CHAR_SPACING, WIDTH_IN_CHARS};
+use game::keys::{button_text, with_keys};
+use models::{Zinger, get_zinger, zingers};
+use platform_types::{Bindings, Button, Input, Key, Speaker, SFX, Theme, unscaled, command, CARD_WIDTH, WHITE};
 pub use platform_types::StateParams;
 
 #[derive(Clone, Copy, Default)]
@@ -9,13 +11,116 @@ enum HelpVis {
     Hidden
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum HelpPage {
+    #[default]
+    Rules,
+    Zinger(Zinger),
+    Tutorial,
+    Settings,
+    Credits,
+}
+
+impl HelpPage {
+    const COUNT: u8 = zingers::ALL.len() as u8 + 4;
+
+    fn index(self) -> u8 {
+        match self {
+            Self::Rules => 0,
+            Self::Zinger(zinger) => 1 + zinger as u8,
+            Self::Tutorial => Self::COUNT - 3,
+            Self::Settings => Self::COUNT - 2,
+            Self::Credits => Self::COUNT - 1,
+        }
+    }
+
+    fn from_index(index: u8) -> Self {
+        match index % Self::COUNT {
+            0 => Self::Rules,
+            i if i == Self::COUNT - 3 => Self::Tutorial,
+            i if i == Self::COUNT - 2 => Self::Settings,
+            i if i == Self::COUNT - 1 => Self::Credits,
+            i => Self::Zinger(
+                get_zinger(zingers::ALL[usize::from(i - 1)])
+                    .expect("zingers::ALL should only contain zingers")
+            ),
+        }
+    }
+
+    fn next(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    fn previous(self) -> Self {
+        Self::from_index(self.index() + Self::COUNT - 1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Setting {
+    AnimationSpeed,
+    Theme,
+    CpuStrategy,
+    Binding(Button),
+    ResetBindings,
+}
+
+impl Setting {
+    const COUNT: u8 = Button::ALL.len() as u8 + 4;
+
+    fn from_index(index: u8) -> Self {
+        match index % Self::COUNT {
+            0 => Self::AnimationSpeed,
+            1 => Self::Theme,
+            2 => Self::CpuStrategy,
+            i if i == Self::COUNT - 1 => Self::ResetBindings,
+            i => Self::Binding(Button::ALL[usize::from(i - 3)]),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Rebinding {
+    #[default]
+    Idle,
+    /// The next key pressed will be bound to this button.
+    Waiting(Button),
+    Refused { key: Key, only_key_for: Button },
+}
+
+struct Settings {
+    selected_index: u8,
+    rebinding: Rebinding,
+    bindings: Bindings,
+    theme: Theme,
+    /// Used to tell key repeats apart from fresh presses.
+    held_keys: [bool; Key::COUNT],
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            selected_index: 0,
+            rebinding: Rebinding::default(),
+            bindings: Bindings::default(),
+            theme: Theme::default(),
+            held_keys: [false; Key::COUNT],
+        }
+    }
+}
+
 pub struct State {
     game_state: game::State,
     commands: Commands,
+    /// What the game drew on the last frame before the help was shown, so
+    /// it can be kept behind the help, dimmed.
+    table: Vec<platform_types::Command>,
     input: Input,
     speaker: Speaker,
     help_vis: HelpVis,
+    help_page: HelpPage,
     top_index_with_offset: usize,
+    settings: Settings,
 }
 
 impl State {
@@ -32,40 +137,111 @@ impl State {
         Self {
             game_state: game::State::new(seed),
             commands: Commands::default(),
+            table: Vec::new(),
             input: Input::default(),
             speaker: Speaker::default(),
             help_vis: if true { HelpVis::Hidden } else {HelpVis::default()},
+            help_page: HelpPage::default(),
             top_index_with_offset: 0,
+            settings: Settings::default(),
         }
     }
 }
 
 impl platform_types::State for State {
     fn frame(&mut self) -> (&[platform_types::Command], &[SFX]) {
-        self.commands.clear();
         self.speaker.clear();
 
         if self.input.pressed_this_frame(Button::HELP) {
             self.help_vis = match self.help_vis {
                 HelpVis::Shown => HelpVis::Hidden,
-                HelpVis::Hidden => HelpVis::Shown,
+                HelpVis::Hidden => {
+                    // The commands are still the ones from the last frame.
+                    self.table.clear();
+                    self.table.extend_from_slice(self.commands.slice());
+
+                    HelpVis::Shown
+                },
             };
 
             self.speaker.request_sfx(SFX::ButtonPress);
         }
 
+        self.commands.clear();
+
         match self.help_vis {
             HelpVis::Shown => {
-                if self.input.gamepad.contains(Button::DOWN) {
-                    self.top_index_with_offset += 1;
-                } else if self.input.gamepad.contains(Button::UP) {
-                    self.top_index_with_offset =
-                        self.top_index_with_offset.saturating_sub(1);
+                if self.input.repeated_this_frame(Button::LEFT) {
+                    self.help_page = self.help_page.previous();
+                    self.top_index_with_offset = 0;
+                } else if self.input.repeated_this_frame(Button::RIGHT) {
+                    self.help_page = self.help_page.next();
+                    self.top_index_with_offset = 0;
+                } else if self.help_page == HelpPage::Settings
+                && matches!(self.settings.rebinding, Rebinding::Waiting(_))
+                && self.input.pressed_this_frame(Button::B) {
+                    // Keys go straight to the binding while waiting, so this
+                    // is for Cancel pressed some other way, like on a gamepad.
+                    self.settings.rebinding = Rebinding::Idle;
+                } else if self.help_page == HelpPage::Settings {
+                    let selected_index = &mut self.settings.selected_index;
+                    if self.input.repeated_this_frame(Button::DOWN) {
+                        *selected_index = (*selected_index + 1) % Setting::COUNT;
+                    } else if self.input.repeated_this_frame(Button::UP) {
+                        *selected_index = (*selected_index + Setting::COUNT - 1) % Setting::COUNT;
+                    }
+                } else if self.input.repeated_this_frame(Button::DOWN) {
+                    self.top_index_with_offset += usize::from(CHAR_ADVANCE_H.get().get());
+                } else if self.input.repeated_this_frame(Button::UP) {
+                    self.top_index_with_offset = self.top_index_with_offset
+                        .saturating_sub(usize::from(CHAR_ADVANCE_H.get().get()));
                 }
 
+                if self.help_page == HelpPage::Tutorial
+                && self.input.pressed_this_frame(Button::A) {
+                    let animation_speed = self.game_state.animation_speed;
+                    self.game_state = game::State::tutorial();
+                    self.game_state.animation_speed = animation_speed;
+                    self.help_vis = HelpVis::Hidden;
+
+                    self.speaker.request_sfx(SFX::ButtonPress);
+                }
+
+                if self.help_page == HelpPage::Settings
+                && self.input.pressed_this_frame(Button::A) {
+                    self.settings.rebinding = Rebinding::Idle;
+
+                    match Setting::from_index(self.settings.selected_index) {
+                        Setting::AnimationSpeed => {
+                            self.game_state.animation_speed = self.game_state.animation_speed.next();
+                        },
+                        Setting::Theme => {
+                            self.settings.theme = self.settings.theme.next();
+                        },
+                        Setting::CpuStrategy => {
+                            let next = self.game_state.cpu_strategies[0].next();
+                            self.game_state.cpu_strategies.fill(next);
+                        },
+                        Setting::Binding(button) => {
+                            self.settings.rebinding = Rebinding::Waiting(button);
+                        },
+                        Setting::ResetBindings => {
+                            self.settings.bindings = Bindings::default();
+                        },
+                    }
+
+                    self.speaker.request_sfx(SFX::ButtonPress);
+                }
+
+                self.commands.extend_from_slice(&self.table);
+                self.commands.fill(gfx::SCREEN_RECT, HELP_DIM_COLOUR);
+
                 render_shown(
                     &mut self.commands,
+                    self.help_page,
                     self.top_index_with_offset,
+                    &self.game_state,
+                    &self.settings,
                 );
             },
             HelpVis::Hidden => {
@@ -73,42 +249,139 @@ impl platform_types::State for State {
                     &mut self.commands,
                     &mut self.game_state,
                     self.input,
+                    &self.settings.bindings,
                     &mut self.speaker,
                 );
             },
         }
 
-        self.input.previous_gamepad = self.input.gamepad;
+        self.input.end_frame();
 
         (self.commands.slice(), self.speaker.slice())
     }
 
     fn press(&mut self, button: Button) {
-        if self.input.previous_gamepad.contains(button) {
-            //This is meant to pass along the key repeat, if any.
-            //Not sure if rewriting history is the best way to do this.
-            self.input.previous_gamepad.remove(button);
-        }
-
         self.input.gamepad.insert(button);
     }
 
     fn release(&mut self, button: Button) {
         self.input.gamepad.remove(button);
     }
+
+    fn pointer_move(&mut self, xy: Option<unscaled::XY>) {
+        self.input.pointer.xy = xy;
+    }
+
+    fn pointer_press(&mut self) {
+        self.input.pointer.down = true;
+    }
+
+    fn pointer_release(&mut self) {
+        self.input.pointer.down = false;
+    }
+
+    fn key_press(&mut self, key: Key) {
+        let is_repeat = core::mem::replace(
+            &mut self.settings.held_keys[key as usize],
+            true
+        );
+
+        if let Rebinding::Waiting(button) = self.settings.rebinding {
+            // Otherwise holding down the key that started the rebinding would
+            // bind that key, once the OS started repeating it.
+            if is_repeat {
+                return
+            }
+
+            self.settings.rebinding = if key == Key::Escape {
+                Rebinding::Idle
+            } else {
+                match self.settings.bindings.bind(button, key) {
+                    Ok(()) => Rebinding::Idle,
+                    Err(only_key_for) => Rebinding::Refused { key, only_key_for },
+                }
+            };
+            return
+        }
+
+        // Held buttons repeat according to `Input::repeat` instead.
+        if is_repeat {
+            return
+        }
+
+        if let Some(button) = self.settings.bindings.button_for(key) {
+            self.press(button);
+        }
+    }
+
+    fn key_release(&mut self, key: Key) {
+        self.settings.held_keys[key as usize] = false;
+
+        if let Some(button) = self.settings.bindings.button_for(key) {
+            self.release(button);
+        }
+    }
+
+    fn bindings(&self) -> &Bindings {
+        &self.settings.bindings
+    }
+
+    fn set_bindings(&mut self, bindings: Bindings) {
+        self.settings.bindings = bindings;
+    }
+
+    fn theme(&self) -> Theme {
+        self.settings.theme
+    }
+
+    fn set_theme(&mut self, theme: Theme) {
+        self.settings.theme = theme;
+    }
 }
 
-fn render_shown(
-    commands: &mut Commands,
-    top_index_with_offset: usize,
-) {
-    const HELP: &[u8] = b"Press shift to show/hide this message.
-----------------
-Help
+const HEADER_H: unscaled::H = unscaled::H(CHAR_ADVANCE_H.get().get() * 2);
+const FOOTER_H: unscaled::H = unscaled::H(CHAR_ADVANCE_H.get().get() * 2);
+
+/// Darker than `gfx::DIM_COLOUR`, since the help is mostly text, which needs to
+/// be readable over whatever is on the table.
+const HELP_DIM_COLOUR: platform_types::ARGB = 0xF8_00_00_00 | (platform_types::BLACK & 0x00_FF_FF_FF);
+
+const BODY_RECT: unscaled::Rect = unscaled::Rect {
+    x: unscaled::X(CHAR_SPACING as _),
+    y: unscaled::Y(HEADER_H.get()),
+    w: unscaled::W(command::WIDTH - CHAR_SPACING as unscaled::Inner * 2),
+    h: unscaled::H(command::HEIGHT - HEADER_H.get() - FOOTER_H.get()),
+};
+
+const RULES: &[u8] = b"Rules
 ----------------
-TODO
+Each player starts with 8 cards. There are 13 kinds of fish, and each kind comes in 5 suits: Red, Green, Blue, Yellow and Purple. Each suit also has a symbol in the top left corner of its cards: a circle for Red, a triangle for Green, a square for Blue, a diamond for Yellow and a cross for Purple.
+
+On your turn, select a fish in your hand and press {Confirm} to ask another player for a card of the same kind, in whichever suit you like. If they have it, they have to give it to you, and you go again.
+
+If they don't have it, you go fish, which means you draw the top card of the deck. If you draw the card you asked for, you go again. Otherwise your turn is over.
 
+Once you have all 5 suits of one kind of fish, they make a basket. Barnacle, Crab, Dogfish, Eel, Flying Fish, Hammerhead, Jellyfish and Shrimp baskets are worth 5 points. Blowfish, Clownfish, Starfish and Whale baskets are worth 10 points. Card Shark baskets are worth 15 points. Whoever has the most points at the end wins.
+
+If you have no cards left, you are dead in the water, and you just draw a card on your turn.
+
+Zingers are cards that bend the rules. The next few pages say what each one does. Select a zinger in your hand and press {Confirm} to play it, or play it from the window that pops up when you have a chance to respond with it.
+
+----------------
+Controls
 ----------------
+{Up}/{Down}/{Left}/{Right}: Move
+{Up}: Show/hide where the selected kind of fish is, on your turn
+{Down}: Sort or rearrange your hand, on your turn
+{Confirm}: Confirm
+{Cancel}: Cancel, or open the notepad on your turn
+{Hint}: Get a hint on your turn, or hold it to speed through the other players' turns
+{Help}: Show/hide this help
+These keys can be changed on the Settings page.
+Mouse or touch: Point at a card or button to select it, and click or tap it to confirm
+";
+
+const CREDITS: &[u8] = b"----------------
 Image Credits
 ----------------
 \"Shark Jaws\" by Lorc, (modified)
@@ -201,30 +474,255 @@ Everything Else
 Ryan Wiedemann (Ryan1729 on github)
 ";
 
-    for (y, line) in text::lines(HELP)
+fn zinger_text(zinger: Zinger) -> &'static [u8] {
+    use Zinger::*;
+    match zinger {
+        DeadScubaDiver => b"Dead Scuba Diver
+
+Play this when you have four of the five cards of a basket. The diver stands in for the missing card, and you make the basket right away.
+
+You can play this at any time.",
+        DivineIntervention => b"Divine Intervention
+
+Play this right after someone else plays a zinger, to cancel that zinger.
+
+If you would rather get rid of it, you can spend a whole turn discarding it instead, but only while there are still other zingers that have not been played.",
+        GlassBottomBoat => b"Glass Bottom Boat
+
+Play this to look at a random card from another player's hand. They get to keep the card, but now you know they have it.
+
+You can play this at any time.",
+        NoFishing => b"No Fishing
+
+Play this when someone asks you for a card. They don't get anything from you, and they have to go fish instead.",
+        TheGameWarden => b"The Game Warden
+
+Play this to take a random card from another player's hand, and shuffle it back into the deck.
+
+You can play this at any time.",
+        TheLure => b"The Lure
+
+Play this as the first thing you do on your turn, to ask a player for any card you like, even a kind of fish you don't have.",
+        TheNet => b"The Net
+
+Play this as the first thing you do on your turn, to ask a player for a card of a particular suit, or of a particular kind of fish. If they have any, they have to give you one of them.",
+        TwoFistedFisherman => b"Two-Fisted Fisherman
+
+Play this when you ask for a card and don't get it. Instead of going fishing, you get to take another turn.",
+    }
+}
+
+const TUTORIAL: &[u8] = b"Tutorial
+
+Press {Confirm} to start a short game with a stacked deck, that shows you how to ask, go fish, make a basket and respond with a zinger.
+
+This will end the current game!";
+
+const SETTINGS: &[u8] = b"Select a setting with {Up} and {Down}, and press {Confirm} to change it. To add a key to a button, select the button, press {Confirm}, then press the key, or Escape to leave it as it was. Each button can have up to 4 keys.
+
+Whatever the speed, you can hold {Hint} during the other players' turns to hurry them along.
+
+The colours can be changed to ones that are easier to tell apart with some kinds of colourblindness, or in some lighting.
+
+Hard+ Cpu players think further ahead, but not in the tutorial.";
+
+fn reflowed(text: &[u8], width_in_chars: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(text.len() + text.len() / width_in_chars.max(1));
+
+    for line in text::lines(text) {
+        output.extend(text::reflow(line, width_in_chars));
+        output.push(b'\n');
+    }
+
+    output
+}
+
+fn render_shown(
+    commands: &mut Commands,
+    page: HelpPage,
+    top_index_with_offset: usize,
+    game_state: &game::State,
+    settings: &Settings,
+) {
+    let title: &[u8] = match page {
+        HelpPage::Rules => b"Rules",
+        HelpPage::Zinger(_) => b"Zingers",
+        HelpPage::Tutorial => b"Tutorial",
+        HelpPage::Settings => b"Settings",
+        HelpPage::Credits => b"Credits",
+    };
+
+    let mut header = Vec::with_capacity(32);
+    header.extend_from_slice(b"< ");
+    header.extend_from_slice(title);
+    header.extend_from_slice(format!(" {}/{} >", page.index() + 1, HelpPage::COUNT).as_bytes());
+
+    commands.print_centered(
+        &header,
+        unscaled::Rect {
+            x: unscaled::X(0),
+            y: unscaled::Y(0),
+            w: command::WIDTH_W,
+            h: HEADER_H,
+        },
+        WHITE,
+    );
+
+    let footer: &[u8] = if page == HelpPage::Settings {
+        b"{Left}/{Right}: Change page   {Up}/{Down}: Select   {Help}: Close"
+    } else {
+        b"{Left}/{Right}: Change page   {Up}/{Down}: Scroll   {Help}: Close"
+    };
+
+    commands.print_centered(
+        &with_keys(footer, &settings.bindings),
+        unscaled::Rect {
+            x: unscaled::X(0),
+            y: unscaled::Y(command::HEIGHT - FOOTER_H.get()),
+            w: command::WIDTH_W,
+            h: FOOTER_H,
+        },
+        WHITE,
+    );
+
+    match page {
+        HelpPage::Rules => {
+            render_scrolled(
+                commands,
+                &reflowed(&with_keys(RULES, &settings.bindings), WIDTH_IN_CHARS as usize - 1),
+                top_index_with_offset,
+            );
+        },
+        HelpPage::Zinger(zinger) => {
+            let card_xy = BODY_RECT.xy() + CHAR_SPACING_H.get();
+
+            commands.draw_card(
+                zingers::ALL[zinger as usize],
+                card_xy,
+            );
+
+            let text_xy = card_xy + CARD_WIDTH + CHAR_ADVANCE_W.get();
+            let width_in_chars = (
+                (BODY_RECT.x + BODY_RECT.w) - text_xy.x
+            ).get() / CHAR_ADVANCE_W.get().get();
+
+            for (y, line) in text::lines(&reflowed(
+                zinger_text(zinger),
+                width_in_chars as usize,
+            )).enumerate() {
+                commands.print_line(
+                    line,
+                    text_xy + unscaled::H(y as unscaled::Inner * CHAR_ADVANCE_H.get().get()),
+                    0 // No override
+                );
+            }
+        },
+        HelpPage::Tutorial => {
+            commands.print_centered(
+                &reflowed(&with_keys(TUTORIAL, &settings.bindings), WIDTH_IN_CHARS as usize / 2),
+                BODY_RECT,
+                WHITE,
+            );
+        },
+        HelpPage::Settings => {
+            let mut text = reflowed(
+                &with_keys(SETTINGS, &settings.bindings),
+                WIDTH_IN_CHARS as usize - 1,
+            );
+            text.push(b'\n');
+
+            for index in 0..Setting::COUNT {
+                text.extend_from_slice(
+                    if index == settings.selected_index { b"> " } else { b"  " }
+                );
+
+                match Setting::from_index(index) {
+                    Setting::AnimationSpeed => {
+                        text.extend_from_slice(b"Animation speed: ");
+                        text.extend_from_slice(game_state.animation_speed.text());
+                    },
+                    Setting::Theme => {
+                        text.extend_from_slice(b"Colours: ");
+                        text.extend_from_slice(settings.theme.text().as_bytes());
+                    },
+                    Setting::CpuStrategy => {
+                        text.extend_from_slice(b"Cpu players: ");
+                        text.extend_from_slice(game_state.cpu_strategies[0].text());
+                    },
+                    Setting::Binding(button) => {
+                        text.extend_from_slice(button_text(button).as_bytes());
+                        text.extend_from_slice(b": ");
+                        if settings.rebinding == Rebinding::Waiting(button) {
+                            text.extend_from_slice(b"Press a key...");
+                        } else {
+                            for (i, key) in settings.bindings.keys(button).enumerate() {
+                                if i > 0 {
+                                    text.extend_from_slice(b", ");
+                                }
+                                text.extend_from_slice(key.text().as_bytes());
+                            }
+                        }
+                    },
+                    Setting::ResetBindings => {
+                        text.extend_from_slice(b"Reset all keys to the defaults");
+                    },
+                }
+                text.push(b'\n');
+            }
+
+            if let Rebinding::Refused { key, only_key_for } = settings.rebinding {
+                text.push(b'\n');
+                text.extend_from_slice(
+                    format!(
+                        "{} is the only key for {}, so it was left alone.",
+                        key.text(),
+                        button_text(only_key_for),
+                    ).as_bytes()
+                );
+            }
+
+            render_scrolled(commands, &text, 0);
+        },
+        HelpPage::Credits => {
+            render_scrolled(
+                commands,
+                CREDITS,
+                top_index_with_offset,
+            );
+        },
+    }
+}
+
+fn render_scrolled(
+    commands: &mut Commands,
+    text: &[u8],
+    top_index_with_offset: usize,
+) {
+    for (y, line) in text::lines(text)
         .skip((top_index_with_offset as u16 / CHAR_ADVANCE_H.get().get()) as usize)
-        .take(command::h_to_usize(command::HEIGHT * CHAR_ADVANCE_H))
+        .take(usize::from(BODY_RECT.h.get() / CHAR_ADVANCE_H.get().get()))
         .enumerate()
     {
         let y = y as unscaled::Inner;
 
         let offset = top_index_with_offset as u16 % CHAR_ADVANCE_H.get().get();
 
+        // Don't draw over the header.
+        if y == 0 && offset > 0 {
+            continue
+        }
+
         commands.print_line(
             line,
-            unscaled::XY {
-                x: unscaled::X(CHAR_SPACING as _),
-                y: unscaled::Y(0),
-            }
+            BODY_RECT.xy()
             // TODO investigate scrolling shimmering which seems to be
             // related to this part. Do we need to make the scrolling
             // speed up, then slow down or something? or is the offset
             // calculation just wrong?  Maybe it won't look right unless
             // we add more in-between frames?
             + unscaled::H(
-                ((y + 1) * CHAR_ADVANCE_H.get().get())
+                (y * CHAR_ADVANCE_H.get().get())
                 - offset
-                - 1
             )
             + CHAR_SPACING_H.get(),
             0 // No override
@@ -233,7 +731,6 @@ Ryan Wiedemann (Ryan1729 on github)
 }
 
 mod text {
-    #[allow(unused)]
     pub fn reflow(bytes: &[u8], width: usize) -> Vec<u8> {
         if width == 0 || bytes.is_empty() {
             return Vec::new();
@@ -1,9 +1,10 @@
 use memories::Memories;
-use models::{Basket, Card, CardIndex, CpuId, Hand, HandId, HandOrdering, Predicate, LurePredicate, NetPredicate, Rank, Suit, Targeting, Zinger, DECK_SIZE, fish_card, get_rank, get_suit, zingers};
+use models::{Basket, Card, CardIndex, CpuId, Hand, HandId, HandOrdering, Predicate, LurePredicate, NetPredicate, Rank, Suit, Targeting, Zinger, fish_card, get_rank, zingers};
 use gfx::{Commands, CHEVRON_H, WINDOW_CONTENT_OFFSET};
 use platform_types::{
-    command,
+    command::{self, Transform},
     unscaled::{self, X, Y, XY, W, H, WH, Rect, x_const_add_w, w_const_sub},
+    Bindings,
     Button,
     Dir,
     Input,
@@ -54,7 +55,7 @@ macro_rules! cpu_handle_negative_response {
             let predicate = $predicate;
             let my_len = $state.cards.hand(hand_id).len();
 
-            let card_option = $state.cards.deck.draw();
+            let card_option = draw_for(&mut $state.cards.deck, &mut $state.memories, hand_id);
 
             if let Some(card) = card_option {
                 let at = DECK_XY;
@@ -64,13 +65,11 @@ macro_rules! cpu_handle_negative_response {
                     my_len
                 );
 
-                $state.animations.push(Animation {
-                    card,
-                    at,
-                    target,
-                    action: AnimationAction::AddToHand(hand_id),
-                    .. <_>::default()
-                });
+                $state.animations.push(
+                    Animation::tween(card, at, target)
+                        .turned(turn(hand_id))
+                        .then(move |state, speaker| state.add_to_hand(speaker, hand_id, card))
+                );
 
                 if predicate.matches(card) {
                     $state.memories.found(hand_id, card);
@@ -126,8 +125,15 @@ const DISCARD_XY: XY = XY {
     y: Y((command::HEIGHT - CARD_HEIGHT.get()) / 2),
 };
 
+/// How much room a card in one of the side hands takes up. Those are given a
+/// quarter turn, like they would be by someone sitting at the side of a table.
+const SIDE_CARD_WH: WH = WH {
+    w: W(CARD_HEIGHT.get()),
+    h: H(CARD_WIDTH.get()),
+};
+
 const PLAYER_BASE_XY: XY = XY {
-    x: X(CARD_WIDTH.get() * 5 / 4),
+    x: X(SIDE_CARD_WH.w.get()),
     y: Y(command::HEIGHT - CARD_HEIGHT.get()),
 };
 
@@ -137,18 +143,32 @@ const CPU1_BASE_XY: XY = XY {
 };
 
 const CPU2_BASE_XY: XY = XY {
-    x: X(CARD_WIDTH.get() * 5 / 4),
+    x: X(SIDE_CARD_WH.w.get()),
     y: Y(0),
 };
 
 const CPU3_BASE_XY: XY = XY {
-    x: X(command::WIDTH - CARD_WIDTH.get()),
+    x: X(command::WIDTH - SIDE_CARD_WH.w.get()),
     y: Y(CARD_HEIGHT.get() / 2),
 };
 
+/// How the cards in the given hand are turned, so the tops of the cards in
+/// the side hands face the middle of the table.
+pub const fn turn(id: HandId) -> Transform {
+    match id {
+        HandId::Player
+        | HandId::Cpu2 => Transform::IDENTITY,
+        HandId::Cpu1 => Transform::ROTATE_CW,
+        HandId::Cpu3 => Transform::ROTATE_CCW,
+    }
+}
+
 fn in_front_of(id: HandId) -> XY {
     const HALF_W: W = W(CARD_WIDTH.get() / 2);
     const HALF_H: H = H(CARD_HEIGHT.get() / 2);
+    // The gathered cards are upright, and overlap the side hands by as much
+    // as they do the others.
+    const SIDE_OVERHANG: W = W(SIDE_CARD_WH.w.get() - HALF_W.get());
 
     match spread(id) {
         Spread::LTR((x1, x2), y) => XY {
@@ -165,13 +185,79 @@ fn in_front_of(id: HandId) -> XY {
                 HandId::Player
                 | HandId::Cpu3 => x - HALF_W,
                 HandId::Cpu1
-                | HandId::Cpu2 => x + HALF_W,
+                | HandId::Cpu2 => x + SIDE_OVERHANG,
             },
              y: y1 + ((y2 - y1) / 2) - HALF_H,
         },
     }
 }
 
+/// Where the given hand's completed baskets rest: the corner of the table to
+/// that hand's left, from the point of view of whoever is holding it. None of
+/// the hands ever reach into the corners.
+fn basket_pile_rect(id: HandId) -> Rect {
+    const W_: W = CARD_WIDTH;
+    const H_: H = H(CARD_HEIGHT.get() / 2);
+    const LEFT: X = X(0);
+    const RIGHT: X = X(command::WIDTH - W_.get());
+    const TOP: Y = Y(0);
+    const BOTTOM: Y = Y(command::HEIGHT - H_.get());
+
+    let (x, y) = match id {
+        HandId::Player => (LEFT, BOTTOM),
+        HandId::Cpu1 => (LEFT, TOP),
+        HandId::Cpu2 => (RIGHT, TOP),
+        HandId::Cpu3 => (RIGHT, BOTTOM),
+    };
+
+    Rect { x, y, w: W_, h: H_ }
+}
+
+/// Gathers the cards of a just completed basket together in front of the
+/// hand, from where they were in it, then moves them onto that hand's basket
+/// pile.
+fn gather_basket(
+    id: HandId,
+    basket: Basket,
+    froms: [XY; Suit::COUNT as usize],
+) -> Animation {
+    use timeline::{Easing, MOVE_FRAMES};
+
+    let in_front = in_front_of(id);
+    let pile = basket_pile_rect(id).xy();
+
+    // Fanned out a little, so each card can still be seen.
+    let gathered = |i: usize| in_front + W(i as unscaled::Inner * 4);
+
+    Animation::sequence([
+        Animation::parallel(
+            basket.into_iter()
+                .zip(froms)
+                .enumerate()
+                .map(|(i, (card, from))|
+                    Animation::tween(card, from, gathered(i))
+                        .shown()
+                        .duration(MOVE_FRAMES * 3 / 2)
+                )
+        ),
+        Animation::parallel(
+            basket.into_iter()
+                .enumerate()
+                .map(|(i, card)|
+                    Animation::tween(card, gathered(i), pile)
+                        .shown()
+                        .easing(Easing::EaseIn)
+                )
+        ),
+    ]).then(move |state, speaker| {
+        for card in basket {
+            state.cards.baskets_mut(id).push(card);
+        }
+
+        speaker.request_sfx(SFX::CardPlace);
+    })
+}
+
 pub enum Spread {
     /// Left To Right
     LTR((X, X), Y),
@@ -182,7 +268,7 @@ pub enum Spread {
 pub const PLAYER_SPREAD: Spread = Spread::LTR(
     (
         PLAYER_BASE_XY.x,
-        x_const_add_w(X(0), w_const_sub(command::WIDTH_W, CARD_WIDTH))
+        x_const_add_w(X(0), w_const_sub(command::WIDTH_W, SIDE_CARD_WH.w))
     ),
     PLAYER_BASE_XY.y
 );
@@ -198,7 +284,7 @@ pub const CPU1_SPREAD: Spread = Spread::TTB(
 pub const CPU2_SPREAD: Spread = Spread::LTR(
     (
         CPU2_BASE_XY.x,
-        x_const_add_w(X(0), w_const_sub(command::WIDTH_W, CARD_WIDTH))
+        x_const_add_w(X(0), w_const_sub(command::WIDTH_W, SIDE_CARD_WH.w))
     ),
     CPU2_BASE_XY.y
 );
@@ -220,55 +306,442 @@ pub const fn spread(id: HandId) -> Spread {
     }
 }
 
-#[derive(Clone)]
-pub struct Animations(pub [Animation; DECK_SIZE as usize]);
+pub mod timeline {
+    //! Cards moving around the table, built out of tweens that can be put in
+    //! sequence or run in parallel, with callbacks that run as each part
+    //! completes.
+    use super::*;
+    use std::rc::Rc;
 
-impl Default for Animations {
-    fn default() -> Self {
-        Self(core::array::from_fn(|_| Animation::default()))
+    pub type Frames = u16;
+
+    /// How long a card takes to move from one place to another, unless
+    /// otherwise specified.
+    pub const MOVE_FRAMES: Frames = 30;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum Easing {
+        Linear,
+        EaseIn,
+        #[default]
+        EaseOut,
+        EaseInOut,
+    }
+
+    impl Easing {
+        /// Maps the proportion of the time that has passed to the proportion of
+        /// the distance that has been covered.
+        pub fn apply(self, t: f32) -> f32 {
+            use Easing::*;
+            let t = t.clamp(0.0, 1.0);
+            match self {
+                Linear => t,
+                EaseIn => t * t,
+                EaseOut => t * (2.0 - t),
+                EaseInOut => if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                },
+            }
+        }
     }
-}
 
-impl Animations {
-    pub fn iter(&self) -> impl Iterator<Item = &Animation> + '_ {
-        self.0.iter().filter(|anim| !anim.is_done())
+    pub type Callback = Rc<dyn Fn(&mut State, &mut Speaker)>;
+
+    #[derive(Clone, Debug)]
+    pub struct Tween {
+        pub card: Card,
+        pub from: XY,
+        pub to: XY,
+        pub duration: Frames,
+        pub easing: Easing,
+        /// Whether the card is drawn face up.
+        pub shown: bool,
+        /// How the card is turned while it moves.
+        pub transform: Transform,
     }
 
-    pub fn push(&mut self, anim: Animation) {
-        let animations = &mut self.0;
-        for i in 0..animations.len() {
-            if animations[i].is_done() {
-                animations[i] = anim;
+    impl Tween {
+        pub fn at(&self, elapsed: Frames) -> XY {
+            if elapsed >= self.duration {
+                return self.to
+            }
 
-                break;
+            let p = self.easing.apply(f32::from(elapsed) / f32::from(self.duration));
+
+            XY {
+                x: X(lerp(self.from.x.get(), self.to.x.get(), p)),
+                y: Y(lerp(self.from.y.get(), self.to.y.get(), p)),
             }
         }
     }
 
-    pub fn all_done(&self) -> bool {
-        self.iter().count() == 0
+    fn lerp(from: unscaled::Inner, to: unscaled::Inner, p: f32) -> unscaled::Inner {
+        let from = f32::from(from);
+        let to = f32::from(to);
+
+        (from + (to - from) * p).round() as unscaled::Inner
     }
-}
 
-pub type Frames = u8;
+    #[derive(Clone)]
+    enum Kind {
+        Tween(Tween),
+        Wait(Frames),
+        Sequence { parts: Vec<Animation>, current: usize },
+        Parallel(Vec<Animation>),
+    }
 
-#[derive(Clone, Default)]
-pub struct Animation {
-    pub delay: Frames,
-    pub card: Card,
-    pub at: XY,
-    pub target: XY,
-    pub action: AnimationAction,
-    pub shown: bool,
+    #[derive(Clone)]
+    pub struct Animation {
+        kind: Kind,
+        elapsed: Frames,
+        finished: bool,
+        on_complete: Vec<Callback>,
+    }
+
+    impl Animation {
+        fn new(kind: Kind) -> Self {
+            Self {
+                kind,
+                elapsed: 0,
+                finished: false,
+                on_complete: Vec::new(),
+            }
+        }
+
+        /// A face down card moving from `from` to `to`.
+        pub fn tween(card: Card, from: XY, to: XY) -> Self {
+            Self::new(Kind::Tween(Tween {
+                card,
+                from,
+                to,
+                duration: MOVE_FRAMES,
+                easing: Easing::default(),
+                shown: false,
+                transform: Transform::IDENTITY,
+            }))
+        }
+
+        pub fn wait(frames: Frames) -> Self {
+            Self::new(Kind::Wait(frames))
+        }
+
+        /// Each part starts once the previous one has completed.
+        pub fn sequence(parts: impl IntoIterator<Item = Animation>) -> Self {
+            Self::new(Kind::Sequence {
+                parts: parts.into_iter().collect(),
+                current: 0,
+            })
+        }
+
+        /// All the parts start at once, and this completes once they all have.
+        pub fn parallel(parts: impl IntoIterator<Item = Animation>) -> Self {
+            Self::new(Kind::Parallel(parts.into_iter().collect()))
+        }
+
+        /// Draws the card face up, if this is a tween.
+        pub fn shown(mut self) -> Self {
+            if let Kind::Tween(ref mut tween) = self.kind {
+                tween.shown = true;
+            }
+            self
+        }
+
+        /// Draws the card turned by the given transform, if this is a tween.
+        pub fn turned(mut self, transform: Transform) -> Self {
+            if let Kind::Tween(ref mut tween) = self.kind {
+                tween.transform = transform;
+            }
+            self
+        }
+
+        pub fn duration(mut self, frames: Frames) -> Self {
+            match self.kind {
+                Kind::Tween(ref mut tween) => { tween.duration = frames; },
+                Kind::Wait(ref mut wait) => { *wait = frames; },
+                Kind::Sequence { .. } | Kind::Parallel(_) => {},
+            }
+            self
+        }
+
+        pub fn easing(mut self, easing: Easing) -> Self {
+            if let Kind::Tween(ref mut tween) = self.kind {
+                tween.easing = easing;
+            }
+            self
+        }
+
+        pub fn delayed(self, frames: Frames) -> Self {
+            if frames == 0 {
+                self
+            } else {
+                Self::sequence([Self::wait(frames), self])
+            }
+        }
+
+        /// Adds a callback to run when this completes, after any previously
+        /// added ones.
+        pub fn then(mut self, callback: impl Fn(&mut State, &mut Speaker) + 'static) -> Self {
+            self.on_complete.push(Rc::new(callback));
+            self
+        }
+
+        /// Moves this forward by one frame. Returns whether this is complete,
+        /// having added the callbacks of anything that completed to `completed`.
+        fn advance(&mut self, completed: &mut Vec<Callback>) -> bool {
+            if self.finished {
+                return true
+            }
+
+            self.finished = match self.kind {
+                Kind::Tween(Tween { duration, .. })
+                | Kind::Wait(duration) => {
+                    self.elapsed = self.elapsed.saturating_add(1);
+                    self.elapsed >= duration
+                },
+                Kind::Sequence { ref mut parts, ref mut current } => {
+                    if let Some(part) = parts.get_mut(*current) {
+                        if part.advance(completed) {
+                            *current += 1;
+                        }
+                    }
+                    *current >= parts.len()
+                },
+                Kind::Parallel(ref mut parts) => {
+                    let mut all_done = true;
+                    for part in parts.iter_mut() {
+                        all_done &= part.advance(completed);
+                    }
+                    all_done
+                },
+            };
+
+            if self.finished {
+                completed.append(&mut self.on_complete);
+            }
+
+            self.finished
+        }
+
+        fn for_each_moving_card(&self, f: &mut impl FnMut(&Tween, XY)) {
+            if self.finished {
+                return
+            }
+
+            match self.kind {
+                Kind::Tween(ref tween) => f(tween, tween.at(self.elapsed)),
+                Kind::Wait(_) => {},
+                Kind::Sequence { ref parts, current } => {
+                    if let Some(part) = parts.get(current) {
+                        part.for_each_moving_card(f);
+                    }
+                },
+                Kind::Parallel(ref parts) => {
+                    for part in parts {
+                        part.for_each_moving_card(f);
+                    }
+                },
+            }
+        }
+    }
+
+    /// How quickly the timeline moves along, compared to the frame rate.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum Speed {
+        Slow,
+        #[default]
+        Normal,
+        Fast,
+        /// Everything pending completes in the same frame.
+        Instant,
+    }
+
+    impl Speed {
+        pub fn next(self) -> Self {
+            use Speed::*;
+            match self {
+                Slow => Normal,
+                Normal => Fast,
+                Fast => Instant,
+                Instant => Slow,
+            }
+        }
+
+        pub fn text(self) -> &'static [u8] {
+            use Speed::*;
+            match self {
+                Slow => b"Slow",
+                Normal => b"Normal",
+                Fast => b"Fast",
+                Instant => b"Instant",
+            }
+        }
+
+        /// How many half frames of the timeline pass each frame, or `None`
+        /// for as many as it takes.
+        fn half_frames(self) -> Option<u8> {
+            use Speed::*;
+            match self {
+                Slow => Some(1),
+                Normal => Some(2),
+                Fast => Some(6),
+                Instant => None,
+            }
+        }
+    }
+
+    /// Everything that is currently animating. Nothing pushed here is ever
+    /// dropped before it completes.
+    #[derive(Clone, Default)]
+    pub struct Animations {
+        animations: Vec<Animation>,
+        /// Half a frame left over from last time, when going slowly.
+        leftover_half_frame: bool,
+    }
+
+    impl Animations {
+        pub fn push(&mut self, animation: Animation) {
+            self.animations.push(animation);
+        }
+
+        pub fn all_done(&self) -> bool {
+            self.animations.is_empty()
+        }
+
+        /// Calls `f` with each card that is currently moving, and where it
+        /// currently is.
+        pub fn for_each_moving_card(&self, mut f: impl FnMut(&Tween, XY)) {
+            for animation in &self.animations {
+                animation.for_each_moving_card(&mut f);
+            }
+        }
+
+        /// Moves everything forward by one frame. Returns the callbacks of
+        /// everything that completed, in the order they completed.
+        pub fn advance(&mut self) -> Vec<Callback> {
+            let mut completed = Vec::new();
+
+            self.animations.retain_mut(|animation| !animation.advance(&mut completed));
+
+            completed
+        }
+
+        /// How many times `advance` should be called this frame, at the given
+        /// speed.
+        pub fn advances_this_frame(&mut self, speed: Speed) -> u32 {
+            match speed.half_frames() {
+                Some(half_frames) => {
+                    let half_frames = half_frames + u8::from(self.leftover_half_frame);
+                    self.leftover_half_frame = half_frames % 2 == 1;
+                    u32::from(half_frames / 2)
+                },
+                None => u32::MAX,
+            }
+        }
+    }
 }
+pub use timeline::{Animation, Animations};
+
+pub mod relayout {
+    //! The cards left in a hand sliding over to their new spots in the spread
+    //! when the hand changes size, instead of jumping there. This is kept
+    //! apart from the timeline, so it never holds up input.
+    use super::*;
+    use models::DECK_SIZE;
+    use timeline::{Easing, Frames, Tween};
+
+    /// How long a card takes to slide to its new spot.
+    pub const RELAYOUT_FRAMES: Frames = 12;
+
+    #[derive(Clone, Copy)]
+    struct Slide {
+        from: XY,
+        elapsed: Frames,
+    }
+
+    #[derive(Clone)]
+    struct HandLayout {
+        len: models::HandLen,
+        /// Indexed by card.
+        drawn_at: [Option<XY>; DECK_SIZE as usize],
+        /// Indexed by card.
+        slides: [Option<Slide>; DECK_SIZE as usize],
+    }
+
+    impl Default for HandLayout {
+        fn default() -> Self {
+            Self {
+                len: 0,
+                drawn_at: [None; DECK_SIZE as usize],
+                slides: [None; DECK_SIZE as usize],
+            }
+        }
+    }
 
-impl Animation {
-    pub fn is_done(&self) -> bool {
-        self.at == self.target
+    impl HandLayout {
+        fn position(&self, id: HandId, hand: &Hand, index: CardIndex) -> XY {
+            let target = get_card_position(spread(id), hand.len(), index);
+
+            let Some(card) = hand.get(index) else {
+                return target
+            };
+
+            match self.slides.get(usize::from(card)).copied().flatten() {
+                Some(Slide { from, elapsed }) => Tween {
+                    card,
+                    from,
+                    to: target,
+                    duration: RELAYOUT_FRAMES,
+                    easing: Easing::EaseOut,
+                    shown: false,
+                    transform: turn(id),
+                }.at(elapsed),
+                None => target,
+            }
+        }
     }
 
-    pub fn is_active(&self) -> bool {
-        self.delay == 0
+    #[derive(Clone, Default)]
+    pub struct Relayout([HandLayout; HandId::COUNT as usize]);
+
+    impl Relayout {
+        /// Moves the slides in the given hand forward by one frame, starting
+        /// new ones if the hand has changed size since the last call.
+        pub fn update(&mut self, id: HandId, hand: &Hand) {
+            let layout = &mut self.0[id as usize];
+
+            if hand.len() != layout.len {
+                for card in hand.iter() {
+                    let card_i = usize::from(card);
+                    // Cards that were not drawn in this hand before arrived
+                    // through the timeline, at their new spot already.
+                    if let Some(from) = layout.drawn_at.get(card_i).copied().flatten() {
+                        layout.slides[card_i] = Some(Slide { from, elapsed: 0 });
+                    }
+                }
+                layout.len = hand.len();
+            }
+
+            for slide_opt in layout.slides.iter_mut() {
+                if let Some(slide) = slide_opt {
+                    slide.elapsed += 1;
+                    if slide.elapsed >= RELAYOUT_FRAMES {
+                        *slide_opt = None;
+                    }
+                }
+            }
+
+            layout.drawn_at = [None; DECK_SIZE as usize];
+            for (i, card) in hand.enumerated_iter() {
+                layout.drawn_at[usize::from(card)] = Some(layout.position(id, hand, i));
+            }
+        }
+
+        /// Where to draw the card at the given index in the hand.
+        pub fn position(&self, id: HandId, hand: &Hand, index: CardIndex) -> XY {
+            self.0[id as usize].position(id, hand, index)
+        }
     }
 }
 
@@ -280,16 +753,6 @@ pub enum AfterDiscard {
     PushPlay(Play),
 }
 
-#[derive(Clone, Default)]
-pub enum AnimationAction {
-    #[default]
-    DoNothing,
-    AddToHand(HandId),
-    PerformGameWarden,
-    AddToDiscard(AfterDiscard),
-    AnimateBackToHand(HandId),
-}
-
 mod question {
     use super::*;
 
@@ -529,6 +992,8 @@ pub enum PlayerSelectingSubMenu {
     Anytime(PlayerSelection, AvailablePlayAnytime),
     Message(Vec<u8>),
     DiscardDivineIntervention,
+    Notepad,
+    Arrange,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -542,6 +1007,7 @@ pub enum PlayerAskingSubMenu {
 pub enum CpuMenu {
     #[default]
     Selecting,
+    Searching(Box<search::Search>),
     Asking(Rank, Question),
     DeadInTheWater,
     WaitingForSuccesfulAsk,
@@ -582,6 +1048,55 @@ impl Cards {
         }
     }
 
+    pub fn baskets(&self, id: HandId) -> &Hand {
+        match id {
+            HandId::Player => &self.player_baskets,
+            HandId::Cpu1 => &self.cpu1_baskets,
+            HandId::Cpu2 => &self.cpu2_baskets,
+            HandId::Cpu3 => &self.cpu3_baskets,
+        }
+    }
+
+    pub fn baskets_mut(&mut self, id: HandId) -> &mut Hand {
+        match id {
+            HandId::Player => &mut self.player_baskets,
+            HandId::Cpu1 => &mut self.cpu1_baskets,
+            HandId::Cpu2 => &mut self.cpu2_baskets,
+            HandId::Cpu3 => &mut self.cpu3_baskets,
+        }
+    }
+
+    /// The points the given hand's baskets are worth so far.
+    pub fn points(&self, id: HandId) -> u32 {
+        let cards: Vec<Card> = self.baskets(id).iter().collect();
+
+        // Baskets are always added all at once, so each chunk is one basket.
+        // A basket completed with the Dead Scuba Diver has only four fish in it,
+        // so we go by whichever fish are there.
+        cards.chunks(Suit::COUNT as usize)
+            .filter_map(|basket| basket.iter().find_map(|&card| get_rank(card)))
+            .map(|rank| u32::from(rank.points()))
+            .sum()
+    }
+
+    /// Whether no rank can be made into a basket anymore, which is when the
+    /// game is over. A basket completed with the Dead Scuba Diver leaves its
+    /// fifth fish in play, so we can't wait for all the fish to be in baskets.
+    pub fn no_more_baskets_can_be_made(&self) -> bool {
+        let mut counts = [0u8; Rank::COUNT as usize];
+        for card in self.deck.iter()
+            .chain(self.player.iter())
+            .chain(self.cpu1.iter())
+            .chain(self.cpu2.iter())
+            .chain(self.cpu3.iter()) {
+            if let Some(rank) = get_rank(card) {
+                counts[rank as usize] += 1;
+            }
+        }
+
+        counts.iter().all(|&count| count < Suit::COUNT)
+    }
+
     fn active_count(&self) -> ActiveCardCount {
         let count = self.deck.len()
         + self.player.len()
@@ -733,6 +1248,10 @@ pub enum PlayKind {
         targeting: Targeting,
         predicate: NetPredicate,
     },
+    TheLure {
+        targeting: Targeting,
+        predicate: LurePredicate,
+    },
 }
 
 impl PlayKind {
@@ -741,7 +1260,8 @@ impl PlayKind {
             Self::FishedUnsuccessfully { .. } => false,
             Self::NoFishing { .. }
             | Self::TwoFistedFisherman { .. }
-            | Self::TheNet { .. } => true,
+            | Self::TheNet { .. }
+            | Self::TheLure { .. } => true,
         }
     }
 }
@@ -759,10 +1279,10 @@ impl Play {
     }
 }
 
-// For debugging
-#[allow(dead_code)]
-#[derive(Default, Debug, PartialEq, Eq)]
-enum HardcodedMode {
+/// Particular situations to start a game in. For debugging, and for tests
+/// that check what happens from them.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum HardcodedMode {
     #[default]
     Release,
     PlayerMultipleZingers,
@@ -779,7 +1299,7 @@ enum HardcodedMode {
 }
 use HardcodedMode::*;
 
-const HARDCODED_MODE: HardcodedMode = Cpu1PlayNetPlayerNoFishing;
+const HARDCODED_MODE: HardcodedMode = Release;
 
 #[derive(Clone, Default)]
 pub struct State {
@@ -796,16 +1316,187 @@ pub struct State {
     pub stack: Vec<Play>,
     pub cpu_menu: CpuMenu,
     pub done_something_this_turn: bool,
+    pub cpu_strategies: [CpuStrategy; CpuId::COUNT as usize],
+    pub tutorial: Option<tutorial::Tutorial>,
+    pub stats: Stats,
+    pub notepad: notepad::Notepad,
+    pub show_remaining_cards: bool,
+    pub hand_sort: HandSort,
+    pub relayout: relayout::Relayout,
+    pub animation_speed: timeline::Speed,
+    /// Whether the player is holding down the button to speed through the
+    /// other players' turns.
+    pub fast_forwarding: bool,
+}
+
+/// How the player's hand is kept in order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HandSort {
+    /// The cards stay where they were put, whether that was by being dealt or
+    /// drawn, or by the player moving them.
+    #[default]
+    AsPlaced,
+    Rank,
+    Suit,
+    ZingersFirst,
+    MostCompleteRankFirst,
+}
+
+impl HandSort {
+    fn next(self) -> Self {
+        use HandSort::*;
+        match self {
+            AsPlaced => Rank,
+            Rank => Suit,
+            Suit => ZingersFirst,
+            ZingersFirst => MostCompleteRankFirst,
+            MostCompleteRankFirst => AsPlaced,
+        }
+    }
+
+    fn previous(self) -> Self {
+        use HandSort::*;
+        match self {
+            AsPlaced => MostCompleteRankFirst,
+            Rank => AsPlaced,
+            Suit => Rank,
+            ZingersFirst => Suit,
+            MostCompleteRankFirst => ZingersFirst,
+        }
+    }
+
+    fn text(self) -> &'static [u8] {
+        use HandSort::*;
+        match self {
+            AsPlaced => b"As placed",
+            Rank => b"Rank",
+            Suit => b"Suit",
+            ZingersFirst => b"Zingers first",
+            MostCompleteRankFirst => b"Most complete",
+        }
+    }
+
+    /// The ordering that would put `hand` in this order, if there is one.
+    pub fn ordering(self, hand: &Hand) -> Option<HandOrdering> {
+        // Zingers go after the fish, unless otherwise specified.
+        let rank_key = |card| get_rank(card).map_or(models::Rank::COUNT, |rank| rank as u8);
+        let suit_key = |card| models::get_suit(card).map_or(Suit::COUNT, |suit| suit as u8);
+
+        match self {
+            HandSort::AsPlaced => None,
+            HandSort::Rank => Some(HandOrdering::sorted_by_key(
+                hand,
+                |card| (rank_key(card), suit_key(card), card)
+            )),
+            HandSort::Suit => Some(HandOrdering::sorted_by_key(
+                hand,
+                |card| (suit_key(card), rank_key(card), card)
+            )),
+            HandSort::ZingersFirst => Some(HandOrdering::sorted_by_key(
+                hand,
+                |card| (get_rank(card).is_some(), rank_key(card), suit_key(card), card)
+            )),
+            HandSort::MostCompleteRankFirst => {
+                let mut counts = [0; models::Rank::COUNT as usize];
+                for card in hand.iter() {
+                    if let Some(rank) = get_rank(card) {
+                        counts[rank as usize] += 1;
+                    }
+                }
+
+                Some(HandOrdering::sorted_by_key(
+                    hand,
+                    |card| (
+                        core::cmp::Reverse(get_rank(card).map_or(0, |rank| counts[rank as usize])),
+                        rank_key(card),
+                        suit_key(card),
+                        card,
+                    )
+                ))
+            },
+        }
+    }
+}
+
+/// Puts `hand` in the order given by `sort`, keeping the same card selected.
+/// Draws a card from the deck for `id`. Everyone sees that happen, so they
+/// all know `id` might now have a card they didn't have before.
+fn draw_for(deck: &mut Hand, memories: &mut Memories, id: HandId) -> Option<Card> {
+    let drew = deck.draw();
+
+    if drew.is_some() {
+        memories.drew(id);
+    }
+
+    drew
+}
+
+fn sort_hand(hand: &mut Hand, selected: &mut CardIndex, sort: HandSort) {
+    if let Some(ordering) = sort.ordering(hand) {
+        *selected = ordering.position(*selected);
+        hand.apply_ordering(&ordering);
+    }
+}
+
+/// Moves the selected card in `hand` to `to`, keeping it selected.
+fn move_selected_card(hand: &mut Hand, selected: &mut CardIndex, to: CardIndex) {
+    let mut ordering = HandOrdering::default();
+    ordering.swap(*selected, to);
+
+    hand.apply_ordering(&ordering);
+    *selected = to;
+}
+
+/// Things the player did over the course of a game, to show once it is over.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hints_used: u32,
+}
+
+/// How a Cpu player decides what to do.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CpuStrategy {
+    #[default]
+    Heuristic,
+    /// "Hard+". Decides which card to ask for by searching through many
+    /// possible continuations of the game. The zingers are still played
+    /// according to the heuristics.
+    Search(search::Budget),
+}
+
+impl CpuStrategy {
+    pub fn next(self) -> Self {
+        use CpuStrategy::*;
+        match self {
+            Heuristic => Search(search::Budget::default()),
+            Search(_) => Heuristic,
+        }
+    }
+
+    pub fn text(self) -> &'static [u8] {
+        use CpuStrategy::*;
+        match self {
+            Heuristic => b"Normal",
+            Search(_) => b"Hard+",
+        }
+    }
 }
 
 impl State {
-    pub fn new(
+    pub fn new(seed: Seed) -> State {
+        Self::with_hardcoded_mode(seed, HARDCODED_MODE)
+    }
+
+    /// A new game, set up the way `mode` says to. Some of the modes pick the
+    /// seed themselves.
+    pub fn with_hardcoded_mode(
         #[allow(unused_variables)]
-        mut seed: Seed
+        mut seed: Seed,
+        mode: HardcodedMode,
     ) -> State {
         let mut initial_hand_size: u8 = 8; //16;
 
-        match HARDCODED_MODE {
+        match mode {
             Release => {},
             PlayerMultipleZingers => {
                 // Gives player multiple zingers. (8)
@@ -855,7 +1546,7 @@ impl State {
             .. <_>::default()
         };
 
-        match HARDCODED_MODE {
+        match mode {
             PlayerStuckWithDivineIntervention => {
                 for zinger in models::zingers::ALL {
                     force_into_start_of_hand(
@@ -922,239 +1613,265 @@ impl State {
             | Release => {}
         }
 
-        for card_i in 0..initial_hand_size {
-            // TODO Once starting turn is randomized, deal cards to first player
-            // first.
-            for (id_i, id) in HandId::ALL.into_iter().enumerate() {
-                let card = match state.cards.deck.draw() {
-                    Some(card) => card,
-                    None => continue,
-                };
-
-                let target = get_card_insert_position(
-                    spread(id),
-                    card_i + 1,
-                );
-
-                state.animations.push(Animation {
-                    card,
-                    at: DECK_XY,
-                    target,
-                    action: AnimationAction::AddToHand(id),
-                    delay: card_i
-                        .saturating_mul(HandId::ALL.len() as u8)
-                        .saturating_add(id_i as u8),
-                    .. <_>::default()
-                });
-            }
-        }
+        state.deal(initial_hand_size);
 
         state
     }
 
-    pub fn tick(&mut self, speaker: &mut Speaker) {
-        use core::cmp::{min, Ordering::*};
+    /// A game with a stacked deck, which walks the player through an ask, going
+    /// fishing, making a basket and responding with a zinger.
+    pub fn tutorial() -> State {
+        let mut rng = xs::from_seed(tutorial::SEED);
 
-        let mut push_after = Vec::new();
-        for anim in self.animations.0.iter_mut() {
-            if anim.is_done() { continue }
+        let mut deck = Hand::fresh_deck(&mut rng);
 
-            if anim.delay > 0 {
-                anim.delay -= 1;
-                continue
+        // The deck is drawn from the end, so these go on in reverse order.
+        let stacked = tutorial::AFTER_DEAL.into_iter().rev()
+            .chain(tutorial::DEAL.into_iter().rev().flat_map(|round| round.into_iter().rev()));
+        for card in stacked {
+            let index_opt = deck.iter().position(|c| c == card);
+            if let Some(i) = index_opt {
+                deck.remove(i as CardIndex);
             }
+            deck.push(card);
+        }
 
-            let x_rate = 3;
-            let y_rate = 3;
-            let d_x = match anim.at.x.cmp(&anim.target.x) {
-                Equal => 0,
-                Greater => {
-                    let x_diff = anim.at.x - anim.target.x;
-                    -(min(x_diff.get(), x_rate) as i8)
-                },
-                Less => {
-                    let x_diff = anim.target.x - anim.at.x;
-                    min(x_diff.get(), x_rate) as i8
-                },
-            };
-            let d_y = match anim.at.y.cmp(&anim.target.y) {
-                Equal => 0,
-                Greater => {
-                    let y_diff = anim.at.y - anim.target.y;
-                    -(min(y_diff.get(), y_rate) as i8)
-                },
-                Less => {
-                    let y_diff = anim.target.y - anim.at.y;
-                    min(y_diff.get(), y_rate) as i8
-                },
-            };
+        let mut state = State {
+            rng,
+            cards: Cards {
+                deck,
+                .. <_>::default()
+            },
+            sub_turn_ids: HandId::ALL,
+            // Don't start in a sub turn
+            sub_turn_index: HandId::COUNT + 1,
+            turn_id: HandId::Player,
+            tutorial: Some(<_>::default()),
+            .. <_>::default()
+        };
 
-            anim.at.x = match d_x {
-                d_x if d_x > 0 => anim.at.x.saturating_add(W(d_x as unscaled::Inner)),
-                d_x if d_x < 0 => anim.at.x.saturating_sub(W(d_x.unsigned_abs() as unscaled::Inner)),
-                _ => anim.at.x,
-            };
-            anim.at.y = match d_y {
-                d_y if d_y > 0 => anim.at.y.saturating_add(H(d_y as unscaled::Inner)),
-                d_y if d_y < 0 => anim.at.y.saturating_sub(H(d_y.unsigned_abs() as unscaled::Inner)),
-                _ => anim.at.y,
-            };
+        // So that Cpu 1 is sure to ask the player for something on their turn.
+        state.memories.memory_mut(CpuId::One).known(
+            HandId::Player,
+            tutorial::CPU1_KNOWS_ABOUT,
+        );
 
-            if anim.is_done() {
-                macro_rules! back_to_selecting {
-                    ($id: ident) => ({
-                        let hand = match $id {
-                            HandId::Player => &mut self.cards.player,
-                            HandId::Cpu1 => &mut self.cards.cpu1,
-                            HandId::Cpu2 => &mut self.cards.cpu2,
-                            HandId::Cpu3 => &mut self.cards.cpu3,
-                        };
-
-                        match CpuId::try_from($id) {
-                            Err(_) => {
-                                self.selection.card_index = hand.len() - 1;
-                            },
-                            Ok(_) => {
-                                if matches!(
-                                    self.cpu_menu,
-                                    CpuMenu::WaitingForSuccesfulAsk
-                                    | CpuMenu::WaitingWhenGotWhatWasFishingFor
-                                    | CpuMenu::WaitingWhenPlayedTwoFistedFisherman
-                                ) {
-                                    self.cpu_menu = CpuMenu::Selecting;
-                                    self.done_something_this_turn = true;
-                                }
-                            }
-                        }
-                    })
-                }
+        state.deal(tutorial::DEAL.len() as u8);
 
-                match &anim.action {
-                    AnimationAction::DoNothing => {},
-                    AnimationAction::AddToHand(id) => {
-                        let hand = match id {
-                            HandId::Player => &mut self.cards.player,
-                            HandId::Cpu1 => &mut self.cards.cpu1,
-                            HandId::Cpu2 => &mut self.cards.cpu2,
-                            HandId::Cpu3 => &mut self.cards.cpu3,
-                        };
-
-                        hand.push(anim.card);
-
-                        let baskets = match id {
-                            HandId::Player => &mut self.cards.player_baskets,
-                            HandId::Cpu1 => &mut self.cards.cpu1_baskets,
-                            HandId::Cpu2 => &mut self.cards.cpu2_baskets,
-                            HandId::Cpu3 => &mut self.cards.cpu3_baskets,
-                        };
-
-                        fn remove_basket(hand: &mut Hand) -> Option<Basket> {
-                            let mut indexes = [None; Suit::COUNT as usize];
-
-                            // TODO? Do we care about this being O(n^2), given that
-                            // we know n is bounded by `DECK_SIZE`, and in fact
-                            // would be smaller in practice?
-                            'outer: for first_card in hand.iter() {
-                                let Some(rank) = get_rank(first_card) else {
-                                    continue
-                                };
-                                indexes = [None; Suit::COUNT as usize];
-                                for (card_i, card) in hand.enumerated_iter() {
-                                    match models::get_rank(card) {
-                                        Some(r) if rank == r => {
-                                            for i in 0..Suit::COUNT as usize {
-                                                if indexes[i].is_none() {
-                                                    indexes[i] = Some(card_i);
-                                                    if i >= Suit::COUNT as usize - 1 {
-                                                        break 'outer
-                                                    } else {
-                                                        break
-                                                    }
-                                                }
-                                            }
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
+        state
+    }
 
-                            match indexes {
-                                [Some(a), Some(b), Some(c), Some(d), Some(e)] => {
-                                    const MSG: &str = "remove_basket indexes should be valid!";
-                                    // We assume that the indexes are in ascending
-                                    // order, so removing in reverse order doesn't
-                                    // invalidate any indexes.
-                                    let c1 = hand.remove(e).expect(MSG);
-                                    let c2 = hand.remove(d).expect(MSG);
-                                    let c3 = hand.remove(c).expect(MSG);
-                                    let c4 = hand.remove(b).expect(MSG);
-                                    let c5 = hand.remove(a).expect(MSG);
-
-                                    Some([c1, c2, c3, c4, c5])
-                                }
-                                _ => None,
-                            }
-                        }
+    /// Whether the game has ended, and everything has settled down, so the
+    /// final baskets have landed on their piles and can be counted.
+    pub fn is_over(&self) -> bool {
+        self.animations.all_done()
+        && self.stack.is_empty()
+        && self.cards.no_more_baskets_can_be_made()
+    }
 
-                        while let Some(basket) = remove_basket(hand) {
-                            self.memories.basket_removed(basket);
-                            // TODO? animate gathering together and heading to a
-                            // separate pile? Or maybe poofing in an expolsion of
-                            // particles?
-                            for card in basket {
-                                baskets.push(card);
-                            }
-                        }
+    fn deal(&mut self, initial_hand_size: u8) {
+        for card_i in 0..initial_hand_size {
+            // TODO Once starting turn is randomized, deal cards to first player
+            // first.
+            for (id_i, id) in HandId::ALL.into_iter().enumerate() {
+                let card = match self.cards.deck.draw() {
+                    Some(card) => card,
+                    None => continue,
+                };
 
-                        speaker.request_sfx(SFX::CardPlace);
+                let target = get_card_insert_position(
+                    spread(id),
+                    card_i + 1,
+                );
 
-                        back_to_selecting!(id);
-                    },
-                    AnimationAction::PerformGameWarden => {
-                        // TODO Animate all cards in deck moving to random targets
-                        // away from, then back to DECK_XY.
+                self.animations.push(
+                    Animation::tween(card, DECK_XY, target)
+                        .turned(turn(id))
+                        .delayed(
+                            timeline::Frames::from(card_i)
+                            * timeline::Frames::from(HandId::COUNT)
+                            + id_i as timeline::Frames
+                        )
+                        .then(move |state, speaker| state.add_to_hand(speaker, id, card))
+                );
+            }
+        }
+    }
 
-                        self.cards.deck.push(anim.card);
-                        self.cards.deck.shuffle(&mut self.rng);
-                    }
-                    AnimationAction::AddToDiscard(after_discard) => {
-                        self.cards.discard.push(anim.card);
-
-                        speaker.request_sfx(SFX::CardPlace);
-
-                        match after_discard {
-                            AfterDiscard::BackToSelecting(id)
-                                => back_to_selecting!(id),
-                            AfterDiscard::Nothing => {}
-                            AfterDiscard::PushPlay(play) => {
-                                self.stack.push(play.clone());
-                                allow_to_respond!(self);
+    /// Moves the timeline along by however much the current speed calls for.
+    pub fn tick(&mut self, speaker: &mut Speaker) {
+        let speed = if self.fast_forwarding {
+            core::cmp::max(self.animation_speed, timeline::Speed::Fast)
+        } else {
+            self.animation_speed
+        };
+
+        let mut advances = self.animations.advances_this_frame(speed);
+        // Callbacks can push more animations, which at `Speed::Instant` we
+        // want to complete this frame too.
+        while advances > 0 && !self.animations.all_done() {
+            for callback in self.animations.advance() {
+                callback(self, speaker);
+            }
+            advances -= 1;
+        }
+    }
+
+    fn back_to_selecting(&mut self, id: HandId) {
+        match CpuId::try_from(id) {
+            Err(_) => {
+                self.selection.card_index = self.cards.hand(id).len().saturating_sub(1);
+            },
+            Ok(_) => {
+                if matches!(
+                    self.cpu_menu,
+                    CpuMenu::WaitingForSuccesfulAsk
+                    | CpuMenu::WaitingWhenGotWhatWasFishingFor
+                    | CpuMenu::WaitingWhenPlayedTwoFistedFisherman
+                ) {
+                    self.cpu_menu = CpuMenu::Selecting;
+                    self.done_something_this_turn = true;
+                }
+            }
+        }
+    }
+
+    /// Puts the card in the hand, then moves any baskets that completes out of it,
+    /// onto the hand's basket pile.
+    fn add_to_hand(&mut self, speaker: &mut Speaker, id: HandId, card: Card) {
+        fn basket_indexes(hand: &Hand) -> Option<[CardIndex; Suit::COUNT as usize]> {
+            let mut indexes = [None; Suit::COUNT as usize];
+
+            // TODO? Do we care about this being O(n^2), given that
+            // we know n is bounded by `DECK_SIZE`, and in fact
+            // would be smaller in practice?
+            'outer: for first_card in hand.iter() {
+                let Some(rank) = get_rank(first_card) else {
+                    continue
+                };
+                indexes = [None; Suit::COUNT as usize];
+                for (card_i, card) in hand.enumerated_iter() {
+                    match models::get_rank(card) {
+                        Some(r) if rank == r => {
+                            for (i, index) in indexes.iter_mut().enumerate() {
+                                if index.is_none() {
+                                    *index = Some(card_i);
+                                    if i >= Suit::COUNT as usize - 1 {
+                                        break 'outer
+                                    } else {
+                                        break
+                                    }
+                                }
                             }
                         }
-                    }
-                    AnimationAction::AnimateBackToHand(id) => {
-                        let id = *id;
-                        let target = get_card_insert_position(
-                            spread(id),
-                            self.cards.hand(id).len(),
-                        );
-
-                        push_after.push(Animation {
-                            card: anim.card,
-                            at: anim.target,
-                            target,
-                            action: AnimationAction::AddToHand(id),
-                            .. <_>::default()
-                        })
+                        _ => {}
                     }
                 }
             }
+
+            match indexes {
+                [Some(a), Some(b), Some(c), Some(d), Some(e)] => Some([a, b, c, d, e]),
+                _ => None,
+            }
+        }
+
+        self.cards.hand_mut(id).push(card);
+
+        while let Some(indexes) = basket_indexes(self.cards.hand(id)) {
+            const MSG: &str = "basket_indexes should be valid!";
+
+            let hand = self.cards.hand_mut(id);
+            let len = hand.len();
+
+            let mut basket: Basket = [0; Suit::COUNT as usize];
+            let mut froms = [DECK_XY; Suit::COUNT as usize];
+            // We assume that the indexes are in ascending
+            // order, so removing in reverse order doesn't
+            // invalidate any indexes.
+            for (i, &index) in indexes.iter().enumerate().rev() {
+                froms[i] = get_card_position(spread(id), len, index);
+                basket[i] = hand.remove(index).expect(MSG);
+            }
+
+            self.memories.basket_removed(basket);
+
+            self.animations.push(gather_basket(id, basket, froms));
+        }
+
+        speaker.request_sfx(SFX::CardPlace);
+
+        self.back_to_selecting(id);
+    }
+
+    /// Shuffles the given card into the deck, and shows the deck's cards
+    /// scattering to random spots around it, then gathering back.
+    fn shuffle_into_deck(&mut self, card: Card) {
+        use timeline::{Easing, MOVE_FRAMES};
+
+        self.cards.deck.push(card);
+        self.cards.deck.shuffle(&mut self.rng);
+
+        let x_range = u32::from(DECK_XY.x.get() - CARD_WIDTH.get())
+            ..u32::from(DECK_XY.x.get() + CARD_WIDTH.get());
+        let y_range = u32::from(DECK_XY.y.get() - CARD_HEIGHT.get() / 2)
+            ..u32::from(DECK_XY.y.get() + CARD_HEIGHT.get() / 2);
+
+        let mut parts = Vec::with_capacity(usize::from(self.cards.deck.len()));
+        for card in self.cards.deck.iter() {
+            let to = XY {
+                x: X(xs::range(&mut self.rng, x_range.clone()) as unscaled::Inner),
+                y: Y(xs::range(&mut self.rng, y_range.clone()) as unscaled::Inner),
+            };
+
+            parts.push(Animation::sequence([
+                Animation::tween(card, DECK_XY, to)
+                    .duration(MOVE_FRAMES / 2),
+                Animation::tween(card, to, DECK_XY)
+                    .duration(MOVE_FRAMES / 2)
+                    .easing(Easing::EaseIn),
+            ]));
         }
 
-        for anim in push_after {
-            self.animations.push(anim);
+        self.animations.push(
+            Animation::parallel(parts)
+                .then(|_, speaker| speaker.request_sfx(SFX::CardPlace))
+        );
+    }
+
+    fn add_to_discard(
+        &mut self,
+        speaker: &mut Speaker,
+        card: Card,
+        after_discard: &AfterDiscard,
+    ) {
+        self.cards.discard.push(card);
+
+        speaker.request_sfx(SFX::CardPlace);
+
+        match after_discard {
+            AfterDiscard::BackToSelecting(id)
+                => self.back_to_selecting(*id),
+            AfterDiscard::Nothing => {}
+            AfterDiscard::PushPlay(play) => {
+                self.stack.push(play.clone());
+                allow_to_respond!(self);
+            }
         }
     }
+
+    /// Sends a card that was being looked at back to the hand it came from.
+    fn animate_back_to_hand(&mut self, id: HandId, card: Card, at: XY) {
+        let target = get_card_insert_position(
+            spread(id),
+            self.cards.hand(id).len(),
+        );
+
+        self.animations.push(
+            Animation::tween(card, at, target)
+                .turned(turn(id))
+                .then(move |state, speaker| state.add_to_hand(speaker, id, card))
+        );
+    }
 }
 
 mod ui {
@@ -1187,6 +1904,9 @@ mod ui {
         pub active: Id,
         pub hot: Id,
         pub next_hot: Id,
+        /// Set when a quick select's chevron is clicked, which should be
+        /// treated the same as pressing that direction.
+        pub pointer_dir: Option<Dir>,
     }
 
     impl Context {
@@ -1207,6 +1927,13 @@ mod ui {
                 self.hot = self.next_hot;
             }
             self.next_hot = Id::Zero;
+            self.pointer_dir = None;
+        }
+
+        /// The direction pressed this frame, whether with the buttons, or by
+        /// clicking on a chevron.
+        pub fn dir_repeated_this_frame(&self, input: Input) -> Option<Dir> {
+            input.dir_repeated_this_frame().or(self.pointer_dir)
         }
     }
 
@@ -1219,18 +1946,31 @@ mod ui {
     pub(crate) fn button_press<'commands, 'ctx, 'speaker>(
         group: &mut Group<'commands, 'ctx, 'speaker>,
         id: Id,
+        rect: unscaled::Rect,
     ) -> bool {
         let mut output = false;
 
+        let pointer_is_over = group.input.pointer_is_over(rect);
+        let pointer_pressed = pointer_is_over && group.input.pointer_pressed_this_frame();
+
+        // Only when the pointer moves, so it doesn't fight with the buttons.
+        if pointer_is_over && group.input.pointer_moved_this_frame() {
+            group.ctx.set_next_hot(id);
+        }
+
         if group.ctx.active == id {
             if group.input.released_this_frame(Button::A) {
                 output = group.ctx.hot == id;
 
+                group.ctx.set_not_active();
+            } else if group.input.pointer_released_this_frame() {
+                output = pointer_is_over;
+
                 group.ctx.set_not_active();
             }
             group.ctx.set_next_hot(id);
-        } else if group.ctx.hot == id {
-            if group.input.pressed_this_frame(Button::A) {
+        } else if group.ctx.hot == id || pointer_pressed {
+            if group.input.pressed_this_frame(Button::A) || pointer_pressed {
                 group.ctx.set_active(id);
                 group.speaker.request_sfx(SFX::ButtonPress);
             }
@@ -1247,9 +1987,10 @@ mod ui {
         use gfx::NineSlice as ns;
         let id = spec.id;
 
-        let result = button_press(group, id);
+        let result = button_press(group, id, spec.rect);
 
-        if group.ctx.active == id && group.input.gamepad.contains(Button::A) {
+        if group.ctx.active == id
+        && (group.input.gamepad.contains(Button::A) || group.input.pointer.down) {
             group.commands.draw_nine_slice(ns::ButtonPressed, spec.rect);
         } else if group.ctx.hot == id {
             group.commands.draw_nine_slice(ns::ButtonHot, spec.rect);
@@ -1279,6 +2020,44 @@ mod ui {
         rect: Rect,
         id: Id,
     ) {
+        let x = (rect.x + (rect.w / 2)) - (gfx::CHEVRON_W / 2);
+
+        let up_rect = Rect {
+            x: rect.x,
+            y: rect.y,
+            w: rect.w,
+            h: gfx::CHEVRON_H,
+        };
+        let down_rect = Rect {
+            x: rect.x,
+            y: rect.y + rect.h - gfx::CHEVRON_H,
+            w: rect.w,
+            h: gfx::CHEVRON_H,
+        };
+
+        if group.input.pointer_moved_this_frame()
+        && group.input.pointer_is_over(rect) {
+            group.ctx.set_next_hot(id);
+        }
+
+        if group.input.pointer_pressed_this_frame() {
+            let clicked_dir = if group.input.pointer_is_over(up_rect) {
+                Some(Dir::Up)
+            } else if group.input.pointer_is_over(down_rect) {
+                Some(Dir::Down)
+            } else {
+                None
+            };
+
+            if let Some(dir) = clicked_dir {
+                // The caller decides what a direction does based on what is
+                // hot, so this needs to be hot right away.
+                group.ctx.hot = id;
+                group.ctx.set_next_hot(id);
+                group.ctx.pointer_dir = Some(dir);
+            }
+        }
+
         let mut highlighted = gfx::Highlighted::No;
         if group.ctx.active == id
         || group.ctx.hot == id {
@@ -1286,8 +2065,6 @@ mod ui {
             highlighted = gfx::Highlighted::Yes;
         }
 
-        let x = (rect.x + (rect.w / 2)) - (gfx::CHEVRON_W / 2);
-
         group.commands.draw_chevron(
             XY {
                 x,
@@ -1679,46 +2456,17 @@ fn anytime_play(
         // the same as the game warden could be described.
         macro_rules! play_perhaps {
             ($card: ident) => {
-                let mut others = hand_id.besides();
-                xs::shuffle(rng, &mut others);
-
-                for target in others {
-                    // Note: It's not fair to look at other's cards besides
-                    // counting how many of them there are.
-                    let len = cards.hand(target).len();
-                    if len == 1 {
-                        return Some(AnytimePlay {
-                            selection: AnytimePlaySelection::$card(
-                                target
-                            ),
-                        });
-                    }
-                }
-
-                if let ActiveCardCount::VeryFew = cards.active_count() {
-                    for target in others {
-                        // Note: It's not fair to look at other's cards besides
-                        // counting how many of them there are.
-                        if !cards.hand(target).is_empty() {
-                            return Some(AnytimePlay {
-                                selection: AnytimePlaySelection::$card(
-                                    target
-                                ),
-                            });
-                        }
-                    }
-                }
-
-                for target in others {
-                    if let Some(_) = memories
-                        .memory(responder_id)
-                        .likely_to_fill_basket_soon(target) {
-                        return Some(AnytimePlay {
-                            selection: AnytimePlaySelection::$card(
-                                target
-                            ),
-                        });
-                    }
+                if let Some(target) = warden_or_boat_target(
+                    rng,
+                    cards,
+                    memories,
+                    responder_id,
+                ) {
+                    return Some(AnytimePlay {
+                        selection: AnytimePlaySelection::$card(
+                            target
+                        ),
+                    });
                 }
             }
         }
@@ -1810,13 +2558,13 @@ fn anytime_play(
                     },
                     ..
                 })
-                /*| Some(Play {
+                | Some(Play {
                     kind: PlayKind::TheLure {
-                        source,
+                        targeting: Targeting { source, .. },
                         ..
                     },
                     ..
-                })*/ => {
+                }) => {
                     if *source == hand_id {
                         // Don't cancel our own play.
                     } else if should_shed_zingers(
@@ -1848,6 +2596,44 @@ fn anytime_play(
     None
 }
 
+/// Returns who, if anyone, it seems like a good idea to use The Game Warden or
+/// the Glass Bottom Boat on.
+fn warden_or_boat_target(
+    rng: &mut Xs,
+    cards: &Cards,
+    memories: &Memories,
+    own_id: CpuId,
+) -> Option<HandId> {
+    let mut others = HandId::from(own_id).besides();
+    xs::shuffle(rng, &mut others);
+
+    for target in others {
+        // Note: It's not fair to look at other's cards besides
+        // counting how many of them there are.
+        let len = cards.hand(target).len();
+        if len == 1 {
+            return Some(target);
+        }
+    }
+
+    if let ActiveCardCount::VeryFew = cards.active_count() {
+        for target in others {
+            // Note: It's not fair to look at other's cards besides
+            // counting how many of them there are.
+            if !cards.hand(target).is_empty() {
+                return Some(target);
+            }
+        }
+    }
+
+    others.into_iter().find(|&target| {
+        memories
+            .memory(own_id)
+            .likely_to_fill_basket_soon(target)
+            .is_some()
+    })
+}
+
 fn should_play_super_ask(
     cards: &Cards,
     hand: &Hand,
@@ -1868,6 +2654,83 @@ fn should_play_super_ask(
     }
 }
 
+fn cpu_net_choice(
+    rng: &mut Xs,
+    hand: &Hand,
+    memory: &memories::Memory,
+    own_id: CpuId,
+    wants_super_ask: bool,
+) -> Option<(HandId, NetPredicate)> {
+    let hand_id = HandId::from(own_id);
+
+    match memory.best_net_predicate(hand_id) {
+        Some((predicate, target)) => {
+            // Scooping a card out of a basket someone else is about to complete
+            // is worth a zinger, even if we weren't planning on using it yet.
+            let is_worth_it = wants_super_ask || match predicate {
+                NetPredicate::Rank(rank) => memory.is_likely_to_fill_rank_soon(target, rank),
+                NetPredicate::Suit(_) => false,
+            };
+
+            if is_worth_it {
+                Some((target, predicate))
+            } else {
+                None
+            }
+        },
+        None if wants_super_ask => {
+            // We have nothing to go on, so at least ask for a rank that gets us
+            // closer to a basket.
+            let rank = hand.iter().find_map(get_rank)?;
+
+            let besides = hand_id.besides();
+            let target = besides[xs::range(rng, 0..besides.len() as u32) as usize];
+
+            Some((target, NetPredicate::Rank(rank)))
+        },
+        None => None,
+    }
+}
+
+fn cpu_lure_choice(
+    rng: &mut Xs,
+    hand: &Hand,
+    memory: &memories::Memory,
+    own_id: CpuId,
+    wants_super_ask: bool,
+) -> Option<(HandId, LurePredicate)> {
+    let hand_id = HandId::from(own_id);
+
+    match memory.best_lure_predicate(hand, hand_id) {
+        Some((predicate, target)) => {
+            let is_worth_it = wants_super_ask
+                || memory.is_likely_to_fill_rank_soon(target, predicate.rank);
+
+            if is_worth_it {
+                Some((target, predicate))
+            } else {
+                None
+            }
+        },
+        None if wants_super_ask => {
+            // We have nothing to go on, so at least ask for a card that gets us
+            // closer to a basket.
+            let rank = hand.iter().find_map(get_rank)?;
+
+            let mut suits = Suit::ALL;
+            xs::shuffle(rng, &mut suits);
+            let suit = suits.into_iter()
+                .find(|&suit| !hand.contains(fish_card(rank, suit)))?;
+
+            let besides = hand_id.besides();
+            let target = besides[xs::range(rng, 0..besides.len() as u32) as usize];
+
+            Some((target, LurePredicate { rank, suit }))
+        },
+        None => None,
+    }
+}
+
 fn should_shed_zingers(
     cards: &Cards,
     hand: &Hand,
@@ -1984,14 +2847,22 @@ fn discard_two_fisted_fisherman(
 fn discard_lure(
     cards: &mut Cards,
     animations: &mut Animations,
-    source: HandId,
+    targeting: Targeting,
+    predicate: LurePredicate
 ) {
     discard_given_card(
         cards,
         animations,
-        source,
+        targeting.source,
         zingers::THE_LURE,
-        AfterDiscard::Nothing,
+        AfterDiscard::PushPlay(Play {
+            sub_turn_ids: targeting.source.next_to_current(),
+            sub_turn_index: 0,
+            kind: PlayKind::TheLure {
+                targeting,
+                predicate,
+            }
+        }),
     )
 }
 
@@ -2041,14 +2912,11 @@ fn discard_given_card(
                 i,
             );
 
-            animations.push(Animation {
-                card,
-                at,
-                target: DISCARD_XY,
-                action: AnimationAction::AddToDiscard(after_discard),
-                shown: true,
-                .. <_>::default()
-            });
+            animations.push(
+                Animation::tween(card, at, DISCARD_XY)
+                    .shown()
+                    .then(move |state, speaker| state.add_to_discard(speaker, card, &after_discard))
+            );
 
             return
         }
@@ -2102,6 +2970,7 @@ fn do_play_anytime_menu(
     cards: &mut Cards,
     animations: &mut Animations,
     rng: &mut Xs,
+    memories: &mut Memories,
     player_selection: &mut PlayerSelection,
     available: AvailablePlayAnytime,
 ) -> AnytimeOutcome {
@@ -2391,13 +3260,14 @@ fn do_play_anytime_menu(
                 // Cpu player's hand. This is a separate copy.
                 player_selection.viewing = Some(card);
 
-                animations.push(Animation {
-                    card,
-                    at,
-                    target: in_front_of(HandId::Player),
-                    action: AnimationAction::AnimateBackToHand(target),
-                    .. <_>::default()
-                });
+                memories.player_mut().known(target, card);
+
+                let looking_at = in_front_of(HandId::Player);
+
+                animations.push(
+                    Animation::tween(card, at, looking_at)
+                        .then(move |state, _| state.animate_back_to_hand(target, card, looking_at))
+                );
 
                 discard_glass_bottom_boat(
                     cards,
@@ -2421,7 +3291,7 @@ fn do_play_anytime_menu(
     } else if group.input.pressed_this_frame(Button::B) {
         // TODO? Separate decline button?
         player_selection.declined = true;
-    } else if let Some(dir) = group.input.dir_pressed_this_frame() {
+    } else if let Some(dir) = group.ctx.dir_repeated_this_frame(group.input) {
         let old_el = match group.ctx.hot {
             AnytimeCard => Some(Section::Card),
             CpuIdSelect
@@ -2602,6 +3472,7 @@ pub fn update_and_render(
     commands: &mut Commands,
     state: &mut State,
     input: Input,
+    bindings: &Bindings,
     speaker: &mut Speaker
 ) {
     macro_rules! new_group {
@@ -2617,6 +3488,9 @@ pub fn update_and_render(
 
     state.ctx.frame_init();
 
+    state.fast_forwarding = state.turn_id != HandId::Player
+        && input.gamepad.contains(Button::START);
+
     state.tick(speaker);
 
     if !state.cards.deck.is_empty() {
@@ -2627,17 +3501,12 @@ pub fn update_and_render(
         commands.draw_card(last, DISCARD_XY);
     }
 
-    for anim in state.animations.iter() {
-        if anim.is_active() {
-            if anim.shown
-            // This clause is just for debugging and should be removable later
-            || HARDCODED_MODE != HardcodedMode::Release
-            {
-                commands.draw_card(anim.card, anim.at);
-            } else {
-                commands.draw_card_back(anim.at);
-            }
-        }
+    for id in HandId::ALL {
+        draw_basket_pile(commands, &state.cards, id);
+    }
+
+    for id in HandId::ALL {
+        state.relayout.update(id, state.cards.hand(id));
     }
 
     for id in HandId::CPUS.into_iter() {
@@ -2647,17 +3516,19 @@ pub fn update_and_render(
         if cfg!(debug_assertions)
         || HARDCODED_MODE != HardcodedMode::Release {
             for (i, card) in hand.enumerated_iter() {
-                commands.draw_card(
+                commands.draw_card_turned(
                     card,
-                    get_card_position(spread(id), len, i)
+                    state.relayout.position(id, hand, i),
+                    turn(id),
                 );
             }
             continue
         }
 
         for i in 0..len {
-            commands.draw_card_back(
-                get_card_position(spread(id), len, i)
+            commands.draw_card_back_turned(
+                state.relayout.position(id, hand, i),
+                turn(id),
             );
         }
     }
@@ -2676,15 +3547,15 @@ pub fn update_and_render(
 
             commands.draw_card(
                 card,
-                get_card_position(spread(id), len, i)
+                state.relayout.position(id, hand, i)
             );
         }
 
         if let Some(player_card) = hand.get(state.selection.card_index)
         {
-            let selected_pos = get_card_position(
-                spread(id),
-                len,
+            let selected_pos = state.relayout.position(
+                id,
+                hand,
                 state.selection.card_index
             );
 
@@ -2697,7 +3568,22 @@ pub fn update_and_render(
         }
     }
 
-    if state.animations.all_done() {
+    // Drawn over the hands, so cards passing over them on the way to a
+    // basket pile can still be seen.
+    state.animations.for_each_moving_card(|tween, at| {
+        if tween.shown
+        // This clause is just for debugging and should be removable later
+        || HARDCODED_MODE != HardcodedMode::Release
+        {
+            commands.draw_card_turned(tween.card, at, tween.transform);
+        } else {
+            commands.draw_card_back_turned(at, tween.transform);
+        }
+    });
+
+    if state.is_over() {
+        draw_game_over(commands, &state.cards, state.stats);
+    } else if state.animations.all_done() {
         // TODO Look at sub_turn_ids on top of the stack if there are any. See note below.
         match {
             match state.stack.last() {
@@ -2731,6 +3617,7 @@ pub fn update_and_render(
                                     &mut state.cards,
                                     &mut state.animations,
                                     &mut state.rng,
+                                    &mut state.memories,
                                     &mut state.selection.player_selection,
                                     available,
                                 ) {
@@ -2768,39 +3655,25 @@ pub fn update_and_render(
                                         }
                                     },
                                     AnytimePlaySelection::GlassBottomBoat(target) => {
-                                        let target_hand = state.cards.hand_mut(target);
-                                        let i = xs::range(&mut state.rng, 0..(target_hand.len() as u32)) as _;
-                                        let card = target_hand.remove(i).expect("hand should have already been checked to see if it was not empty!");
-
-                                        state.memories.memory_mut(source).known(target, card);
-
-                                        let at = get_card_position(
-                                            spread(target),
-                                            target_hand.len(),
-                                            i,
-                                        );
-
-                                        state.animations.push(Animation {
-                                            card,
-                                            at,
-                                            target: in_front_of(responder_id),
-                                            action: AnimationAction::AnimateBackToHand(target),
-                                            .. <_>::default()
-                                        });
-
-                                        discard_glass_bottom_boat(
+                                        if let Some(()) = cpu_perform_glass_bottom_boat(
                                             &mut state.cards,
                                             &mut state.animations,
-                                            source.into()
-                                        );
-
-                                        Selection::Response(())
-                                    },
-                                    AnytimePlaySelection::DeadScubaDiver(almost_basket, scuba_i) => {
-                                        play_dead_scuba_diver(
-                                            &mut state.cards,
-                                            responder_id,
-                                            almost_basket,
+                                            &mut state.rng,
+                                            &mut state.memories,
+                                            source,
+                                            target,
+                                        ) {
+                                            Selection::Response(())
+                                        } else {
+                                            debug_assert!(false, "cpu_perform_glass_bottom_boat failed");
+                                            Selection::Nothing
+                                        }
+                                    },
+                                    AnytimePlaySelection::DeadScubaDiver(almost_basket, scuba_i) => {
+                                        play_dead_scuba_diver(
+                                            &mut state.cards,
+                                            responder_id,
+                                            almost_basket,
                                             scuba_i
                                         );
                                         Selection::Response(())
@@ -2863,6 +3736,20 @@ pub fn update_and_render(
                         // Since the stack is empty, the turn_id player gets to play
                         match CpuId::try_from(state.turn_id) {
                             Err(()) => {
+                                if let PlayerMenu::Selecting {
+                                    sub_menu: PlayerSelectingSubMenu::Root,
+                                } = state.selection.player_menu {
+                                    // Cards might be on their way to or from the
+                                    // hand, so wait until they have arrived.
+                                    if state.animations.all_done() {
+                                        sort_hand(
+                                            &mut state.cards.player,
+                                            &mut state.selection.card_index,
+                                            state.hand_sort,
+                                        );
+                                    }
+                                }
+
                                 let selected = state.selection.card_index;
                                 let menu = &mut state.selection.player_menu;
                                 let hand = &state.cards.player;
@@ -2876,9 +3763,9 @@ pub fn update_and_render(
                                         PlayerMenu::Selecting {
                                             ref mut sub_menu,
                                         } => {
-                                            let selected_pos = get_card_position(
-                                                spread(id),
-                                                len,
+                                            let selected_pos = state.relayout.position(
+                                                id,
+                                                hand,
                                                 selected
                                             );
 
@@ -2891,19 +3778,69 @@ pub fn update_and_render(
 
                                             match sub_menu {
                                                 PlayerSelectingSubMenu::Root => {
-                                                    if input.pressed_this_frame(Button::LEFT) {
+                                                    if state.show_remaining_cards {
+                                                        if let Some(rank) = get_rank(player_card) {
+                                                            remaining::render(
+                                                                commands,
+                                                                &state.cards,
+                                                                &state.memories,
+                                                                rank,
+                                                            );
+                                                        }
+                                                    }
+
+                                                    let pointed = pointed_player_card(
+                                                        &state.cards.player,
+                                                        &state.relayout,
+                                                        selected,
+                                                        input,
+                                                    );
+                                                    if let (Some(i), true) = (pointed, input.pointer_moved_this_frame()) {
+                                                        state.selection.card_index = i;
+                                                    }
+                                                    // Touching a card selects it, so it takes a second
+                                                    // touch to play it.
+                                                    let clicked = pointed == Some(selected)
+                                                        && input.pointer_pressed_this_frame();
+
+                                                    if input.repeated_this_frame(Button::LEFT) {
                                                         state.selection.card_index = if selected > 0 {
                                                             selected - 1
                                                         } else {
                                                             state.cards.player.len().saturating_sub(1)
                                                         };
-                                                    } else if input.pressed_this_frame(Button::RIGHT) {
+                                                    } else if input.repeated_this_frame(Button::RIGHT) {
                                                         state.selection.card_index = if selected < state.cards.player.len().saturating_sub(1) {
                                                             selected + 1
                                                         } else {
                                                             0
                                                         };
-                                                    } else if input.pressed_this_frame(Button::A) {
+                                                    } else if input.pressed_this_frame(Button::UP) {
+                                                        state.show_remaining_cards = !state.show_remaining_cards;
+                                                    } else if input.pressed_this_frame(Button::DOWN) {
+                                                        *sub_menu = PlayerSelectingSubMenu::Arrange;
+                                                    } else if input.pressed_this_frame(Button::B) {
+                                                        *sub_menu = PlayerSelectingSubMenu::Notepad;
+                                                    } else if input.pressed_this_frame(Button::START) {
+                                                        let hint = state.memories.player().hint(
+                                                            &state.cards.player,
+                                                            HandId::Player,
+                                                        );
+
+                                                        if let Some(hint) = hint {
+                                                            state.stats.hints_used += 1;
+
+                                                            // Point out a card that can be used to ask.
+                                                            if let Some(i) = state.cards.player.iter()
+                                                                .position(|card| get_rank(card) == Some(hint.rank)) {
+                                                                state.selection.card_index = i as CardIndex;
+                                                            }
+                                                        }
+
+                                                        *sub_menu = PlayerSelectingSubMenu::Message(
+                                                            hint_message(hint)
+                                                        );
+                                                    } else if input.pressed_this_frame(Button::A) || clicked {
                                                         if !state.cards.player.is_empty() {
                                                             let player_card = state.cards.player.get(selected)
                                                                 .expect("selected index should always be valid");
@@ -3025,6 +3962,7 @@ pub fn update_and_render(
                                                         &mut state.cards,
                                                         &mut state.animations,
                                                         &mut state.rng,
+                                                        &mut state.memories,
                                                         player_selection,
                                                         *available
                                                     ) {
@@ -3116,6 +4054,81 @@ pub fn update_and_render(
                                                         // do nothing
                                                     }
                                                 }
+                                                PlayerSelectingSubMenu::Arrange => {
+                                                    commands.draw_nine_slice(
+                                                        gfx::NineSlice::Window,
+                                                        ARRANGE_WINDOW
+                                                    );
+
+                                                    let base_xy = ARRANGE_WINDOW.xy()
+                                                        + WINDOW_CONTENT_OFFSET
+                                                        + WINDOW_CONTENT_OFFSET;
+
+                                                    commands.print(
+                                                        b"Order by:",
+                                                        base_xy,
+                                                        WHITE,
+                                                    );
+                                                    commands.print(
+                                                        state.hand_sort.text(),
+                                                        base_xy + gfx::CHAR_ADVANCE_H.get(),
+                                                        WHITE,
+                                                    );
+                                                    commands.print(
+                                                        &keys::with_keys(
+                                                            b"{Up}/{Down}: Order\n{Left}/{Right}:\n  Move card\n{Confirm}/{Cancel}: Done",
+                                                            bindings,
+                                                        ),
+                                                        base_xy + gfx::CHAR_ADVANCE_H.get() * 3,
+                                                        WHITE,
+                                                    );
+
+                                                    let last = len.saturating_sub(1);
+
+                                                    if input.repeated_this_frame(Button::UP) {
+                                                        state.hand_sort = state.hand_sort.previous();
+                                                    } else if input.repeated_this_frame(Button::DOWN) {
+                                                        state.hand_sort = state.hand_sort.next();
+                                                    } else if input.repeated_this_frame(Button::LEFT)
+                                                    && selected > 0 {
+                                                        state.hand_sort = HandSort::AsPlaced;
+                                                        move_selected_card(
+                                                            &mut state.cards.player,
+                                                            &mut state.selection.card_index,
+                                                            selected - 1,
+                                                        );
+                                                    } else if input.repeated_this_frame(Button::RIGHT)
+                                                    && selected < last {
+                                                        state.hand_sort = HandSort::AsPlaced;
+                                                        move_selected_card(
+                                                            &mut state.cards.player,
+                                                            &mut state.selection.card_index,
+                                                            selected + 1,
+                                                        );
+                                                    } else if input.pressed_this_frame(Button::A)
+                                                    || input.pressed_this_frame(Button::B) {
+                                                        *sub_menu = PlayerSelectingSubMenu::Root;
+                                                    }
+
+                                                    if state.animations.all_done() {
+                                                        sort_hand(
+                                                            &mut state.cards.player,
+                                                            &mut state.selection.card_index,
+                                                            state.hand_sort,
+                                                        );
+                                                    }
+                                                }
+                                                PlayerSelectingSubMenu::Notepad => {
+                                                    if state.notepad.update_and_render(
+                                                        commands,
+                                                        &state.cards,
+                                                        &state.memories,
+                                                        input,
+                                                        bindings,
+                                                    ) {
+                                                        *sub_menu = PlayerSelectingSubMenu::Root;
+                                                    }
+                                                }
                                             }
                                         },
                                         PlayerMenu::Net {
@@ -3202,7 +4215,7 @@ pub fn update_and_render(
                                             } else if input.pressed_this_frame(Button::B) {
                                                 state.selection.card_index = selected;
                                                 state.selection.player_menu = PlayerMenu::default();
-                                            } else if let Some(dir) = input.dir_pressed_this_frame() {
+                                            } else if let Some(dir) = state.ctx.dir_repeated_this_frame(input) {
                                                 const GRID_LEN: usize = 3;
 
                                                 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -3337,129 +4350,19 @@ pub fn update_and_render(
                                                     text: b"Submit",
                                                 }
                                             ) {
-                                                macro_rules! p_net_handle_negative_response {
-                                                    () => {
-                                                        let player_len = state.cards.player.len();
-
-                                                        let drew = state.cards.deck.draw();
-
-                                                        if let Some(card) = drew {
-                                                            let at = DECK_XY;
-
-                                                            let target = get_card_insert_position(
-                                                                spread(HandId::Player),
-                                                                player_len
-                                                            );
-
-                                                            state.animations.push(Animation {
-                                                                card,
-                                                                at,
-                                                                target,
-                                                                action: AnimationAction::AddToHand(HandId::Player),
-                                                                .. <_>::default()
-                                                            });
-                                                        }
-
-                                                        // This card counts as a turn, so just go on to the next turn.
-                                                        to_next_turn!(state);
-                                                    }
-                                                }
-
                                                 discard_lure(
                                                     &mut state.cards,
                                                     &mut state.animations,
-                                                    HandId::Player
+                                                    Targeting {
+                                                        source: HandId::Player,
+                                                        target: (*target).into(),
+                                                    },
+                                                    *predicate,
                                                 );
-
-                                                let target_hand_id = (*target).into();
-                                                if state.cards.hand(target_hand_id)
-                                                    .contains(zingers::NO_FISHING)
-                                                && should_use_no_fishing_against(
-                                                    state.memories.memory(*target),
-                                                    state.cards.hand(target_hand_id),
-                                                    HandId::Player,
-                                                    Predicate::RankSuit(predicate.rank, predicate.suit),
-                                                    state.cards.active_count(),
-                                                ) {
-                                                    discard_no_fishing(
-                                                        &mut state.cards,
-                                                        &mut state.animations,
-                                                        target_hand_id.with_target(HandId::Player),
-                                                        Predicate::RankSuit(predicate.rank, predicate.suit)
-                                                    );
-                                                    p_net_handle_negative_response!();
-                                                } else {
-                                                    let player_len = state.cards.player.len();
-                                                    let target_hand = state.cards.hand_mut(target_hand_id);
-
-                                                    state.memories.asked_for(
-                                                        HandId::Player,
-                                                        Predicate::RankSuit(predicate.rank, predicate.suit)
-                                                    );
-
-                                                    let memory = state.memories.memory(*target);
-                                                    let mut found = None;
-                                                    let LurePredicate { rank, suit } = *predicate;
-                                                    {
-                                                        for (i, card) in target_hand.enumerated_iter() {
-                                                            match (get_rank(card), get_suit(card)) {
-                                                                (Some(r), Some(s)) if r == rank && s == suit => {
-                                                                    found = Some((rank, suit, i));
-                                                                    // TODO? pick best one to give up, when there's no unlikely ones left?
-                                                                    // If this is an undesirable to give up card, keep looking.
-                                                                    if memory.is_likely_to_fill_rank_soon(
-                                                                        target_hand_id,
-                                                                        rank
-                                                                    ) || memory.is_likely_to_fill_rank_soon(
-                                                                        HandId::Player,
-                                                                        rank
-                                                                    ) {
-                                                                        continue
-                                                                    }
-                                                                    break
-                                                                }
-                                                                _ => {}
-                                                            }
-                                                        }
-                                                    }
-
-                                                    if let Some((rank, suit, i)) = found {
-                                                        state.memories.found(
-                                                            HandId::Player,
-                                                            models::fish_card(rank, suit),
-                                                        );
-                                                        let at = get_card_position(
-                                                            spread(target_hand_id),
-                                                            target_hand.len(),
-                                                            i,
-                                                        );
-                                                        let removed = target_hand.remove(i);
-                                                        debug_assert!(removed.is_some());
-                                                        if let Some(card) = removed {
-                                                            let target = get_card_insert_position(
-                                                                spread(HandId::Player),
-                                                                player_len
-                                                            );
-
-                                                            state.animations.push(Animation {
-                                                                card,
-                                                                at,
-                                                                target,
-                                                                action: AnimationAction::AddToHand(HandId::Player),
-                                                                shown: true,
-                                                                .. <_>::default()
-                                                            });
-                                                        }
-
-                                                        to_next_turn!(state);
-                                                    } else {
-                                                        p_net_handle_negative_response!();
-                                                    }
-                                                }
                                             } else if input.pressed_this_frame(Button::B) {
                                                 state.selection.card_index = selected;
                                                 state.selection.player_menu = PlayerMenu::default();
-                                            } else if let Some(dir) = input.dir_pressed_this_frame() {
+                                            } else if let Some(dir) = state.ctx.dir_repeated_this_frame(input) {
                                                 const GRID_LEN: usize = 3;
 
                                                 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -3533,7 +4436,7 @@ pub fn update_and_render(
                                                 () => {
                                                     let player_len = state.cards.player.len();
 
-                                                    let drew = state.cards.deck.draw();
+                                                    let drew = draw_for(&mut state.cards.deck, &mut state.memories, HandId::Player);
 
                                                     *menu = PlayerMenu::Fished{
                                                         question: question.clone(),
@@ -3549,25 +4452,28 @@ pub fn update_and_render(
                                                             player_len
                                                         );
 
-                                                        state.animations.push(Animation {
-                                                            card,
-                                                            at,
-                                                            target,
-                                                            action: AnimationAction::AddToHand(HandId::Player),
-                                                            .. <_>::default()
-                                                        });
+                                                        state.animations.push(
+                                                            Animation::tween(card, at, target)
+                                                                .then(move |state, speaker| state.add_to_hand(speaker, HandId::Player, card))
+                                                        );
                                                     }
                                                 }
                                             }
 
                                             match sub_menu {
                                                 PlayerAskingSubMenu::Root => {
+                                                    commands.dim_screen();
                                                     commands.draw_nine_slice(gfx::NineSlice::Window, ASKING_WINDOW);
 
                                                     let base_xy = ASKING_WINDOW.xy() + WINDOW_CONTENT_OFFSET;
 
                                                     let card_xy = base_xy;
 
+                                                    commands.draw_glow(
+                                                        Rect::xy_wh(card_xy, WH { w: CARD_WIDTH, h: CARD_HEIGHT }),
+                                                        gfx::GLOW_COLOUR,
+                                                    );
+
                                                     commands.draw_card(
                                                         used,
                                                         card_xy
@@ -3695,6 +4601,13 @@ pub fn update_and_render(
                                                                 }
                                                             }
 
+                                                            if found.is_none() {
+                                                                state.memories.did_not_have(
+                                                                    question.target(),
+                                                                    question.predicate
+                                                                );
+                                                            }
+
                                                             if let Some((card, i)) = found {
                                                                 state.memories.found(
                                                                     HandId::Player,
@@ -3711,14 +4624,11 @@ pub fn update_and_render(
                                                                     player_len
                                                                 );
 
-                                                                state.animations.push(Animation {
-                                                                    card,
-                                                                    at,
-                                                                    target,
-                                                                    action: AnimationAction::AddToHand(HandId::Player),
-                                                                    shown: true,
-                                                                    .. <_>::default()
-                                                                });
+                                                                state.animations.push(
+                                                                    Animation::tween(card, at, target)
+                                                                        .shown()
+                                                                        .then(move |state, speaker| state.add_to_hand(speaker, HandId::Player, card))
+                                                                );
 
                                                                 *menu = PlayerMenu::default();
                                                                 state.done_something_this_turn = true;
@@ -3731,7 +4641,7 @@ pub fn update_and_render(
                                                     } else if input.pressed_this_frame(Button::B) {
                                                         state.selection.card_index = selected;
                                                         state.selection.player_menu = PlayerMenu::default();
-                                                    } else if let Some(dir) = input.dir_pressed_this_frame() {
+                                                    } else if let Some(dir) = state.ctx.dir_repeated_this_frame(input) {
                                                         const GRID_LEN: usize = 3;
 
                                                         #[derive(Clone, Copy, PartialEq, Eq)]
@@ -3821,6 +4731,7 @@ pub fn update_and_render(
                                                     }
                                                 },
                                                 PlayerAskingSubMenu::TwoFistedFisherman => {
+                                                    commands.dim_screen();
                                                     commands.draw_nine_slice(
                                                         gfx::NineSlice::Window,
                                                         PLAYER_TWO_FISTED_FISHERMAN_WINDOW
@@ -3967,7 +4878,7 @@ pub fn update_and_render(
 
                                         if input.pressed_this_frame(Button::A)
                                         || input.pressed_this_frame(Button::B) {
-                                            let drew = state.cards.deck.draw();
+                                            let drew = draw_for(&mut state.cards.deck, &mut state.memories, HandId::Player);
 
                                             state.selection.card_index = state.cards.player.len().saturating_sub(1);
                                             state.selection.player_menu = PlayerMenu::default();
@@ -3980,13 +4891,14 @@ pub fn update_and_render(
                                                     state.cards.player.len(),
                                                 );
 
-                                                state.animations.push(Animation {
-                                                    card,
-                                                    at,
-                                                    target,
-                                                    action: AnimationAction::AddToHand(HandId::Player),
-                                                    .. <_>::default()
-                                                });
+                                                state.animations.push(
+                                                    Animation::tween(card, at, target)
+                                                        .then(move |state, speaker| state.add_to_hand(speaker, HandId::Player, card))
+                                                );
+                                            } else {
+                                                // There's nothing left to draw, so all
+                                                // we can do is pass.
+                                                to_next_turn!(state);
                                             }
                                         }
                                     } else {
@@ -4013,141 +4925,278 @@ pub fn update_and_render(
                                         if hand.is_empty() {
                                             *menu = CpuMenu::DeadInTheWater;
                                         } else {
-                                            //TODO restore
-                                            if let Some((rank, suit, target)) = state.memories.memory(id)
-                                                .informed_question(hand, hand_id) {
-                                                let question = Question::new(
-                                                    Targeting {
-                                                        target,
-                                                        source: hand_id,
-                                                    },
-                                                    Predicate::RankSuit(rank, suit),
-                                                );
+                                            let memory = state.memories.memory(id);
+                                            let informed = memory.informed_question(
+                                                &mut state.rng,
+                                                hand,
+                                                hand_id,
+                                            );
 
-                                                *menu = CpuMenu::Asking(
-                                                    rank,
-                                                    question,
-                                                );
-                                                state.done_something_this_turn = true;
+                                            // If we know where a card is, then asking
+                                            // for it normally is a sure thing, so
+                                            // there's no need to spend a super ask.
+                                            let is_sure_thing = informed
+                                                .map(|(rank, suit, target)| {
+                                                    memory.is_known_to_be_in(
+                                                        fish_card(rank, suit),
+                                                        target,
+                                                    )
+                                                })
+                                                .unwrap_or_default();
+
+                                            enum ZingerPlay {
+                                                DivineIntervention,
+                                                GameWarden(HandId),
+                                                GlassBottomBoat(HandId),
+                                                TheNet(HandId, NetPredicate),
+                                                TheLure(HandId, LurePredicate),
                                             }
 
-                                            if let CpuMenu::Selecting = *menu {
-                                                let mut zinger_to_play = None;
-                                                // TODO? randomize order through the cards here to make Cpu
-                                                // player less predictable?
-
-                                                let mut ordering = HandOrdering::default();
+                                            let mut zinger_to_play = None;
+                                            // TODO? randomize order through the cards here to make Cpu
+                                            // player less predictable?
+                                            for card in hand.iter() {
+                                                let Some(zinger) = models::get_zinger(card) else {
+                                                    continue
+                                                };
 
-                                                for (i, card) in hand.enumerated_iter() {
-                                                    if models::get_zinger(card).is_some() {
-                                                        // TODO? Are all zingers high priority?
-                                                        ordering.move_to_first(i);
+                                                match zinger {
+                                                    Zinger::DeadScubaDiver => {
+                                                        // TODO? is there a case where we'd rather play
+                                                        // it here than wait to respond to our own turn?
                                                     }
-                                                }
-
-                                                for card in hand.ordering_iter(ordering) {
-                                                    if let Some(rank) = models::get_rank(card) {
-                                                        let besides = HandId::besides(hand_id);
-                                                        let target_id = besides[
-                                                            xs::range(&mut state.rng, 0..besides.len() as u32) as usize
-                                                        ];
-
-                                                        // TODO? Decide what suit to ask for intelligently
-                                                        // in this case? Does it matter given that asking
-                                                        // for a card you have as a distraction is viable?
-                                                        // Maybe only ask for a card you have when you
-                                                        // have 4 in your hand already?
-                                                        let suit = Suit::from_rng(&mut state.rng);
-
-                                                        let question = Question::new(
-                                                            Targeting {
-                                                                source: hand_id,
-                                                                target: target_id,
-                                                            },
-                                                            Predicate::RankSuit(rank, suit),
-                                                        );
-
-                                                        *menu = CpuMenu::Asking(
-                                                            rank,
-                                                            question,
-                                                        );
-                                                        state.done_something_this_turn = true;
-                                                        break
-                                                    } else if let Some(zinger) = models::get_zinger(card) {
-                                                        match zinger {
-                                                            Zinger::DeadScubaDiver => {
-                                                                // TODO? is there a case where we'd rather play
-                                                                // it here than wait to respond to our own turn?
-                                                            }
-                                                            Zinger::TheNet | Zinger::TheLure => {
-                                                                // TODO actually play the Net in cases where it seems like a good idea
-                                                                if state.done_something_this_turn {
-                                                                    // Cannot play it
-                                                                } else if should_play_super_ask(
-                                                                    &state.cards,
-                                                                    state.cards.hand(hand_id),
-                                                                    &state.stack,
-                                                                    &state.memories,
-                                                                    id,
-                                                                ) {
-                                                                    zinger_to_play = Some(zinger);
-                                                                    break
-                                                                } else {
-                                                                    // Don't discard it
-                                                                }
-                                                            }
-                                                            Zinger::DivineIntervention => {
-                                                                if state.done_something_this_turn {
-                                                                    // Cannot play it
-                                                                } else if state.cards.can_discard_divine_intervention()
-                                                                && should_shed_zingers(
-                                                                    &state.cards,
-                                                                    state.cards.hand(hand_id),
-                                                                    &state.stack,
-                                                                ) {
-                                                                    zinger_to_play = Some(zinger);
-                                                                    break
+                                                    Zinger::TheGameWarden | Zinger::GlassBottomBoat => {
+                                                        // These don't use up our turn, so if
+                                                        // there's a good target, we might as
+                                                        // well play them before asking.
+                                                        if let Some(target) = warden_or_boat_target(
+                                                            &mut state.rng,
+                                                            &state.cards,
+                                                            &state.memories,
+                                                            id,
+                                                        ) {
+                                                            zinger_to_play = Some(
+                                                                if zinger == Zinger::TheGameWarden {
+                                                                    ZingerPlay::GameWarden(target)
                                                                 } else {
-                                                                    // Don't discard it
+                                                                    ZingerPlay::GlassBottomBoat(target)
                                                                 }
-                                                            }
-                                                            Zinger::TwoFistedFisherman => {
-                                                                // Can't play that now. Wait until asking for something.
-                                                            }
-                                                            // TODO Play other Zingers sometimes.
-                                                            _ => { todo!("Attempted to play {zinger:?}") }
+                                                            );
+                                                            break
                                                         }
-                                                    } else {
-                                                        debug_assert!(false, "Non-fish, non-zinger card!? {card}");
                                                     }
-                                                }
+                                                    Zinger::TheNet | Zinger::TheLure => {
+                                                        if state.done_something_this_turn
+                                                        || is_sure_thing {
+                                                            // Cannot play it, or don't need to.
+                                                            continue
+                                                        }
 
-                                                match zinger_to_play {
-                                                    Some(Zinger::DivineIntervention) => {
-                                                        discard_divine_intervention(
-                                                            &mut state.cards,
-                                                            &mut state.animations,
-                                                            hand_id
+                                                        let wants_super_ask = should_play_super_ask(
+                                                            &state.cards,
+                                                            hand,
+                                                            &state.stack,
+                                                            &state.memories,
+                                                            id,
                                                         );
-                                                        // We're doing the special
-                                                        // discard action, so don't
-                                                        // do anything to the stack.
+
+                                                        if zinger == Zinger::TheNet {
+                                                            if let Some((target, predicate)) = cpu_net_choice(
+                                                                &mut state.rng,
+                                                                hand,
+                                                                memory,
+                                                                id,
+                                                                wants_super_ask,
+                                                            ) {
+                                                                zinger_to_play = Some(
+                                                                    ZingerPlay::TheNet(target, predicate)
+                                                                );
+                                                                break
+                                                            }
+                                                        } else if let Some((target, predicate)) = cpu_lure_choice(
+                                                            &mut state.rng,
+                                                            hand,
+                                                            memory,
+                                                            id,
+                                                            wants_super_ask,
+                                                        ) {
+                                                            zinger_to_play = Some(
+                                                                ZingerPlay::TheLure(target, predicate)
+                                                            );
+                                                            break
+                                                        }
+                                                    }
+                                                    Zinger::DivineIntervention => {
+                                                        if state.done_something_this_turn
+                                                        || informed.is_some() {
+                                                            // Cannot play it, or would rather ask.
+                                                        } else if state.cards.can_discard_divine_intervention()
+                                                        && should_shed_zingers(
+                                                            &state.cards,
+                                                            hand,
+                                                            &state.stack,
+                                                        ) {
+                                                            zinger_to_play = Some(ZingerPlay::DivineIntervention);
+                                                            break
+                                                        } else {
+                                                            // Don't discard it
+                                                        }
+                                                    }
+                                                    Zinger::TwoFistedFisherman => {
+                                                        // Can't play that now. Wait until asking for something.
                                                     }
-                                                    //Some(Zinger::TheLure) => {
-                                                        //state.stack.push()
-                                                    //}
-                                                    Some(zinger) => {
-                                                        todo!("cpu handle selecting {zinger:?}");
+                                                    Zinger::NoFishing => {
+                                                        // Can't play that now. Wait until someone asks us for something.
                                                     }
-                                                    None => {
-                                                        if let CpuMenu::Selecting = *menu {
-                                                            *menu = CpuMenu::DeadInTheWater;
+                                                }
+                                            }
+
+                                            match zinger_to_play {
+                                                Some(ZingerPlay::DivineIntervention) => {
+                                                    discard_divine_intervention(
+                                                        &mut state.cards,
+                                                        &mut state.animations,
+                                                        hand_id
+                                                    );
+                                                    // We're doing the special
+                                                    // discard action, so don't
+                                                    // do anything to the stack.
+                                                }
+                                                Some(ZingerPlay::GameWarden(target)) => {
+                                                    let performed = perform_game_warden(
+                                                        &mut state.cards,
+                                                        &mut state.animations,
+                                                        &mut state.rng,
+                                                        Targeting {
+                                                            source: hand_id,
+                                                            target,
+                                                        },
+                                                    );
+                                                    debug_assert!(performed.is_some(), "perform_game_warden failed");
+                                                }
+                                                Some(ZingerPlay::GlassBottomBoat(target)) => {
+                                                    let performed = cpu_perform_glass_bottom_boat(
+                                                        &mut state.cards,
+                                                        &mut state.animations,
+                                                        &mut state.rng,
+                                                        &mut state.memories,
+                                                        id,
+                                                        target,
+                                                    );
+                                                    debug_assert!(performed.is_some(), "cpu_perform_glass_bottom_boat failed");
+                                                }
+                                                Some(ZingerPlay::TheNet(target, predicate)) => {
+                                                    discard_net(
+                                                        &mut state.cards,
+                                                        &mut state.animations,
+                                                        hand_id.with_target(target),
+                                                        predicate,
+                                                    );
+                                                    state.done_something_this_turn = true;
+                                                }
+                                                Some(ZingerPlay::TheLure(target, predicate)) => {
+                                                    discard_lure(
+                                                        &mut state.cards,
+                                                        &mut state.animations,
+                                                        hand_id.with_target(target),
+                                                        predicate,
+                                                    );
+                                                    state.done_something_this_turn = true;
+                                                }
+                                                None => {
+                                                    let search = match state.cpu_strategies[id as usize] {
+                                                        CpuStrategy::Heuristic => None,
+                                                        CpuStrategy::Search(budget) => search::Search::new(
+                                                            search::Position::new(
+                                                                hand_id,
+                                                                hand,
+                                                                HandId::ALL.map(|id| state.cards.hand(id).len()),
+                                                                state.cards.deck.len(),
+                                                                &[
+                                                                    &state.cards.player_baskets,
+                                                                    &state.cards.cpu1_baskets,
+                                                                    &state.cards.cpu2_baskets,
+                                                                    &state.cards.cpu3_baskets,
+                                                                    &state.cards.discard,
+                                                                ],
+                                                            ),
+                                                            memory,
+                                                            budget,
+                                                        ),
+                                                    };
+
+                                                    if let Some(search) = search {
+                                                        *menu = CpuMenu::Searching(Box::new(search));
+                                                    } else {
+                                                        let question = if let Some((rank, suit, target)) = informed {
+                                                            Some((rank, suit, target))
+                                                        } else if let Some(rank) = hand.iter().find_map(get_rank) {
+                                                            let besides = HandId::besides(hand_id);
+                                                            let target = besides[
+                                                                xs::range(&mut state.rng, 0..besides.len() as u32) as usize
+                                                            ];
+
+                                                            let suit = memory.suit_to_ask_for(
+                                                                &mut state.rng,
+                                                                hand,
+                                                                target,
+                                                                rank,
+                                                            );
+
+                                                            Some((rank, suit, target))
+                                                        } else {
+                                                            None
+                                                        };
+
+                                                        match question {
+                                                            Some((rank, suit, target)) => {
+                                                                let question = Question::new(
+                                                                    Targeting {
+                                                                        source: hand_id,
+                                                                        target,
+                                                                    },
+                                                                    Predicate::RankSuit(rank, suit),
+                                                                );
+
+                                                                *menu = CpuMenu::Asking(
+                                                                    rank,
+                                                                    question,
+                                                                );
+                                                                state.done_something_this_turn = true;
+                                                            }
+                                                            None => {
+                                                                *menu = CpuMenu::DeadInTheWater;
+                                                            }
                                                         }
                                                     }
                                                 }
                                             }
                                         }
                                     },
+                                    CpuMenu::Searching(ref mut search) => {
+                                        // Keep showing the selecting window while
+                                        // we think it over.
+                                        commands.draw_nine_slice(
+                                            gfx::NineSlice::Window,
+                                            CPU_SELECTING_WINDOW
+                                        );
+
+                                        if let Some(search::Ask { rank, suit, target }) = search.step(&mut state.rng) {
+                                            let question = Question::new(
+                                                Targeting {
+                                                    source: id.into(),
+                                                    target,
+                                                },
+                                                Predicate::RankSuit(rank, suit),
+                                            );
+
+                                            *menu = CpuMenu::Asking(
+                                                rank,
+                                                question,
+                                            );
+                                            state.done_something_this_turn = true;
+                                        }
+                                    },
                                     CpuMenu::Asking(_rank, ref mut question) => {
                                         macro_rules! handle_ask {
                                             () => {
@@ -4194,18 +5243,20 @@ pub fn update_and_render(
                                                         my_len
                                                     );
 
-                                                    state.animations.push(Animation {
-                                                        card,
-                                                        at,
-                                                        target,
-                                                        action: AnimationAction::AddToHand(id.into()),
-                                                        shown: true,
-                                                        .. <_>::default()
-                                                    });
+                                                    state.animations.push(
+                                                        Animation::tween(card, at, target)
+                                                            .shown()
+                                                            .turned(turn(id.into()))
+                                                            .then(move |state, speaker| state.add_to_hand(speaker, id.into(), card))
+                                                    );
 
                                                     state.cpu_menu = CpuMenu::WaitingForSuccesfulAsk;
                                                     state.done_something_this_turn = true;
                                                 } else {
+                                                    state.memories.did_not_have(
+                                                        question.target(),
+                                                        question.predicate,
+                                                    );
                                                     cpu_handle_negative_response!(state, menu, id, question.predicate);
                                                 }
                                             }
@@ -4216,6 +5267,7 @@ pub fn update_and_render(
                                             state.cards.hand(question.target()).contains(zingers::NO_FISHING),
                                         ) {
                                             (HandId::Player, true) => {
+                                                commands.dim_screen();
                                                 commands.draw_nine_slice(gfx::NineSlice::Window, NO_FISHING_WINDOW);
 
                                                 let base_xy = NO_FISHING_WINDOW.xy()
@@ -4335,8 +5387,8 @@ pub fn update_and_render(
                                         // Just wait until player acknowledges turn.
                                         if input.pressed_this_frame(Button::A)
                                         | input.pressed_this_frame(Button::B) {
-                                            let drew = state.cards.deck.draw();
                                             let hand_id = id.into();
+                                            let drew = draw_for(&mut state.cards.deck, &mut state.memories, hand_id);
                                             let len = state.cards.hand(hand_id).len();
 
                                             state.selection.card_index = state.cards.player.len().saturating_sub(1);
@@ -4351,13 +5403,15 @@ pub fn update_and_render(
                                                     len
                                                 );
 
-                                                state.animations.push(Animation {
-                                                    card,
-                                                    at,
-                                                    target,
-                                                    action: AnimationAction::AddToHand(hand_id),
-                                                    .. <_>::default()
-                                                });
+                                                state.animations.push(
+                                                    Animation::tween(card, at, target)
+                                                        .turned(turn(hand_id))
+                                                        .then(move |state, speaker| state.add_to_hand(speaker, hand_id, card))
+                                                );
+                                            } else {
+                                                // There's nothing left to draw, so all
+                                                // they can do is pass.
+                                                to_next_turn!(state);
                                             }
                                         }
                                     },
@@ -4425,240 +5479,288 @@ pub fn update_and_render(
                     // Resolve the card on the top of the stack
                     Some(play) => {
                         let mut go_again = false;
-                        match play.kind {
-                            PlayKind::FishedUnsuccessfully{ .. } => {
-                                assert!(state.stack.is_empty());
-                                // Just move to the next turn
-                            },
-                            PlayKind::NoFishing{ question } => {
-                                match CpuId::try_from(question.targeting.target) {
-                                    Err(()) => {
-                                        let menu = &mut state.selection.player_menu;
 
-                                        // Started as a copy of p_handle_negative_response!();
-                                        // TODO? reduce this duplication?
-                                        let player_len = state.cards.player.len();
+                        // The Net and The Lure resolve the same way, besides what
+                        // they ask for.
+                        macro_rules! resolve_super_ask {
+                            ($targeting: expr, $predicate: expr, $kind: expr) => {
+                                let Targeting { source, target } = $targeting;
+                                let predicate = $predicate;
 
-                                        let drew = state.cards.deck.draw();
+                                macro_rules! super_ask_handle_negative_response {
+                                    () => {
+                                        let len = state.cards.hand(source).len();
 
-                                        *menu = PlayerMenu::Fished{
-                                            question: question.clone(),
-                                            drew,
-                                        };
-                                        state.done_something_this_turn = true;
-                                        // Ensure that we actually show the fished menu
-                                        go_again = true;
+                                        let drew = draw_for(&mut state.cards.deck, &mut state.memories, source);
 
                                         if let Some(card) = drew {
                                             let at = DECK_XY;
 
                                             let target = get_card_insert_position(
-                                                spread(HandId::Player),
-                                                player_len
+                                                spread(source),
+                                                len
                                             );
 
-                                            state.animations.push(Animation {
-                                                card,
-                                                at,
-                                                target,
-                                                action: AnimationAction::AddToHand(HandId::Player),
-                                                .. <_>::default()
-                                            });
+                                            state.animations.push(
+                                                Animation::tween(card, at, target)
+                                                    .turned(turn(source))
+                                                    .then(move |state, speaker| state.add_to_hand(speaker, source, card))
+                                            );
                                         }
-                                    }
-                                    Ok(asker_id) => {
-                                        let menu = &mut state.cpu_menu;
-                                        cpu_handle_negative_response!(state, menu, asker_id, question.predicate);
+
+                                        // This card counts as a turn, so just go on to the next turn.
+                                        to_next_turn!(state);
                                     }
                                 }
-                            }
-                            PlayKind::TwoFistedFisherman{ source: _, cancelled } => {
-                                if !cancelled {
-                                    // If this turns out to be false ever, we need to
-                                    // store that the player gets an extra turn somewhere
-                                    // more persistently.
-                                    assert!(state.stack.is_empty());
-                                    go_again = true;
+
+                                macro_rules! super_ask_take_card {
+                                    () => {
+                                        let len = state.cards.hand(source).len();
+                                        let target_hand = state.cards.hand_mut(target);
+
+                                        state.memories.asked_for(
+                                            source,
+                                            predicate
+                                        );
+
+                                        let mut found = None;
+                                        for (i, card) in target_hand.enumerated_iter() {
+                                            if !predicate.matches(card) {
+                                                continue
+                                            }
+
+                                            found = Some(i);
+
+                                            // TODO? pick best one to give up, when there's no unlikely ones left?
+                                            // If this is an undesirable to give up card, keep looking.
+                                            if let (Ok(target_id), Some(rank)) = (
+                                                CpuId::try_from(target),
+                                                get_rank(card),
+                                            ) {
+                                                let memory = state.memories.memory(target_id);
+                                                if memory.is_likely_to_fill_rank_soon(
+                                                    target,
+                                                    rank
+                                                ) || memory.is_likely_to_fill_rank_soon(
+                                                    source,
+                                                    rank
+                                                ) {
+                                                    continue
+                                                }
+                                            }
+                                            break
+                                        }
+
+                                        if let Some(i) = found {
+                                            let at = get_card_position(
+                                                spread(target),
+                                                target_hand.len(),
+                                                i,
+                                            );
+
+                                            let removed = target_hand.remove(i);
+                                            debug_assert!(removed.is_some());
+                                            if let Some(card) = removed {
+                                                state.memories.found(source, card);
+
+                                                let target = get_card_insert_position(
+                                                    spread(source),
+                                                    len
+                                                );
+
+                                                state.animations.push(
+                                                    Animation::tween(card, at, target)
+                                                        .shown()
+                                                        .turned(turn(source))
+                                                        .then(move |state, speaker| state.add_to_hand(speaker, source, card))
+                                                );
+                                            }
+
+                                            to_next_turn!(state);
+                                        } else {
+                                            state.memories.did_not_have(target, predicate);
+                                            super_ask_handle_negative_response!();
+                                        }
+                                    }
                                 }
-                            }
-                            PlayKind::TheNet{ targeting: Targeting{ source, target }, predicate } => {
+
                                 match CpuId::try_from(target) {
                                     Err(()) => {
-                                        //let menu = &mut state.selection.player_menu;
-//
-                                        //// Started as a copy of p_handle_negative_response!();
-                                        //// TODO? reduce this duplication?
-                                        //let player_len = state.cards.player.len();
-//
-                                        //let drew = state.cards.deck.draw();
-//
-                                        //*menu = PlayerMenu::Fished{
-                                            //question: question.clone(),
-                                            //drew,
-                                        //};
-                                        //state.done_something_this_turn = true;
-                                        //// Ensure that we actually show the fished menu
-                                        //go_again = true;
-//
-                                        //if let Some(card) = drew {
-                                            //let at = DECK_XY;
-//
-                                            //let target = get_card_insert_position(
-                                                //spread(HandId::Player),
-                                                //player_len
-                                            //);
-//
-                                            //state.animations.push(Animation {
-                                                //card,
-                                                //at,
-                                                //target,
-                                                //action: AnimationAction::AddToHand(HandId::Player),
-                                                //.. <_>::default()
-                                            //});
-                                        //}
-
-                                        todo!("probably move p_handle_negative_response!(); here")
-                                    }
-                                    Ok(target_id) => {
-                                        macro_rules! net_handle_negative_response {
-                                            () => {
-                                                let len = state.cards.hand(source).len();
+                                        if state.cards.player.contains(zingers::NO_FISHING) {
+                                            commands.dim_screen();
+                                            commands.draw_nine_slice(gfx::NineSlice::Window, NO_FISHING_WINDOW);
 
-                                                let drew = state.cards.deck.draw();
+                                            let base_xy = NO_FISHING_WINDOW.xy()
+                                                + WINDOW_CONTENT_OFFSET;
 
-                                                if let Some(card) = drew {
-                                                    let at = DECK_XY;
+                                            let card_xy = base_xy;
 
-                                                    let target = get_card_insert_position(
-                                                        spread(source),
-                                                        len
-                                                    );
+                                            commands.draw_card(
+                                                zingers::NO_FISHING,
+                                                card_xy,
+                                            );
 
-                                                    state.animations.push(Animation {
-                                                        card,
-                                                        at,
-                                                        target,
-                                                        action: AnimationAction::AddToHand(source),
-                                                        .. <_>::default()
-                                                    });
+                                            let description_base_xy = card_xy + CARD_WIDTH;
+
+                                            let description_base_rect = unscaled::Rect::xy_wh(
+                                                description_base_xy,
+                                                unscaled::WH {
+                                                    w: NO_FISHING_WINDOW.w - CARD_WIDTH * 2,
+                                                    h: NO_FISHING_WINDOW.h - WINDOW_CONTENT_OFFSET.h * 2,
                                                 }
+                                            );
 
-                                                // This card counts as a turn, so just go on to the next turn.
-                                                to_next_turn!(state);
+                                            let mut question = Question::new(
+                                                $targeting,
+                                                predicate,
+                                            );
+
+                                            commands.print_centered(
+                                                question.fresh_cpu_ask_description(
+                                                    description_base_rect.w,
+                                                ),
+                                                description_base_rect,
+                                                WHITE,
+                                            );
+
+                                            let submit_base_xy = NO_FISHING_WINDOW.xy()
+                                                + WINDOW_CONTENT_OFFSET.h
+                                                + NO_FISHING_WINDOW.w
+                                                - (CARD_WIDTH + WINDOW_CONTENT_OFFSET.w);
+
+                                            let group = new_group!();
+
+                                            if do_button(
+                                                group,
+                                                ButtonSpec {
+                                                    id: Submit,
+                                                    rect: fit_to_rest_of_window(
+                                                        submit_base_xy,
+                                                        NO_FISHING_WINDOW,
+                                                    ),
+                                                    text: b"Play",
+                                                }
+                                            ) {
+                                                discard_no_fishing(
+                                                    &mut state.cards,
+                                                    &mut state.animations,
+                                                    target.with_target(source),
+                                                    predicate
+                                                );
+                                                // The No Fishing play will handle the
+                                                // negative response once it resolves.
+                                                go_again = true;
+                                            } else if input.pressed_this_frame(Button::B) {
+                                                super_ask_take_card!();
+                                            } else {
+                                                // Keep this play around until the
+                                                // player decides.
+                                                state.stack.push(Play {
+                                                    sub_turn_ids: play.sub_turn_ids,
+                                                    sub_turn_index: play.sub_turn_index,
+                                                    kind: $kind,
+                                                });
+                                                go_again = true;
                                             }
-                                        }
 
+                                            group.ctx.set_next_hot(Submit);
+                                        } else {
+                                            super_ask_take_card!();
+                                        }
+                                    }
+                                    Ok(target_id) => {
                                         if state.cards.hand(target)
                                             .contains(zingers::NO_FISHING)
                                         && should_use_no_fishing_against(
                                             state.memories.memory(target_id),
                                             state.cards.hand(target),
                                             source,
-                                            Predicate::Net(predicate),
+                                            predicate,
                                             state.cards.active_count(),
                                         ) {
                                             discard_no_fishing(
                                                 &mut state.cards,
                                                 &mut state.animations,
                                                 target.with_target(source),
-                                                Predicate::Net(predicate)
+                                                predicate
                                             );
-                                            net_handle_negative_response!();
+                                            // The No Fishing play will handle the
+                                            // negative response once it resolves.
+                                            go_again = true;
                                         } else {
-                                            let len = state.cards.hand(source).len();
-                                            let target_hand = state.cards.hand_mut(target);
+                                            super_ask_take_card!();
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        match play.kind {
+                            PlayKind::FishedUnsuccessfully{ .. } => {
+                                assert!(state.stack.is_empty());
+                                // Just move to the next turn
+                            },
+                            PlayKind::NoFishing{ question } => {
+                                match CpuId::try_from(question.targeting.target) {
+                                    Err(()) => {
+                                        let menu = &mut state.selection.player_menu;
 
-                                            state.memories.asked_for(
-                                                source,
-                                                Predicate::Net(predicate)
-                                            );
+                                        // Started as a copy of p_handle_negative_response!();
+                                        // TODO? reduce this duplication?
+                                        let player_len = state.cards.player.len();
 
-                                            let memory = state.memories.memory(target_id);
-                                            let mut found = None;
-                                            match predicate {
-                                                NetPredicate::Rank(rank) => {
-                                                    for (i, card) in target_hand.enumerated_iter() {
-                                                        match (get_rank(card), get_suit(card)) {
-                                                            (Some(r), Some(suit)) if r == rank => {
-                                                                found = Some((rank, suit, i));
-                                                                // TODO? pick best one to give up, when there's no unlikely ones left?
-                                                                // If this is an undesirable to give up card, keep looking.
-                                                                if memory.is_likely_to_fill_rank_soon(
-                                                                    target,
-                                                                    rank
-                                                                ) || memory.is_likely_to_fill_rank_soon(
-                                                                    source,
-                                                                    rank
-                                                                ) {
-                                                                    continue
-                                                                }
-                                                                break
-                                                            }
-                                                            _ => {}
-                                                        }
-                                                    }
-                                                }
-                                                NetPredicate::Suit(suit) => {
-                                                    for (i, card) in target_hand.enumerated_iter() {
-                                                        match (get_rank(card), get_suit(card)) {
-                                                            (Some(rank), Some(s)) if s == suit => {
-                                                                found = Some((rank, suit, i));
-                                                                // TODO? pick best one to give up, when there's no unlikely ones left?
-                                                                // If this is an undesirable to give up card, keep looking.
-                                                                if memory.is_likely_to_fill_rank_soon(
-                                                                    target,
-                                                                    rank
-                                                                ) || memory.is_likely_to_fill_rank_soon(
-                                                                    source,
-                                                                    rank
-                                                                ) {
-                                                                    continue
-                                                                }
-                                                                break
-                                                            }
-                                                            _ => {}
-                                                        }
-                                                    }
-                                                }
-                                            }
+                                        let drew = draw_for(&mut state.cards.deck, &mut state.memories, HandId::Player);
 
-                                            if let Some((rank, suit, i)) = found {
-                                                state.memories.found(
-                                                    source,
-                                                    models::fish_card(rank, suit)
-                                                );
-                                                let at = get_card_position(
-                                                    spread(target),
-                                                    target_hand.len(),
-                                                    i,
-                                                );
+                                        *menu = PlayerMenu::Fished{
+                                            question: question.clone(),
+                                            drew,
+                                        };
+                                        state.done_something_this_turn = true;
+                                        // Ensure that we actually show the fished menu
+                                        go_again = true;
 
-                                                let removed = target_hand.remove(i);
-                                                debug_assert!(removed.is_some());
-                                                if let Some(card) = removed {
-                                                    let target = get_card_insert_position(
-                                                        spread(source),
-                                                        len
-                                                    );
+                                        if let Some(card) = drew {
+                                            let at = DECK_XY;
 
-                                                    state.animations.push(Animation {
-                                                        card,
-                                                        at,
-                                                        target,
-                                                        action: AnimationAction::AddToHand(HandId::Player),
-                                                        shown: true,
-                                                        .. <_>::default()
-                                                    });
-                                                }
+                                            let target = get_card_insert_position(
+                                                spread(HandId::Player),
+                                                player_len
+                                            );
 
-                                                to_next_turn!(state);
-                                            } else {
-                                                net_handle_negative_response!();
-                                            }
+                                            state.animations.push(
+                                                Animation::tween(card, at, target)
+                                                    .then(move |state, speaker| state.add_to_hand(speaker, HandId::Player, card))
+                                            );
                                         }
                                     }
+                                    Ok(asker_id) => {
+                                        let menu = &mut state.cpu_menu;
+                                        cpu_handle_negative_response!(state, menu, asker_id, question.predicate);
+                                    }
+                                }
+                            }
+                            PlayKind::TwoFistedFisherman{ source: _, cancelled } => {
+                                if !cancelled {
+                                    // If this turns out to be false ever, we need to
+                                    // store that the player gets an extra turn somewhere
+                                    // more persistently.
+                                    assert!(state.stack.is_empty());
+                                    go_again = true;
                                 }
                             }
+                            PlayKind::TheNet{ targeting, predicate } => {
+                                resolve_super_ask!(
+                                    targeting,
+                                    Predicate::Net(predicate),
+                                    PlayKind::TheNet{ targeting, predicate }
+                                );
+                            }
+                            PlayKind::TheLure{ targeting, predicate } => {
+                                resolve_super_ask!(
+                                    targeting,
+                                    Predicate::RankSuit(predicate.rank, predicate.suit),
+                                    PlayKind::TheLure{ targeting, predicate }
+                                );
+                            }
                         }
 
                         if !go_again && state.stack.is_empty() {
@@ -4670,6 +5772,17 @@ pub fn update_and_render(
             }
         }
     }
+
+    if let Some(tutorial) = &mut state.tutorial {
+        if tutorial.update_and_render(commands, &state.cards, state.turn_id, input, bindings) {
+            state.tutorial = None;
+        }
+    }
+
+    if state.animation_speed == timeline::Speed::Instant {
+        // So anything that started this frame is also done by the end of it.
+        state.tick(speaker);
+    }
 }
 
 fn draw_dead_in_the_water(commands: &mut Commands) {
@@ -4694,10 +5807,81 @@ fn draw_dead_in_the_water(commands: &mut Commands) {
     );
 }
 
+fn draw_game_over(commands: &mut Commands, cards: &Cards, stats: Stats) {
+    commands.dim_screen();
+    commands.draw_nine_slice(gfx::NineSlice::Window, GAME_OVER_WINDOW);
+
+    let mut text = Vec::with_capacity(128);
+    text.extend(b"Game over!\n\n");
+    for id in HandId::ALL {
+        text.extend(HandId::TEXT[id as usize]);
+        text.extend(b": ");
+        text.extend(cards.points(id).to_string().as_bytes());
+        text.extend(b"pt\n");
+    }
+    text.extend(b"\nHints used: ");
+    text.extend(stats.hints_used.to_string().as_bytes());
+
+    commands.print_centered(
+        &text,
+        fit_to_rest_of_window(
+            GAME_OVER_WINDOW.xy() + WINDOW_CONTENT_OFFSET,
+            GAME_OVER_WINDOW,
+        ),
+        WHITE,
+    );
+}
+
 fn get_card_insert_position(spread: Spread, len: u8) -> XY {
     get_card_position(spread, len + 1, len)
 }
 
+/// The index of the player's card that the pointer is over, if any. The
+/// selected card is drawn over the rest, and later cards over earlier ones.
+fn pointed_player_card(
+    hand: &Hand,
+    relayout: &relayout::Relayout,
+    selected: CardIndex,
+    input: Input,
+) -> Option<CardIndex> {
+    let is_pointed = |i: CardIndex| input.pointer_is_over(Rect::xy_wh(
+        relayout.position(HandId::Player, hand, i),
+        WH { w: CARD_WIDTH, h: CARD_HEIGHT },
+    ));
+
+    if selected < hand.len() && is_pointed(selected) {
+        return Some(selected)
+    }
+
+    (0..hand.len()).rev().find(|&i| is_pointed(i))
+}
+
+fn draw_basket_pile(commands: &mut Commands, cards: &Cards, id: HandId) {
+    let baskets = cards.baskets(id);
+    if baskets.is_empty() {
+        return
+    }
+
+    let rect = basket_pile_rect(id);
+
+    commands.draw_nine_slice(gfx::NineSlice::Window, rect);
+
+    let base_xy = rect.xy() + WINDOW_CONTENT_OFFSET + WINDOW_CONTENT_OFFSET;
+
+    commands.print_line(b"Baskets", base_xy, WHITE);
+
+    // Counted in chunks, the same way `Cards::points` does.
+    let count = usize::from(baskets.len()).div_ceil(Suit::COUNT as usize);
+
+    let mut line = Vec::with_capacity(16);
+    line.extend(count.to_string().as_bytes());
+    line.extend(b": ");
+    line.extend(cards.points(id).to_string().as_bytes());
+    line.extend(b"pt");
+
+    commands.print_line(&line, base_xy + gfx::CHAR_ADVANCE_H.get(), WHITE);
+}
+
 fn get_card_position(spread: Spread, len: u8, index: models::CardIndex) -> XY {
     match spread {
         Spread::LTR((min_edge, max_edge), y) => {
@@ -4722,63 +5906,619 @@ fn get_card_position(spread: Spread, len: u8, index: models::CardIndex) -> XY {
                 return XY { x, y: min_edge };
             }
 
-            let span = CARD_HEIGHT;
+            let span = SIDE_CARD_WH.h;
+
+            let full_width = max_edge.saturating_point_sub(min_edge);
+            let usable_height = full_width.saturating_sub(span);
+
+            let offset = core::cmp::min(usable_height / len.into(), span);
+
+            XY {
+                x,
+                y: min_edge.saturating_add(offset * index.into())
+            }
+        },
+    }
+}
+
+fn perform_game_warden(
+    cards: &mut Cards,
+    animations: &mut Animations,
+    rng: &mut Xs,
+    Targeting {
+        source,
+        target,
+    }: Targeting,
+) -> Option<()> {
+    let hand = cards.hand_mut(target);
+
+    if hand.is_empty() {
+        return None;
+    }
+
+    let i = xs::range(rng, 0..hand.len() as u32) as CardIndex;
+
+    let at = get_card_position(
+        spread(target),
+        hand.len(),
+        i,
+    );
+
+    let () = hand
+        .remove(i)
+        .map(|card| {
+            animations.push(
+                Animation::tween(card, at, DECK_XY)
+                    .then(move |state, _| state.shuffle_into_deck(card))
+            )
+        })?;
+
+    discard_game_warden(
+        cards,
+        animations,
+        source.into(),
+    );
+
+    Some(())
+}
+
+fn cpu_perform_glass_bottom_boat(
+    cards: &mut Cards,
+    animations: &mut Animations,
+    rng: &mut Xs,
+    memories: &mut Memories,
+    source: CpuId,
+    target: HandId,
+) -> Option<()> {
+    let target_hand = cards.hand_mut(target);
+
+    if target_hand.is_empty() {
+        return None;
+    }
+
+    let i = xs::range(rng, 0..(target_hand.len() as u32)) as CardIndex;
+    let card = target_hand.remove(i)?;
+
+    memories.memory_mut(source).known(target, card);
+
+    let at = get_card_position(
+        spread(target),
+        target_hand.len(),
+        i,
+    );
+
+    let looking_at = in_front_of(source.into());
+
+    animations.push(
+        Animation::tween(card, at, looking_at)
+            .then(move |state, _| state.animate_back_to_hand(target, card, looking_at))
+    );
+
+    discard_glass_bottom_boat(
+        cards,
+        animations,
+        source.into()
+    );
+
+    Some(())
+}
+
+/// What the help and prompts call each button, and the keys bound to them.
+pub mod keys {
+    use platform_types::{Bindings, Button};
+
+    pub fn button_text(button: Button) -> &'static str {
+        match button {
+            Button::A => "Confirm",
+            Button::B => "Cancel",
+            Button::SELECT => "Help",
+            Button::START => "Hint",
+            _ => button.text(),
+        }
+    }
+
+    /// Replaces each button name in braces, like `{Confirm}`, with the first key
+    /// bound to that button, so the text matches whatever keys are in use.
+    pub fn with_keys(text: &[u8], bindings: &Bindings) -> Vec<u8> {
+        let mut output = Vec::with_capacity(text.len());
+
+        let mut rest = text;
+        while let Some(start) = rest.iter().position(|&byte| byte == b'{') {
+            output.extend_from_slice(&rest[..start]);
+            rest = &rest[start..];
+
+            let found = rest.iter().position(|&byte| byte == b'}')
+                .and_then(|end| {
+                    Button::ALL.into_iter()
+                        .find(|&button| button_text(button).as_bytes() == &rest[1..end])
+                        .map(|button| (button, end))
+                });
+
+            match found {
+                Some((button, end)) => {
+                    if let Some(key) = bindings.keys(button).next() {
+                        output.extend_from_slice(key.text().as_bytes());
+                    }
+                    rest = &rest[end + 1..];
+                },
+                None => {
+                    output.push(b'{');
+                    rest = &rest[1..];
+                },
+            }
+        }
+        output.extend_from_slice(rest);
+
+        output
+    }
+}
+
+pub mod tutorial {
+    use super::*;
+    use models::{Rank::*, Suit::*};
+
+    pub const SEED: Seed = [84, 117, 116, 111, 114, 105, 97, 108, 32, 115, 101, 101, 100, 33, 33, 33];
+
+    /// One row per round of dealing, in the order the cards are dealt.
+    pub const DEAL: [[Card; HandId::COUNT as usize]; 5] = [
+        [zingers::NO_FISHING, fish_card(Dogfish, Yellow), fish_card(Crab, Blue), fish_card(Jellyfish, Green)],
+        [fish_card(Shrimp, Blue), fish_card(Shrimp, Red), fish_card(Eel, Green), fish_card(Whale, Green)],
+        [fish_card(Dogfish, Red), fish_card(Shrimp, Green), fish_card(Jellyfish, Red), fish_card(Starfish, Green)],
+        [fish_card(Dogfish, Green), fish_card(Eel, Red), fish_card(Whale, Red), fish_card(Clownfish, Red)],
+        // The player starts with the last card they were dealt selected.
+        [fish_card(Dogfish, Blue), fish_card(Eel, Blue), fish_card(Starfish, Red), fish_card(Barnacle, Red)],
+    ];
+
+    /// The top of the deck after the deal, in the order the cards get drawn.
+    pub const AFTER_DEAL: [Card; 2] = [
+        fish_card(Dogfish, Purple),
+        fish_card(Crab, Red),
+    ];
+
+    pub const CPU1_KNOWS_ABOUT: Card = fish_card(Shrimp, Blue);
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum Step {
+        #[default]
+        Ask,
+        Fish,
+        Basket,
+        Response,
+        Done,
+    }
+
+    impl Step {
+        fn text(self) -> &'static [u8] {
+            use Step::*;
+            match self {
+                Ask => b"You have three Dogfish. Select one and press {Confirm} to ask for more. Ask Cpu 1 for the Yellow Dogfish.",
+                Fish => b"Got it! When you get what you asked for, you go again. Now ask Cpu 2 for the Purple Dogfish. They don't have it, so you will have to go fish.",
+                Basket => b"You fished up what you asked for! One of each suit of a fish makes a basket. Whoever has the most points in baskets wins. Ask anyone for a Shrimp.",
+                Response => b"Cpu 1 wants your Shrimp. You have No Fishing, which is a zinger that stops an ask. Press {Confirm} to play it.",
+                Done => b"That's the basics! Press {Help} for the rules, and to see what each zinger does. Press {Hint} to close this.",
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    pub struct Tutorial {
+        pub step: Step,
+        text: Vec<u8>,
+        /// The keys named in `text`.
+        bindings: Bindings,
+    }
+
+    impl Tutorial {
+        /// What the tutorial is currently telling the player, reflowed to fit
+        /// its window.
+        pub fn text(&self) -> &[u8] {
+            &self.text
+        }
+
+        /// Returns true when the player has closed the tutorial.
+        pub fn update_and_render(
+            &mut self,
+            commands: &mut Commands,
+            cards: &Cards,
+            turn_id: HandId,
+            input: Input,
+            bindings: &Bindings,
+        ) -> bool {
+            use Step::*;
+
+            // The player might not follow along exactly, so we move on whenever
+            // something later has happened too.
+            let next = match self.step {
+                _ if self.step < Response
+                && turn_id != HandId::Player => Response,
+                Ask | Fish if !cards.player_baskets.is_empty() => Basket,
+                Ask if cards.player.contains(fish_card(Dogfish, Yellow)) => Fish,
+                Response if turn_id != HandId::Cpu1
+                || !cards.player.contains(zingers::NO_FISHING) => Done,
+                step => step,
+            };
+
+            if next != self.step || self.text.is_empty() || self.bindings != *bindings {
+                self.step = next;
+                self.bindings = *bindings;
+                self.text = keys::with_keys(next.text(), bindings);
+
+                let rect = fit_to_rest_of_window(
+                    TUTORIAL_WINDOW.xy() + WINDOW_CONTENT_OFFSET,
+                    TUTORIAL_WINDOW,
+                );
+                text::bytes_reflow_in_place(
+                    &mut self.text,
+                    usize::from(rect.w.get() / gfx::CHAR_ADVANCE_W.get().get()),
+                );
+            }
+
+            commands.draw_nine_slice(gfx::NineSlice::Window, TUTORIAL_WINDOW);
+
+            commands.print_centered(
+                &self.text,
+                fit_to_rest_of_window(
+                    TUTORIAL_WINDOW.xy() + WINDOW_CONTENT_OFFSET,
+                    TUTORIAL_WINDOW,
+                ),
+                WHITE,
+            );
+
+            self.step == Done && input.pressed_this_frame(Button::START)
+        }
+    }
+}
+
+pub mod notepad {
+    use super::*;
+    use models::FISH_COUNT;
+    use platform_types::YELLOW;
+
+    /// What the player has noted down about whether someone has a card.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum Mark {
+        #[default]
+        Blank,
+        Suspected,
+        RuledOut,
+    }
+
+    impl Mark {
+        fn next(self) -> Self {
+            use Mark::*;
+            match self {
+                Blank => Suspected,
+                Suspected => RuledOut,
+                RuledOut => Blank,
+            }
+        }
+
+        fn text(self) -> u8 {
+            use Mark::*;
+            match self {
+                Blank => b'.',
+                Suspected => b'?',
+                RuledOut => b'x',
+            }
+        }
+    }
+
+    const HAS_TEXT: u8 = b'O';
+    const DOES_NOT_HAVE_TEXT: u8 = b'-';
+
+    const COLUMN_COUNT: u8 = HandId::COUNT * Suit::COUNT;
+
+    const LABEL_CHARS: unscaled::Inner = 12;
+    const CELL_CHARS: unscaled::Inner = 2;
+    const GROUP_CHARS: unscaled::Inner = CELL_CHARS * Suit::COUNT as unscaled::Inner + 2;
+
+    pub(crate) const SUIT_TEXT: [u8; Suit::COUNT as usize] = [b'R', b'G', b'B', b'Y', b'P'];
+
+    const LEGEND: &[u8] = b"O: Has it   -: Doesn't   ?: Suspected   x: Ruled out";
+    const CONTROLS: &[u8] = b"{Confirm}: Change mark   {Cancel}: Close";
+
+    /// A Clue-style grid of every fish card against every player, which fills
+    /// itself in with what is certain, and lets the player mark what they
+    /// suspect about the rest.
+    #[derive(Clone, Debug)]
+    pub struct Notepad {
+        marks: [[Mark; HandId::COUNT as usize]; FISH_COUNT as usize],
+        cursor_rank: u8,
+        cursor_column: u8,
+    }
+
+    impl Default for Notepad {
+        fn default() -> Self {
+            Self {
+                marks: [[Mark::default(); HandId::COUNT as usize]; FISH_COUNT as usize],
+                cursor_rank: 0,
+                cursor_column: 0,
+            }
+        }
+    }
+
+    /// Who the player can be certain has `card`, counting a basket as belonging
+    /// to whoever made it.
+    pub fn certain_holder(
+        cards: &Cards,
+        memories: &Memories,
+        card: Card,
+    ) -> Option<HandId> {
+        if cards.player.contains(card) {
+            return Some(HandId::Player)
+        }
+
+        for id in HandId::ALL {
+            if cards.baskets(id).contains(card) {
+                return Some(id)
+            }
+        }
+
+        let memory = memories.player();
+        HandId::CPUS.into_iter()
+            .find(|&id| memory.is_known_to_be_in(card, id))
+    }
+
+    /// The text for a cell, and whether it is certain, rather than marked by
+    /// the player.
+    fn cell(
+        marks: &[[Mark; HandId::COUNT as usize]; FISH_COUNT as usize],
+        cards: &Cards,
+        memories: &Memories,
+        card: Card,
+        id: HandId,
+    ) -> (u8, bool) {
+        match certain_holder(cards, memories, card) {
+            Some(holder) if holder == id => (HAS_TEXT, true),
+            Some(_) => (DOES_NOT_HAVE_TEXT, true),
+            // The player always knows everything in their own hand.
+            None if id == HandId::Player => (DOES_NOT_HAVE_TEXT, true),
+            None => (marks[card as usize][id as usize].text(), false),
+        }
+    }
+
+    fn column_card_and_id(rank_index: u8, column: u8) -> (Card, HandId) {
+        (
+            fish_card(
+                Rank::ALL[usize::from(rank_index)],
+                Suit::ALL[usize::from(column % Suit::COUNT)],
+            ),
+            HandId::ALL[usize::from(column / Suit::COUNT)],
+        )
+    }
+
+    impl Notepad {
+        pub fn mark(&self, card: Card, id: HandId) -> Mark {
+            self.marks[card as usize][id as usize]
+        }
+
+        /// Returns true when the player has closed the notepad.
+        pub fn update_and_render(
+            &mut self,
+            commands: &mut Commands,
+            cards: &Cards,
+            memories: &Memories,
+            input: Input,
+            bindings: &Bindings,
+        ) -> bool {
+            match input.dir_repeated_this_frame() {
+                Some(Dir::Up) => {
+                    self.cursor_rank = (self.cursor_rank + Rank::COUNT - 1) % Rank::COUNT;
+                },
+                Some(Dir::Down) => {
+                    self.cursor_rank = (self.cursor_rank + 1) % Rank::COUNT;
+                },
+                Some(Dir::Left) => {
+                    self.cursor_column = (self.cursor_column + COLUMN_COUNT - 1) % COLUMN_COUNT;
+                },
+                Some(Dir::Right) => {
+                    self.cursor_column = (self.cursor_column + 1) % COLUMN_COUNT;
+                },
+                None => {},
+            }
+
+            let (cursor_card, cursor_id) = column_card_and_id(
+                self.cursor_rank,
+                self.cursor_column,
+            );
+
+            if input.pressed_this_frame(Button::A) {
+                let (_, certain) = cell(&self.marks, cards, memories, cursor_card, cursor_id);
+                if !certain {
+                    let mark = &mut self.marks[cursor_card as usize][cursor_id as usize];
+                    *mark = mark.next();
+                }
+            }
+
+            commands.draw_nine_slice(gfx::NineSlice::Window, NOTEPAD_WINDOW);
+
+            let base_xy = NOTEPAD_WINDOW.xy() + WINDOW_CONTENT_OFFSET + WINDOW_CONTENT_OFFSET;
+
+            let at = |column: unscaled::Inner, row: unscaled::Inner| {
+                base_xy
+                + W(column * gfx::CHAR_ADVANCE_W.get().get())
+                + H(row * gfx::CHAR_ADVANCE_H.get().get())
+            };
+
+            for (i, text) in HandId::TEXT.iter().enumerate() {
+                let column = LABEL_CHARS + i as unscaled::Inner * GROUP_CHARS;
+                commands.print_line(text, at(column, 0), WHITE);
+
+                for (suit_i, &suit_text) in SUIT_TEXT.iter().enumerate() {
+                    let is_cursor_column = usize::from(self.cursor_column)
+                        == i * Suit::COUNT as usize + suit_i;
+                    commands.print_char(
+                        suit_text,
+                        at(column + suit_i as unscaled::Inner * CELL_CHARS, 1),
+                        if is_cursor_column { YELLOW } else { WHITE },
+                    );
+                }
+            }
+
+            for rank_index in 0..Rank::COUNT {
+                let row = 2 + unscaled::Inner::from(rank_index);
+                let is_cursor_row = rank_index == self.cursor_rank;
+
+                commands.print_line(
+                    Rank::TEXT[usize::from(rank_index)],
+                    at(0, row),
+                    if is_cursor_row { YELLOW } else { WHITE },
+                );
+
+                for column in 0..COLUMN_COUNT {
+                    let (card, id) = column_card_and_id(rank_index, column);
+                    let (text, _) = cell(&self.marks, cards, memories, card, id);
+
+                    let is_cursor = is_cursor_row && column == self.cursor_column;
+
+                    let char_column = LABEL_CHARS
+                        + unscaled::Inner::from(column / Suit::COUNT) * GROUP_CHARS
+                        + unscaled::Inner::from(column % Suit::COUNT) * CELL_CHARS;
+
+                    commands.print_char(
+                        if is_cursor && text == Mark::Blank.text() { b'_' } else { text },
+                        at(char_column, row),
+                        if is_cursor { YELLOW } else { WHITE },
+                    );
+                }
+            }
+
+            let row = 3 + unscaled::Inner::from(Rank::COUNT);
+            commands.print_line(LEGEND, at(0, row), WHITE);
+            commands.print_line(&keys::with_keys(CONTROLS, bindings), at(0, row + 1), WHITE);
+
+            input.pressed_this_frame(Button::B)
+        }
+    }
+}
+
+pub mod remaining {
+    use super::*;
+    use notepad::{SUIT_TEXT, certain_holder};
+
+    /// Where a card is, as far as the player can be sure.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Whereabouts {
+        Hand(HandId),
+        Basket(HandId),
+        Unaccounted,
+    }
 
-            let full_width = max_edge.saturating_point_sub(min_edge);
-            let usable_height = full_width.saturating_sub(span);
+    pub fn whereabouts(
+        cards: &Cards,
+        memories: &Memories,
+        card: Card,
+    ) -> Whereabouts {
+        match certain_holder(cards, memories, card) {
+            Some(id) if cards.baskets(id).contains(card) => Whereabouts::Basket(id),
+            Some(id) => Whereabouts::Hand(id),
+            None => Whereabouts::Unaccounted,
+        }
+    }
 
-            let offset = core::cmp::min(usable_height / len.into(), span);
+    /// The chance that the next card drawn from the deck is a particular kind
+    /// of card, as far as the player can tell.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct DrawOdds {
+        pub matching: u8,
+        /// How many cards could be on top of the deck. That is, the cards in
+        /// the deck plus the cards in the other players' hands that the player
+        /// has not seen.
+        pub out_of: u8,
+    }
 
-            XY {
-                x,
-                y: min_edge.saturating_add(offset * index.into())
+    impl DrawOdds {
+        pub fn percent(self) -> Option<u8> {
+            if self.out_of == 0 {
+                return None
             }
-        },
+
+            Some((
+                (u16::from(self.matching) * 100 + u16::from(self.out_of) / 2)
+                / u16::from(self.out_of)
+            ) as u8)
+        }
     }
-}
 
-fn perform_game_warden(
-    cards: &mut Cards,
-    animations: &mut Animations,
-    rng: &mut Xs,
-    Targeting {
-        source,
-        target,
-    }: Targeting,
-) -> Option<()> {
-    let hand = cards.hand_mut(target);
+    pub fn draw_odds(
+        cards: &Cards,
+        memories: &Memories,
+        rank: Rank,
+    ) -> DrawOdds {
+        let mut matching = 0;
+        for suit in Suit::ALL {
+            if whereabouts(cards, memories, fish_card(rank, suit)) == Whereabouts::Unaccounted {
+                matching += 1;
+            }
+        }
 
-    if hand.is_empty() {
-        return None;
+        let mut out_of = cards.deck.len();
+        for id in HandId::CPUS {
+            let hand = cards.hand(id);
+            let known_count = hand.iter()
+                .filter(|&card| whereabouts(cards, memories, card) == Whereabouts::Hand(id))
+                .count() as u8;
+            out_of += hand.len().saturating_sub(known_count);
+        }
+
+        DrawOdds {
+            matching,
+            out_of,
+        }
     }
 
-    let i = xs::range(rng, 0..hand.len() as u32) as CardIndex;
+    pub fn render(
+        commands: &mut Commands,
+        cards: &Cards,
+        memories: &Memories,
+        rank: Rank,
+    ) {
+        commands.draw_nine_slice(gfx::NineSlice::Window, REMAINING_WINDOW);
 
-    let at = get_card_position(
-        spread(target),
-        hand.len(),
-        i,
-    );
+        let base_xy = REMAINING_WINDOW.xy() + WINDOW_CONTENT_OFFSET + WINDOW_CONTENT_OFFSET;
 
-    let () = hand
-        .remove(i)
-        .map(|card| {
-            animations.push(Animation {
-                card,
-                at,
-                target: DECK_XY,
-                action: AnimationAction::PerformGameWarden,
-                .. <_>::default()
-            })
-        })?;
+        let at = |row: unscaled::Inner| {
+            base_xy + H(row * gfx::CHAR_ADVANCE_H.get().get())
+        };
 
-    discard_game_warden(
-        cards,
-        animations,
-        source.into(),
-    );
+        commands.print_line(Rank::TEXT[rank as usize], at(0), WHITE);
+
+        let mut line = Vec::with_capacity(16);
+        for (i, suit) in Suit::ALL.into_iter().enumerate() {
+            line.clear();
+            line.push(SUIT_TEXT[i]);
+            line.push(b' ');
+            match whereabouts(cards, memories, fish_card(rank, suit)) {
+                Whereabouts::Hand(HandId::Player) => line.extend(b"Yours"),
+                Whereabouts::Hand(id) => line.extend(HandId::TEXT[id as usize]),
+                Whereabouts::Basket(id) => {
+                    line.extend(HandId::TEXT[id as usize]);
+                    line.extend(b" basket");
+                },
+                Whereabouts::Unaccounted => line.push(b'?'),
+            }
 
-    Some(())
+            commands.print_line(&line, at(1 + i as unscaled::Inner), WHITE);
+        }
+
+        let odds = draw_odds(cards, memories, rank);
+
+        line.clear();
+        line.extend(b"Draw: ");
+        match odds.percent() {
+            Some(percent) => {
+                line.extend(odds.matching.to_string().as_bytes());
+                line.push(b'/');
+                line.extend(odds.out_of.to_string().as_bytes());
+                line.push(b' ');
+                line.extend(percent.to_string().as_bytes());
+                line.push(b'%');
+            },
+            None => line.push(b'-'),
+        }
+
+        commands.print_line(&line, at(1 + Suit::COUNT as unscaled::Inner), WHITE);
+    }
 }
 
 mod text {
@@ -4948,6 +6688,47 @@ mod text {
     }
 }
 
+fn hint_message(hint: Option<memories::Hint>) -> Vec<u8> {
+    use memories::HintReason;
+
+    let mut message = Vec::with_capacity(128);
+
+    let Some(hint) = hint else {
+        message.extend(b"No hint this time. Nobody has shown any sign of having the fish you need.");
+        return message
+    };
+
+    let target_text = HandId::TEXT[hint.target as usize];
+
+    message.extend(b"Hint: Ask ");
+    message.extend(target_text);
+    message.extend(b" for the ");
+    message.extend(Suit::TEXT[hint.suit as usize]);
+    message.push(b' ');
+    message.extend(Rank::TEXT[hint.rank as usize]);
+    message.extend(b". ");
+    message.extend(target_text);
+
+    match hint.reason {
+        HintReason::Seen => {
+            message.extend(b" was seen getting it.");
+        },
+        HintReason::AskedFor(count) => {
+            message.extend(b" asked for ");
+            message.extend(Rank::TEXT[hint.rank as usize]);
+            message.extend(match count {
+                0 | 1 => b" cards once." as &[u8],
+                2 => b" cards twice.",
+                3 => b" cards 3 times.",
+                4 => b" cards 4 times.",
+                _ => b" cards 5 or more times.",
+            });
+        },
+    }
+
+    message
+}
+
 fn fit_to_rest_of_window(
     base_xy: unscaled::XY,
     window: unscaled::Rect
@@ -5196,6 +6977,17 @@ const DEAD_IN_THE_WATER_WINDOW: unscaled::Rect = {
     }
 };
 
+const GAME_OVER_WINDOW: unscaled::Rect = {
+    const X_OFFSET: unscaled::Inner = 128 - 16;
+    const Y_OFFSET: unscaled::Inner = 80;
+    unscaled::Rect {
+        x: X(X_OFFSET),
+        y: Y(Y_OFFSET),
+        w: W(command::WIDTH - X_OFFSET * 2),
+        h: H(command::HEIGHT - Y_OFFSET * 2),
+    }
+};
+
 const PLAYER_PLAY_ANYTIME_WINDOW: unscaled::Rect = {
     const OFFSET: unscaled::Inner = 64;
     unscaled::Rect {
@@ -5251,4 +7043,465 @@ const NO_FISHING_WINDOW: unscaled::Rect = {
     }
 };
 
-const CONFIRM_BUTTON_HEIGHT: H = H(64);
\ No newline at end of file
+/// Over the top of Cpu 2's hand, which doesn't matter much in the tutorial.
+const TUTORIAL_WINDOW: unscaled::Rect = {
+    const X_OFFSET: unscaled::Inner = CARD_WIDTH.get() * 5 / 4;
+    const WIN_H: unscaled::Inner = gfx::CHAR_ADVANCE_H.get().get() * 4
+    + WINDOW_CONTENT_OFFSET.h.get() * 4;
+
+    unscaled::Rect {
+        x: X(X_OFFSET),
+        y: Y(0),
+        w: W(command::WIDTH - X_OFFSET * 2),
+        h: H(WIN_H),
+    }
+};
+
+const NOTEPAD_WINDOW: unscaled::Rect = {
+    const X_OFFSET: unscaled::Inner = CARD_WIDTH.get() / 4;
+    // Header rows, a row per rank, a gap, then the legend and controls.
+    const WIN_H: unscaled::Inner = gfx::CHAR_ADVANCE_H.get().get()
+    * (2 + Rank::COUNT as unscaled::Inner + 3)
+    + WINDOW_CONTENT_OFFSET.h.get() * 4;
+
+    unscaled::Rect {
+        x: X(X_OFFSET),
+        y: Y((command::HEIGHT - WIN_H) / 2),
+        w: W(command::WIDTH - X_OFFSET * 2),
+        h: H(WIN_H),
+    }
+};
+
+const REMAINING_WINDOW: unscaled::Rect = {
+    // Wide enough for "P Cpu 3 basket".
+    const WIN_W: unscaled::Inner = gfx::CHAR_ADVANCE_W.get().get() * 15
+    + WINDOW_CONTENT_OFFSET.w.get() * 4;
+    // The rank, a row per suit, then the odds.
+    const WIN_H: unscaled::Inner = gfx::CHAR_ADVANCE_H.get().get()
+    * (2 + Suit::COUNT as unscaled::Inner)
+    + WINDOW_CONTENT_OFFSET.h.get() * 4;
+
+    unscaled::Rect {
+        x: X(DECK_XY.x.get() + CARD_WIDTH.get() + WINDOW_CONTENT_OFFSET.w.get()),
+        y: Y(DECK_XY.y.get() + (CARD_HEIGHT.get() - WIN_H) / 2),
+        w: W(WIN_W),
+        h: H(WIN_H),
+    }
+};
+
+/// Shown in the same place as the remaining cards, since they are never shown
+/// at the same time.
+const ARRANGE_WINDOW: unscaled::Rect = REMAINING_WINDOW;
+
+const CONFIRM_BUTTON_HEIGHT: H = H(64);
+
+#[cfg(test)]
+mod hint_message_returns_the_expected_result {
+    use super::*;
+
+    #[test]
+    fn on_this_asked_for_example() {
+        let hint = memories::Hint {
+            rank: Rank::Whale,
+            suit: Suit::Red,
+            target: HandId::Cpu2,
+            reason: memories::HintReason::AskedFor(2),
+        };
+
+        assert_eq!(
+            hint_message(Some(hint)),
+            b"Hint: Ask Cpu 2 for the Red Whale. Cpu 2 asked for Whale cards twice.",
+        );
+    }
+}
+
+#[cfg(test)]
+mod no_more_baskets_can_be_made_returns_the_expected_result {
+    use super::*;
+
+    #[test]
+    fn when_a_rank_is_spread_across_the_hands() {
+        let mut cards = Cards::default();
+        cards.player.push(fish_card(Rank::Whale, Suit::Red));
+        cards.cpu1.push(fish_card(Rank::Whale, Suit::Green));
+        cards.cpu2.push(fish_card(Rank::Whale, Suit::Blue));
+        cards.cpu3.push(fish_card(Rank::Whale, Suit::Yellow));
+        cards.deck.push(fish_card(Rank::Whale, Suit::Purple));
+
+        assert!(!cards.no_more_baskets_can_be_made());
+    }
+
+    #[test]
+    fn when_the_dead_scuba_diver_left_a_fish_behind() {
+        let mut cards = Cards::default();
+        for suit in [Suit::Red, Suit::Green, Suit::Blue, Suit::Yellow] {
+            cards.cpu1_baskets.push(fish_card(Rank::Whale, suit));
+        }
+        cards.cpu1_baskets.push(zingers::DEAD_SCUBA_DIVER);
+        cards.player.push(fish_card(Rank::Whale, Suit::Purple));
+        cards.cpu2.push(zingers::NO_FISHING);
+
+        assert!(cards.no_more_baskets_can_be_made());
+    }
+}
+
+#[cfg(test)]
+mod timeline_runs_callbacks_in_the_expected_order {
+    use super::*;
+    use std::{cell::Cell, rc::Rc};
+
+    fn log(card: Card) -> impl Fn(&mut State, &mut Speaker) {
+        move |state, _| state.cards.discard.push(card)
+    }
+
+    #[test]
+    fn on_this_sequence_in_parallel_example() {
+        let mut state = State::default();
+        let mut speaker = Speaker::default();
+
+        state.animations.push(
+            Animation::parallel([
+                Animation::tween(0, DECK_XY, DISCARD_XY)
+                    .duration(3)
+                    .then(log(0)),
+                Animation::sequence([
+                    Animation::wait(1),
+                    Animation::tween(1, DECK_XY, DISCARD_XY)
+                        .duration(1)
+                        .then(log(1)),
+                ]).then(log(2)),
+            ]).then(log(3))
+        );
+
+        for _ in 0..3 {
+            assert!(!state.animations.all_done());
+            state.tick(&mut speaker);
+        }
+
+        assert!(state.animations.all_done());
+        assert_eq!(state.cards.discard.iter().collect::<Vec<_>>(), [1, 2, 0, 3]);
+    }
+
+    #[test]
+    fn on_this_more_animations_than_cards_example() {
+        let mut state = State::default();
+        let mut speaker = Speaker::default();
+
+        const COUNT: u32 = models::DECK_SIZE as u32 * 3;
+
+        let completed = Rc::new(Cell::new(0));
+
+        for _ in 0..COUNT {
+            let completed = Rc::clone(&completed);
+            state.animations.push(
+                Animation::tween(0, DECK_XY, DISCARD_XY)
+                    .then(move |_, _| completed.set(completed.get() + 1))
+            );
+        }
+
+        while !state.animations.all_done() {
+            state.tick(&mut speaker);
+        }
+
+        assert_eq!(completed.get(), COUNT);
+    }
+}
+
+#[cfg(test)]
+mod moving_cards_end_up_in_the_expected_place {
+    use super::*;
+
+    fn finish_animations(state: &mut State) {
+        let mut speaker = Speaker::default();
+        while !state.animations.all_done() {
+            state.tick(&mut speaker);
+        }
+    }
+
+    #[test]
+    fn when_a_basket_is_completed() {
+        let mut state = State::default();
+        let mut speaker = Speaker::default();
+
+        let id = HandId::Cpu2;
+        let zinger = zingers::THE_NET;
+        state.cards.hand_mut(id).push(zinger);
+        for suit in Suit::ALL.into_iter().skip(1) {
+            state.cards.hand_mut(id).push(fish_card(Rank::Barnacle, suit));
+        }
+
+        state.add_to_hand(&mut speaker, id, fish_card(Rank::Barnacle, Suit::ALL[0]));
+
+        // The basket is on its way to the pile.
+        assert_eq!(state.cards.hand(id).iter().collect::<Vec<_>>(), [zinger]);
+        assert!(state.cards.baskets(id).is_empty());
+        assert!(!state.animations.all_done());
+
+        finish_animations(&mut state);
+
+        assert_eq!(state.cards.hand(id).iter().collect::<Vec<_>>(), [zinger]);
+        assert_eq!(state.cards.baskets(id).len(), Suit::COUNT);
+        assert_eq!(state.cards.points(id), u32::from(Rank::Barnacle.points()));
+    }
+
+    #[test]
+    fn when_a_card_is_shuffled_into_the_deck() {
+        let mut state = State::default();
+
+        for card in 0..10 {
+            state.cards.deck.push(card);
+        }
+        let card = zingers::THE_GAME_WARDEN;
+
+        state.shuffle_into_deck(card);
+
+        // The cards stay in the deck while they are shown scattering.
+        assert_eq!(state.cards.deck.len(), 11);
+        assert!(state.cards.deck.contains(card));
+        assert!(!state.animations.all_done());
+
+        finish_animations(&mut state);
+
+        assert_eq!(state.cards.deck.len(), 11);
+        assert!(state.cards.deck.contains(card));
+    }
+}
+
+#[cfg(test)]
+mod cards_heading_to_the_side_hands_are_turned {
+    use super::*;
+
+    #[test]
+    fn while_being_dealt() {
+        let mut state = State::new(<_>::default());
+
+        // Each hand's first card has started moving by now.
+        for _ in 0..HandId::COUNT {
+            state.animations.advance();
+        }
+
+        let mut seen = Vec::new();
+        state.animations.for_each_moving_card(|tween, _| {
+            seen.push((tween.to, tween.transform));
+        });
+
+        for id in HandId::ALL {
+            let to = get_card_insert_position(spread(id), 1);
+
+            assert!(
+                seen.contains(&(to, turn(id))),
+                "{id:?}: {seen:?}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod relayout_slides_cards_to_their_new_spots {
+    use super::*;
+    use relayout::{Relayout, RELAYOUT_FRAMES};
+
+    fn slides_when_a_card_is_removed_from_the_middle(id: HandId) {
+        let mut relayout = Relayout::default();
+        let mut hand = Hand::default();
+        for card in 0..3 {
+            hand.push(card);
+        }
+
+        relayout.update(id, &hand);
+        let before = relayout.position(id, &hand, 2);
+
+        hand.remove(1);
+        relayout.update(id, &hand);
+
+        let target = get_card_position(spread(id), hand.len(), 1);
+        assert_ne!(before, target);
+
+        let during = relayout.position(id, &hand, 1);
+        assert_ne!(during, before);
+        assert_ne!(during, target);
+
+        for _ in 1..RELAYOUT_FRAMES {
+            relayout.update(id, &hand);
+        }
+
+        for i in 0..hand.len() {
+            assert_eq!(
+                relayout.position(id, &hand, i),
+                get_card_position(spread(id), hand.len(), i)
+            );
+        }
+    }
+
+    #[test]
+    fn in_a_left_to_right_spread() {
+        slides_when_a_card_is_removed_from_the_middle(HandId::Player);
+    }
+
+    #[test]
+    fn in_a_top_to_bottom_spread() {
+        slides_when_a_card_is_removed_from_the_middle(HandId::Cpu1);
+    }
+
+    #[test]
+    fn without_holding_up_the_timeline() {
+        let mut state = State::default();
+        let mut commands = Commands::default();
+        let mut speaker = Speaker::default();
+
+        for card in 0..8 {
+            state.cards.cpu2.push(card);
+        }
+        update_and_render(&mut commands, &mut state, Input::default(), &Bindings::default(), &mut speaker);
+
+        const REMOVED: CardIndex = 3;
+        state.cards.cpu2.remove(REMOVED);
+        update_and_render(&mut commands, &mut state, Input::default(), &Bindings::default(), &mut speaker);
+
+        let len = state.cards.cpu2.len();
+        // Whichever of the cards after the removed one has a new spot to go to.
+        let moving = (REMOVED..len)
+            .find(|&i| {
+                get_card_position(spread(HandId::Cpu2), len + 1, i + 1)
+                != get_card_position(spread(HandId::Cpu2), len, i)
+            })
+            .expect("some card should need to move");
+
+        assert!(state.animations.all_done());
+        assert_ne!(
+            state.relayout.position(HandId::Cpu2, &state.cards.cpu2, moving),
+            get_card_position(spread(HandId::Cpu2), len, moving)
+        );
+    }
+}
+
+#[cfg(test)]
+mod ui_responds_to_the_pointer {
+    use super::*;
+    use platform_types::Pointer;
+
+    const RECT: Rect = Rect { x: X(10), y: Y(10), w: W(40), h: H(30) };
+
+    fn with_group<Out>(
+        ctx: &mut ui::Context,
+        input: Input,
+        f: impl FnOnce(&mut ui::Group) -> Out,
+    ) -> Out {
+        let mut commands = Commands::default();
+        let mut speaker = Speaker::default();
+
+        ctx.frame_init();
+
+        f(&mut ui::Group {
+            commands: &mut commands,
+            ctx,
+            input,
+            speaker: &mut speaker,
+        })
+    }
+
+    fn do_submit(ctx: &mut ui::Context, input: Input) -> bool {
+        with_group(ctx, input, |group| do_button(
+            group,
+            ButtonSpec {
+                id: Submit,
+                rect: RECT,
+                text: b"Submit",
+            }
+        ))
+    }
+
+    fn next_frame(input: &mut Input, pointer: Pointer) {
+        input.previous_pointer = input.pointer;
+        input.pointer = pointer;
+    }
+
+    const OVER: Option<XY> = Some(XY { x: X(20), y: Y(20) });
+    const ELSEWHERE: Option<XY> = Some(XY { x: X(100), y: Y(100) });
+
+    #[test]
+    fn when_a_button_is_clicked() {
+        let mut ctx = ui::Context::default();
+        let mut input = Input::default();
+
+        next_frame(&mut input, Pointer { xy: OVER, down: false });
+        assert!(!do_submit(&mut ctx, input));
+        next_frame(&mut input, Pointer { xy: OVER, down: true });
+        assert!(!do_submit(&mut ctx, input));
+        next_frame(&mut input, Pointer { xy: OVER, down: false });
+        assert!(do_submit(&mut ctx, input));
+    }
+
+    #[test]
+    fn when_a_button_is_pressed_on_but_released_elsewhere() {
+        let mut ctx = ui::Context::default();
+        let mut input = Input::default();
+
+        next_frame(&mut input, Pointer { xy: OVER, down: true });
+        assert!(!do_submit(&mut ctx, input));
+        next_frame(&mut input, Pointer { xy: ELSEWHERE, down: true });
+        assert!(!do_submit(&mut ctx, input));
+        next_frame(&mut input, Pointer { xy: ELSEWHERE, down: false });
+        assert!(!do_submit(&mut ctx, input));
+        assert_eq!(ctx.active, ui::Id::Zero);
+    }
+
+    #[test]
+    fn when_a_quick_select_chevron_is_clicked() {
+        let mut ctx = ui::Context::default();
+        let mut input = Input::default();
+
+        let bottom = XY { x: X(20), y: Y(RECT.y.get() + RECT.h.get() - 1) };
+
+        next_frame(&mut input, Pointer { xy: Some(bottom), down: true });
+        with_group(&mut ctx, input, |group| ui::draw_quick_select(group, RECT, NetPredicate));
+
+        assert_eq!(ctx.hot, NetPredicate);
+        assert_eq!(ctx.dir_repeated_this_frame(input), Some(Dir::Down));
+
+        // It only counts for the frame it was clicked on.
+        next_frame(&mut input, Pointer { xy: Some(bottom), down: true });
+        with_group(&mut ctx, input, |group| ui::draw_quick_select(group, RECT, NetPredicate));
+
+        assert_eq!(ctx.dir_repeated_this_frame(input), None);
+    }
+}
+
+#[cfg(test)]
+mod holding_a_direction_repeats {
+    use super::*;
+    use platform_types::Repeat;
+
+    #[test]
+    fn when_selecting_a_card_in_hand() {
+        let mut commands = Commands::default();
+        let mut speaker = Speaker::default();
+        let mut state = State::tutorial();
+        let mut input = Input::default();
+        input.repeat = Repeat { delay: 10, interval: 5 };
+
+        while !state.animations.all_done() {
+            update_and_render(&mut commands, &mut state, input, &Bindings::default(), &mut speaker);
+        }
+        state.selection.card_index = 0;
+
+        input.gamepad.insert(Button::RIGHT);
+
+        let mut indexes = Vec::new();
+        for _ in 0..21 {
+            update_and_render(&mut commands, &mut state, input, &Bindings::default(), &mut speaker);
+            input.end_frame();
+
+            indexes.push(state.selection.card_index);
+        }
+
+        // Once on the press, then again after the delay, then after each
+        // interval.
+        assert_eq!(indexes[0], 1);
+        assert_eq!(indexes[9], 1);
+        assert_eq!(indexes[10], 2);
+        assert_eq!(indexes[14], 2);
+        assert_eq!(indexes[15], 3);
+        assert_eq!(indexes[20], 4);
+    }
+}
@@ -1,6 +1,6 @@
 use gfx::{Commands};
-use platform_types::{Button, Input, Speaker};
-use models::{Rank, Suit, Zinger, fish_card, zinger_card};
+use platform_types::{unscaled, Bindings, Button, Input, Speaker};
+use models::{HandId, NetPredicate, Predicate, Rank, Suit, Zinger, fish_card, get_rank, zinger_card, zingers};
 use game::*;
 
 const SOME_SEED: xs::Seed = [42, 42, 42, 42, 42, 42, 42, 42, 42, 42, 42, 42, 42, 42, 42, 42];
@@ -64,12 +64,258 @@ dbg!();
     // TODO? Asserts of some kind?
 }
 
+#[test]
+fn cpu1_plays_the_net_on_the_dogfish_basket() {
+    let mut u_and_r = UAndR::with_hardcoded_mode(
+        SOME_SEED,
+        HardcodedMode::Cpu1PlayNetPlayerNoFishing,
+    );
+
+    macro_rules! decline_until_the_stack_is_empty {
+        () => {
+            for _ in 0..1000 {
+                if u_and_r.state.stack.is_empty() {
+                    break
+                }
+                u_and_r.input.gamepad.remove(Button::B);
+                u_and_r.call_no_button();
+                u_and_r.call(Button::B);
+            }
+        }
+    }
+
+    // Put the Purple Dogfish, which the mode gave the player, at the start of
+    // the hand. The selection starts on the last card, so one press right
+    // wraps around to it.
+    let purple_dogfish = fish_card(Rank::Dogfish, Suit::Purple);
+    assert!(u_and_r.state.cards.player.contains(purple_dogfish));
+    force_into_start_of_hand(&mut u_and_r.state, purple_dogfish, FullHandId::Player);
+
+    // The player asks Cpu 2 for each of the dogfish it has, getting another
+    // turn each time. The ask menu starts on Cpu 1, and Red.
+    for button in [Button::RIGHT, Button::A, Button::UP, Button::RIGHT, Button::RIGHT, Button::A] {
+        u_and_r.press(button);
+    }
+    for button in [Button::A, Button::UP, Button::RIGHT, Button::UP, Button::RIGHT, Button::A] {
+        u_and_r.press(button);
+    }
+    for button in [Button::A, Button::UP, Button::RIGHT, Button::UP, Button::UP, Button::RIGHT, Button::A] {
+        u_and_r.press(button);
+    }
+
+    for suit in [Suit::Red, Suit::Green, Suit::Blue] {
+        assert!(
+            u_and_r.state.cards.player.contains(fish_card(Rank::Dogfish, suit)),
+            "The player did not get the {suit:?} Dogfish"
+        );
+    }
+
+    // Then the player asks Cpu 1 for the last one, and does not get it, since
+    // Cpu 3 has it. The player declines to use the Two-Fisted Fisherman, goes
+    // fish, and declines to respond, which ends their turn.
+    for button in [Button::A, Button::RIGHT, Button::UP, Button::UP, Button::UP, Button::RIGHT, Button::A] {
+        u_and_r.press(button);
+    }
+    for button in [Button::B, Button::A] {
+        u_and_r.press(button);
+    }
+    decline_until_the_stack_is_empty!();
+
+    // From here Cpu 1 should be able to infer that the player is about to fill
+    // the dogfish basket, so it makes sense to use The Net to scoop one out.
+    for _ in 0..1000 {
+        if let Some(Play { kind: PlayKind::TheNet { .. }, .. }) = u_and_r.state.stack.last() {
+            break
+        }
+        u_and_r.call_no_button();
+    }
+
+    let Some(Play { kind: PlayKind::TheNet { targeting, predicate }, .. }) = u_and_r.state.stack.last() else {
+        panic!("Cpu 1 did not play The Net!");
+    };
+
+    assert_eq!(targeting.source, HandId::Cpu1);
+    assert_eq!(targeting.target, HandId::Player);
+    assert_eq!(*predicate, NetPredicate::Rank(Rank::Dogfish));
+
+    // Decline to use No Fishing
+    decline_until_the_stack_is_empty!();
+    u_and_r.call_until_animations_are_done();
+
+    assert!(
+        u_and_r.state.cards.cpu1.iter().any(|card| get_rank(card) == Some(Rank::Dogfish)),
+        "Cpu 1 did not get a dogfish!"
+    );
+}
+
+#[test]
+fn cpus_using_search_can_play_through_several_turns() {
+    let mut u_and_r = UAndR::new(SOME_SEED);
+
+    u_and_r.state.cpu_strategies = [
+        CpuStrategy::Search(search::Budget::Iterations { per_move: 64, per_frame: 16 });
+        3
+    ];
+
+    let mut searched_across_frames = false;
+    // Enough frames for the player's random mashing to end their turn, which
+    // depends on how long the animations take.
+    for _ in 0..4096 {
+        if let CpuMenu::Searching(_) = u_and_r.state.cpu_menu {
+            searched_across_frames = true;
+        }
+
+        let button = gen_button(&mut u_and_r.state.rng);
+        u_and_r.input.gamepad = <_>::default();
+        u_and_r.call(button);
+    }
+
+    assert!(searched_across_frames);
+}
+
+#[test]
+fn the_player_can_get_a_hint_from_what_they_have_seen() {
+    let mut u_and_r = UAndR::new(SOME_SEED);
+
+    // Nothing has been seen yet, so there is nothing to hint at, and asking
+    // for a hint does not count as using one.
+    u_and_r.call(Button::START);
+
+    assert_eq!(u_and_r.state.stats.hints_used, 0);
+
+    // Close the message saying so.
+    u_and_r.input.gamepad = <_>::default();
+    u_and_r.call(Button::B);
+    u_and_r.input.gamepad = <_>::default();
+    u_and_r.call_no_button();
+
+    let state = &mut u_and_r.state;
+
+    let whale = fish_card(Rank::Whale, Suit::Green);
+    force_into_start_of_hand(state, whale, FullHandId::Player);
+
+    state.memories.asked_for(HandId::Cpu2, Predicate::RankSuit(Rank::Whale, Suit::Red));
+    state.memories.asked_for(HandId::Cpu2, Predicate::RankSuit(Rank::Whale, Suit::Blue));
+
+    u_and_r.call(Button::START);
+
+    assert_eq!(u_and_r.state.stats.hints_used, 1);
+}
+
+#[test]
+fn the_player_can_mark_suspicions_in_the_notepad() {
+    let mut u_and_r = UAndR::new(SOME_SEED);
+
+    let card = fish_card(Rank::Barnacle, Suit::Red);
+
+    assert_eq!(
+        notepad::certain_holder(&u_and_r.state.cards, &u_and_r.state.memories, card),
+        None,
+        "precondition failure: The cell we mark should not already be filled in"
+    );
+
+    // Open the notepad, then move from the player's column to Cpu 1's.
+    for button in [Button::B, Button::RIGHT, Button::RIGHT, Button::RIGHT, Button::RIGHT, Button::RIGHT, Button::A] {
+        u_and_r.input.gamepad = <_>::default();
+        u_and_r.call(button);
+        u_and_r.input.gamepad = <_>::default();
+        u_and_r.call_no_button();
+    }
+
+    assert_eq!(u_and_r.state.notepad.mark(card, HandId::Cpu1), notepad::Mark::Suspected);
+    assert_eq!(u_and_r.state.notepad.mark(card, HandId::Cpu2), notepad::Mark::Blank);
+}
+
+#[test]
+fn draw_odds_only_count_what_the_player_could_know() {
+    let u_and_r = UAndR::tutorial();
+
+    let state = &u_and_r.state;
+
+    // The player has the Red, Green and Blue dogfish. Cpu 1 has the Yellow one,
+    // but the player hasn't seen it, and the Purple one is in the deck.
+    assert_eq!(
+        remaining::whereabouts(&state.cards, &state.memories, fish_card(Rank::Dogfish, Suit::Red)),
+        remaining::Whereabouts::Hand(HandId::Player)
+    );
+    assert_eq!(
+        remaining::whereabouts(&state.cards, &state.memories, fish_card(Rank::Dogfish, Suit::Yellow)),
+        remaining::Whereabouts::Unaccounted
+    );
+
+    // Everything besides the player's hand is unseen.
+    let out_of = models::DECK_SIZE - state.cards.player.len();
+
+    assert_eq!(
+        remaining::draw_odds(&state.cards, &state.memories, Rank::Dogfish),
+        remaining::DrawOdds { matching: 2, out_of }
+    );
+}
+
+#[test]
+fn the_player_can_sort_and_rearrange_their_hand() {
+    let mut u_and_r = UAndR::tutorial();
+
+    // Open the arrange menu, then order by rank.
+    u_and_r.press_without_waiting(Button::DOWN);
+    u_and_r.press_without_waiting(Button::DOWN);
+
+    assert_eq!(u_and_r.state.hand_sort, HandSort::Rank);
+    assert_eq!(
+        u_and_r.state.cards.player.iter().collect::<Vec<_>>(),
+        [
+            fish_card(Rank::Dogfish, Suit::Red),
+            fish_card(Rank::Dogfish, Suit::Green),
+            fish_card(Rank::Dogfish, Suit::Blue),
+            fish_card(Rank::Shrimp, Suit::Blue),
+            zingers::NO_FISHING,
+        ]
+    );
+
+    // The Blue Dogfish was selected before sorting, so it should still be,
+    // and moving it should go back to keeping the cards where they are put.
+    u_and_r.press_without_waiting(Button::LEFT);
+
+    assert_eq!(u_and_r.state.hand_sort, HandSort::AsPlaced);
+    assert_eq!(
+        u_and_r.state.cards.player.iter().collect::<Vec<_>>(),
+        [
+            fish_card(Rank::Dogfish, Suit::Red),
+            fish_card(Rank::Dogfish, Suit::Blue),
+            fish_card(Rank::Dogfish, Suit::Green),
+            fish_card(Rank::Shrimp, Suit::Blue),
+            zingers::NO_FISHING,
+        ]
+    );
+}
+
+#[test]
+fn the_arrange_window_names_the_keys_that_are_bound() {
+    let mut u_and_r = UAndR::tutorial();
+    u_and_r.bindings = Bindings::from_config("B = K\n");
+
+    // Open the arrange menu, then draw a frame of it by itself.
+    u_and_r.press_without_waiting(Button::DOWN);
+    u_and_r.commands.clear();
+    u_and_r.call_no_button();
+
+    let printed: String = u_and_r.commands.slice().iter()
+        .filter_map(|command| match gfx::sprite_of(command) {
+            gfx::Sprite::Char(byte) => Some(char::from(byte)),
+            _ => None,
+        })
+        .collect();
+
+    assert!(printed.contains("Z/K: Done"), "{printed}");
+}
+
 /// A testhelper to call game::update_and_render
 struct UAndR {
     commands: Commands,
     state: State,
     speaker: Speaker,
     input: Input,
+    bindings: Bindings,
 }
 
 impl UAndR {
@@ -79,6 +325,7 @@ impl UAndR {
             state: State::new(seed),
             speaker: Speaker::default(),
             input: Input::default(),
+            bindings: Bindings::default(),
         };
 
         while !output.state.animations.all_done() {
@@ -88,21 +335,63 @@ impl UAndR {
         output
     }
 
+    fn with_hardcoded_mode(seed: xs::Seed, mode: HardcodedMode) -> Self {
+        let mut output = Self {
+            commands: Commands::default(),
+            state: State::with_hardcoded_mode(seed, mode),
+            speaker: Speaker::default(),
+            input: Input::default(),
+            bindings: Bindings::default(),
+        };
+
+        output.call_until_animations_are_done();
+
+        output
+    }
+
+    fn tutorial() -> Self {
+        let mut output = Self {
+            commands: Commands::default(),
+            state: State::tutorial(),
+            speaker: Speaker::default(),
+            input: Input::default(),
+            bindings: Bindings::default(),
+        };
+
+        output.call_until_animations_are_done();
+
+        output
+    }
+
     fn call(&mut self, button: Button) {
         self.input.gamepad.insert(button);
 
         self.call_no_button();
     }
 
+    fn press(&mut self, button: Button) {
+        self.press_without_waiting(button);
+        self.call_until_animations_are_done();
+    }
+
+    fn press_without_waiting(&mut self, button: Button) {
+        self.call(button);
+        // Buttons are activated on release.
+        self.input.gamepad = <_>::default();
+        self.call_no_button();
+    }
+
     fn call_no_button(&mut self) {
         update_and_render(
             &mut self.commands,
             &mut self.state,
             self.input,
+            &self.bindings,
             &mut self.speaker,
         );
 
         self.input.previous_gamepad = self.input.gamepad;
+        self.input.previous_pointer = self.input.pointer;
     }
 
     fn call_until_animations_are_done(&mut self) {
@@ -124,4 +413,216 @@ const GAME_BUTTONS: [Button; 7] = [
 
 fn gen_button(rng: &mut xs::Xs) -> Button {
     GAME_BUTTONS[xs::range(rng, 0..GAME_BUTTONS.len() as u32) as usize]
-}
\ No newline at end of file
+}
+
+#[test]
+fn the_search_playouts_agree_with_the_game_on_the_tutorial_asks() {
+    let mut u_and_r = UAndR::tutorial();
+
+    let cards = &u_and_r.state.cards;
+    let mut sim = search::Sim::new(
+        HandId::ALL.map(|id| cards.hand(id)),
+        &cards.deck,
+    );
+
+    let check = |u_and_r: &UAndR, sim: &search::Sim, went_again: bool| {
+        let cards = &u_and_r.state.cards;
+        for id in HandId::ALL {
+            let mut hand: Vec<_> = cards.hand(id).iter().collect();
+            hand.sort();
+            assert_eq!(hand, sim.hand(id), "{id:?}'s hand");
+            assert_eq!(cards.points(id), sim.points(id), "{id:?}'s points");
+        }
+        assert_eq!(cards.deck.iter().collect::<Vec<_>>(), sim.deck());
+        assert_eq!(u_and_r.state.turn_id == HandId::Player, went_again);
+    };
+
+    // Ask Cpu 1 for the Yellow Dogfish.
+    for button in [Button::A, Button::RIGHT, Button::UP, Button::UP, Button::UP, Button::RIGHT, Button::A] {
+        u_and_r.press(button);
+    }
+    let went_again = sim.ask(
+        HandId::Player,
+        search::Ask { rank: Rank::Dogfish, suit: Suit::Yellow, target: HandId::Cpu1 },
+    );
+    check(&u_and_r, &sim, went_again);
+
+    // Ask Cpu 2 for the Purple Dogfish, then go fish.
+    for button in [Button::A, Button::UP, Button::RIGHT, Button::DOWN, Button::RIGHT, Button::A, Button::A] {
+        u_and_r.press(button);
+    }
+    let went_again = sim.ask(
+        HandId::Player,
+        search::Ask { rank: Rank::Dogfish, suit: Suit::Purple, target: HandId::Cpu2 },
+    );
+    check(&u_and_r, &sim, went_again);
+}
+
+#[test]
+fn the_tutorial_can_be_followed_through_to_the_end() {
+    let mut u_and_r = UAndR::tutorial();
+
+    let step = |u_and_r: &UAndR| u_and_r.state.tutorial.as_ref().map(|t| t.step);
+
+    assert_eq!(step(&u_and_r), Some(tutorial::Step::Ask));
+
+    // Ask Cpu 1 for the Yellow Dogfish.
+    for button in [Button::A, Button::RIGHT, Button::UP, Button::UP, Button::UP, Button::RIGHT, Button::A] {
+        u_and_r.press(button);
+    }
+
+    assert!(u_and_r.state.cards.player.contains(fish_card(Rank::Dogfish, Suit::Yellow)));
+    assert_eq!(step(&u_and_r), Some(tutorial::Step::Fish));
+
+    // Ask Cpu 2 for the Purple Dogfish, then go fish.
+    for button in [Button::A, Button::UP, Button::RIGHT, Button::DOWN, Button::RIGHT, Button::A, Button::A] {
+        u_and_r.press(button);
+    }
+
+    assert_eq!(u_and_r.state.cards.points(HandId::Player), 5);
+    assert_eq!(step(&u_and_r), Some(tutorial::Step::Basket));
+
+    // Ask Cpu 3 for the Yellow Shrimp, then go fish.
+    for button in [Button::A, Button::UP, Button::UP, Button::RIGHT, Button::UP, Button::UP, Button::UP, Button::RIGHT, Button::A, Button::A] {
+        u_and_r.press(button);
+    }
+
+    // The Cpus get a chance to respond before the turn ends.
+    for _ in 0..1000 {
+        if u_and_r.state.turn_id != HandId::Player {
+            break
+        }
+        u_and_r.call_no_button();
+    }
+
+    assert_eq!(u_and_r.state.turn_id, HandId::Cpu1);
+    assert_eq!(step(&u_and_r), Some(tutorial::Step::Response));
+
+    // Wait for Cpu 1 to ask, then play No Fishing.
+    for _ in 0..1000 {
+        if !u_and_r.state.cards.player.contains(zingers::NO_FISHING) {
+            break
+        }
+        u_and_r.press(Button::A);
+    }
+
+    assert!(u_and_r.state.cards.discard.contains(zingers::NO_FISHING));
+    assert_eq!(step(&u_and_r), Some(tutorial::Step::Done));
+
+    u_and_r.press(Button::START);
+
+    assert!(u_and_r.state.tutorial.is_none());
+}
+
+#[test]
+fn the_tutorial_names_the_keys_that_are_bound() {
+    let mut u_and_r = UAndR::tutorial();
+
+    let text = |u_and_r: &UAndR| {
+        u_and_r.state.tutorial.as_ref()
+            .map(|t| String::from_utf8_lossy(t.text()).replace('\n', " "))
+            .unwrap_or_default()
+    };
+
+    assert!(text(&u_and_r).contains("press Z to ask"), "{}", text(&u_and_r));
+
+    u_and_r.bindings = Bindings::from_config("A = K\n");
+    u_and_r.call_no_button();
+
+    assert!(text(&u_and_r).contains("press K to ask"), "{}", text(&u_and_r));
+}
+
+fn frames_until_the_deal_is_done(
+    animation_speed: timeline::Speed,
+    turn_id: HandId,
+    input: Input,
+) -> u32 {
+    let mut commands = Commands::default();
+    let mut state = State::new(SOME_SEED);
+    let mut speaker = Speaker::default();
+    state.animation_speed = animation_speed;
+    state.turn_id = turn_id;
+
+    let mut frames = 0;
+    while !state.animations.all_done() {
+        update_and_render(&mut commands, &mut state, input, &Bindings::default(), &mut speaker);
+        frames += 1;
+    }
+
+    frames
+}
+
+#[test]
+fn the_deal_takes_fewer_frames_at_faster_speeds() {
+    let frames = |speed| frames_until_the_deal_is_done(speed, HandId::Player, Input::default());
+
+    let slow = frames(timeline::Speed::Slow);
+    let normal = frames(timeline::Speed::Normal);
+    let fast = frames(timeline::Speed::Fast);
+    let instant = frames(timeline::Speed::Instant);
+
+    assert!(slow > normal, "{slow} > {normal}");
+    assert!(normal > fast, "{normal} > {fast}");
+    assert_eq!(instant, 1);
+}
+
+#[test]
+fn holding_start_only_speeds_through_other_players_turns() {
+    let mut holding_start = Input::default();
+    holding_start.gamepad.insert(Button::START);
+
+    let normal = frames_until_the_deal_is_done(timeline::Speed::Normal, HandId::Cpu1, Input::default());
+    let players_turn = frames_until_the_deal_is_done(timeline::Speed::Normal, HandId::Player, holding_start);
+    let cpus_turn = frames_until_the_deal_is_done(timeline::Speed::Normal, HandId::Cpu1, holding_start);
+
+    assert_eq!(players_turn, normal);
+    assert!(cpus_turn < normal, "{cpus_turn} < {normal}");
+}
+
+#[test]
+fn the_tutorial_can_be_followed_without_waiting_at_instant_speed() {
+    let mut u_and_r = UAndR::tutorial();
+    u_and_r.state.animation_speed = timeline::Speed::Instant;
+
+    // Ask Cpu 1 for the Yellow Dogfish.
+    for button in [Button::A, Button::RIGHT, Button::UP, Button::UP, Button::UP, Button::RIGHT, Button::A] {
+        u_and_r.press_without_waiting(button);
+        assert!(u_and_r.state.animations.all_done());
+    }
+
+    assert!(u_and_r.state.cards.player.contains(fish_card(Rank::Dogfish, Suit::Yellow)));
+
+    // Ask Cpu 2 for the Purple Dogfish, then go fish.
+    for button in [Button::A, Button::UP, Button::RIGHT, Button::DOWN, Button::RIGHT, Button::A, Button::A] {
+        u_and_r.press_without_waiting(button);
+        assert!(u_and_r.state.animations.all_done());
+    }
+
+    assert_eq!(u_and_r.state.cards.points(HandId::Player), 5);
+}
+
+#[test]
+fn the_player_can_pick_a_card_to_ask_with_the_pointer() {
+    let mut u_and_r = UAndR::tutorial();
+
+    let id = HandId::Player;
+    let hand = &u_and_r.state.cards.player;
+    let last = hand.len() - 1;
+    let xy = u_and_r.state.relayout.position(id, hand, last) + unscaled::W(4);
+
+    // Clicking the selected card is like pressing A on it.
+    u_and_r.input.pointer.xy = Some(xy);
+    u_and_r.input.pointer.down = true;
+    u_and_r.call_no_button();
+    u_and_r.input.pointer.down = false;
+    u_and_r.input.pointer.xy = None;
+    u_and_r.call_no_button();
+
+    // Ask Cpu 1 for the Yellow Dogfish, the same way the tutorial does after
+    // pressing A.
+    for button in [Button::RIGHT, Button::UP, Button::UP, Button::UP, Button::RIGHT, Button::A] {
+        u_and_r.press(button);
+    }
+
+    assert!(u_and_r.state.cards.player.contains(fish_card(Rank::Dogfish, Suit::Yellow)));
+}
@@ -8,7 +8,7 @@ macro_rules! compile_time_assert {
 
 use models::{Card, NetPredicate, Rank, Suit, get_rank, get_suit, get_zinger, Zinger};
 
-use platform_types::{ARGB, Command, sprite, unscaled, command::{self, Rect}, CHAR_W, CHAR_H, CHAR_WIDTH, CHAR_HEIGHT, FONT_WIDTH, CARD_WIDTH, CARD_HEIGHT, bytes_lines};
+use platform_types::{ARGB, Command, sprite, unscaled, command::{self, Rect, Transform, Fill}, CHAR_W, CHAR_H, CHAR_WIDTH, CHAR_HEIGHT, FONT_WIDTH, CARD_WIDTH, CARD_HEIGHT, bytes_lines};
 
 const FONT_OFFSET: sprite::H = unscaled::h_const_mul(
     card::IMAGE_H.get(),
@@ -29,6 +29,11 @@ impl Commands {
         self.commands.clear();
     }
 
+    /// Adds the given commands, as they are, after the ones already here.
+    pub fn extend_from_slice(&mut self, commands: &[Command]) {
+        self.commands.extend_from_slice(commands);
+    }
+
     pub fn sspr(
         &mut self,
         sprite_xy: sprite::XY,
@@ -39,16 +44,142 @@ impl Commands {
                 rect,
                 sprite_xy,
                 colour_override: 0,
+                transform: <_>::default(),
+                fill: <_>::default(),
+            }
+        );
+    }
+
+    /// Like `sspr`, but with the sprite turned by the given transform. Since
+    /// the rect may be clipped by the edges of the screen, this takes the
+    /// unclipped rect, so the flipped sprite can still be lined up with it.
+    pub fn sspr_transformed(
+        &mut self,
+        sprite_xy: sprite::XY,
+        rect: unscaled::Rect,
+        transform: Transform,
+    ) {
+        self.push_transformed(sprite_xy, rect, 0, transform);
+    }
+
+    /// Fills the rect with the colour, blended over whatever is already
+    /// drawn there by the colour's alpha.
+    pub fn fill(
+        &mut self,
+        rect: unscaled::Rect,
+        colour: ARGB,
+    ) {
+        self.push_command_if_useful(
+            Command {
+                rect: Rect::from_unscaled(rect),
+                sprite_xy: <_>::default(),
+                colour_override: colour,
+                transform: <_>::default(),
+                fill: Fill::Yes,
+            }
+        );
+    }
+
+    /// Darkens everything drawn so far, so that a window drawn after this
+    /// stands out from the table behind it.
+    pub fn dim_screen(&mut self) {
+        self.fill(SCREEN_RECT, DIM_COLOUR);
+    }
+
+    /// Draws a soft glow around the rect, which is brightest next to it and
+    /// fades out over `GLOW_LAYERS` steps of `GLOW_STEP` pixels. This is meant
+    /// to be drawn before whatever is in the rect.
+    pub fn draw_glow(
+        &mut self,
+        rect: unscaled::Rect,
+        colour: ARGB,
+    ) {
+        // The layers overlap, so the closer to the rect, the more of them a
+        // pixel is covered by.
+        for layer in (1..=GLOW_LAYERS).rev() {
+            let w = unscaled::W(GLOW_STEP * layer);
+            let h = unscaled::H(GLOW_STEP * layer);
+
+            self.fill(
+                unscaled::Rect {
+                    x: rect.x.saturating_sub(w),
+                    y: rect.y.saturating_sub(h),
+                    w: rect.w + w + w,
+                    h: rect.h + h + h,
+                },
+                colour,
+            );
+        }
+    }
+
+    fn push_transformed(
+        &mut self,
+        mut sprite_xy: sprite::XY,
+        unclipped: unscaled::Rect,
+        colour_override: ARGB,
+        transform: Transform,
+    ) {
+        let rect = Rect::from_unscaled(unclipped);
+
+        // The renderer only knows about the visible part of the rect, so when
+        // a flip moves the hidden part of the sprite to the other side, we
+        // skip over it here instead.
+        let visible = rect.unscaled();
+        let hidden_w = unclipped.w.get().saturating_sub(visible.w.get());
+        let hidden_h = unclipped.h.get().saturating_sub(visible.h.get());
+
+        if transform.flips_x() {
+            if transform.transposes() {
+                sprite_xy.y.0 += hidden_w;
+            } else {
+                sprite_xy.x.0 += hidden_w;
+            }
+        }
+
+        if transform.flips_y() {
+            if transform.transposes() {
+                sprite_xy.x.0 += hidden_h;
+            } else {
+                sprite_xy.y.0 += hidden_h;
+            }
+        }
+
+        self.push_command_if_useful(
+            Command {
+                rect,
+                sprite_xy,
+                colour_override,
+                transform,
+                fill: <_>::default(),
             }
         );
     }
 
     pub fn print_char(
         &mut self,
-        mut character: u8,
-        unscaled::XY { x, y } : unscaled::XY,
+        character: u8,
+        xy: unscaled::XY,
         colour_override: ARGB
     ) {
+        let (sprite_xy, rect) = Self::char_sprite_and_rect(character, xy);
+
+        self.push_command_if_useful(
+            Command {
+                rect: Rect::from_unscaled(rect),
+                sprite_xy,
+                colour_override,
+                transform: <_>::default(),
+                fill: <_>::default(),
+            }
+        );
+    }
+
+    /// Where on the sprite sheet the character is, and where it goes if it
+    /// is printed at `xy`.
+    fn char_sprite_and_rect(
+        mut character: u8,
+        unscaled::XY { x, y } : unscaled::XY,
+    ) -> (sprite::XY, unscaled::Rect) {
         let mut y = y;
         let mut font_offset = FONT_OFFSET;
 
@@ -78,20 +209,15 @@ impl Commands {
             }
         };
 
-        self.push_command_if_useful(
-            Command {
-                rect: Rect::from_unscaled(
-                    unscaled::Rect {
-                        x,
-                        y,
-                        w: CHAR_W.get(),
-                        h,
-                    }
-                ),
-                sprite_xy,
-                colour_override,
-            }
-        );
+        (
+            sprite_xy,
+            unscaled::Rect {
+                x,
+                y,
+                w: CHAR_W.get(),
+                h,
+            },
+        )
     }
 
     pub fn print_line(
@@ -543,25 +669,35 @@ impl Commands {
         card: Card,
         xy: unscaled::XY,
     ) {
-        self.draw_shadow_for_card_at(xy);
+        self.draw_card_turned(card, xy, Transform::IDENTITY);
+    }
+
+    /// Draws the card turned by the given transform, with the top left corner
+    /// of where it ends up at `xy`. A card given a quarter turn is
+    /// `CARD_HEIGHT` wide and `CARD_WIDTH` tall.
+    pub fn draw_card_turned(
+        &mut self,
+        card: Card,
+        xy: unscaled::XY,
+        transform: Transform,
+    ) {
+        let frame = CardFrame { xy, transform };
+
+        self.draw_shadow_for_card_at(frame);
 
         let suit_opt = get_suit(card);
         let rank_opt = get_rank(card);
         let zinger_opt = get_zinger(card);
 
-        self.sspr(
+        frame.sspr(
+            self,
             sprite::XY {
                 x: card::BACKING_SPRITE_X,
                 y: card::BACKING_SPRITE_BASE_Y
                 + card::HEIGHT.get()
                 * sprite::Inner::from(card / models::RANK_COUNT)
             },
-            Rect::from_unscaled(unscaled::Rect {
-                x: xy.x,
-                y: xy.y,
-                w: card::WIDTH.get(),
-                h: card::HEIGHT.get(),
-            })
+            CardFrame::WHOLE_CARD,
         );
 
         let image_x = match suit_opt {
@@ -586,82 +722,123 @@ impl Commands {
             },
         };
 
-        self.sspr(
+        frame.sspr(
+            self,
             sprite::XY {
                 x: image_x,
                 y: image_y,
             },
-            Rect::from_unscaled(unscaled::Rect {
-                x: xy.x + card::IMAGE_W_OFFSET.get(),
-                y: xy.y + card::IMAGE_H_OFFSET.get(),
+            unscaled::Rect {
+                x: unscaled::X(0) + card::IMAGE_W_OFFSET.get(),
+                y: unscaled::Y(0) + card::IMAGE_H_OFFSET.get(),
                 w: card::IMAGE_W.get(),
                 h: card::IMAGE_H.get(),
-            })
+            },
         );
 
+        if let Some(suit) = suit_opt {
+            self.draw_suit_symbol(suit, frame);
+        }
+
         let (line1, line2) = Self::label_lines_for(
             suit_opt,
             rank_opt,
             zinger_opt,
         );
 
-        self.print_line(
+        frame.print_line(
+            self,
             line1,
-            xy + card::LINE_W_OFFSET.get() + card::LINE_H_1_OFFSET.get(),
+            unscaled::XY::default() + card::LINE_W_OFFSET.get() + card::LINE_H_1_OFFSET.get(),
             card::TEXT_COLOUR,
         );
-        self.print_line(
+        frame.print_line(
+            self,
             line2,
-            xy + card::LINE_W_OFFSET.get() + card::LINE_H_2_OFFSET.get(),
+            unscaled::XY::default() + card::LINE_W_OFFSET.get() + card::LINE_H_2_OFFSET.get(),
             card::TEXT_COLOUR,
         );
     }
 
+    /// Draws a symbol for the suit in the top left corner of the card in
+    /// `frame`, so that the suit can be told apart from the others without
+    /// relying on its colour.
+    fn draw_suit_symbol(
+        &mut self,
+        suit: Suit,
+        frame: CardFrame,
+    ) {
+        frame.sspr(
+            self,
+            sprite::XY {
+                x: card::SUIT_SYMBOL_BASE_X
+                    + unscaled::Inner::from(u8::from(suit))
+                    * card::SUIT_SYMBOL_W.get(),
+                y: card::SUIT_SYMBOL_Y,
+            },
+            unscaled::Rect {
+                x: unscaled::X(0) + card::SUIT_SYMBOL_W_OFFSET.get(),
+                y: unscaled::Y(0) + card::SUIT_SYMBOL_H_OFFSET.get(),
+                w: card::SUIT_SYMBOL_W.get(),
+                h: card::SUIT_SYMBOL_H.get(),
+            },
+        );
+    }
+
     pub fn draw_card_back(
         &mut self,
         xy: unscaled::XY,
     ) {
-        self.draw_shadow_for_card_at(xy);
+        self.draw_card_back_turned(xy, Transform::IDENTITY);
+    }
+
+    /// Draws the back of a card, turned like `draw_card_turned` does.
+    pub fn draw_card_back_turned(
+        &mut self,
+        xy: unscaled::XY,
+        transform: Transform,
+    ) {
+        let frame = CardFrame { xy, transform };
+
+        self.draw_shadow_for_card_at(frame);
 
         let image_x = card::BACKING_SPRITE_X;
         let image_y = card::BACKING_SPRITE_BASE_Y
             + unscaled::Inner::from(models::SUIT_COUNT + 2)
             * card::HEIGHT.get();
 
-        self.sspr(
+        frame.sspr(
+            self,
             sprite::XY {
                 x: image_x, 
                 y: image_y,
             },
-            Rect::from_unscaled(unscaled::Rect {
-                x: xy.x,
-                y: xy.y,
-                w: card::WIDTH.get(),
-                h: card::HEIGHT.get(),
-            })
+            CardFrame::WHOLE_CARD,
         );
     }
 
     fn draw_shadow_for_card_at(
         &mut self,
-        xy: unscaled::XY,
+        CardFrame { xy, transform }: CardFrame,
     ) {
         let image_x = card::BACKING_SPRITE_X;
         let image_y = card::BACKING_SPRITE_BASE_Y
             + unscaled::Inner::from(models::SUIT_COUNT + 1)
             * card::HEIGHT.get();
 
-        self.sspr(
+        // The light comes from the same place however the card is turned.
+        let frame = CardFrame {
+            xy: xy + unscaled::W(2) + unscaled::H(2),
+            transform,
+        };
+
+        frame.sspr(
+            self,
             sprite::XY {
                 x: image_x, 
                 y: image_y,
             },
-            Rect::from_unscaled(unscaled::Rect {
-                x: xy.x + unscaled::W(2),
-                y: xy.y + unscaled::H(2),
-                w: card::WIDTH.get(),
-                h: card::HEIGHT.get(),
-            })
+            CardFrame::WHOLE_CARD,
         );
     }
 
@@ -670,7 +847,7 @@ impl Commands {
         predicate: NetPredicate,
         xy: unscaled::XY,
     ) {
-        self.draw_shadow_for_card_at(xy);
+        self.draw_shadow_for_card_at(CardFrame { xy, transform: Transform::IDENTITY });
 
         let mut suit_opt = None;
         let mut rank_opt = None;
@@ -792,6 +969,10 @@ impl Commands {
             }
         }
 
+        if let Some(suit) = suit_opt {
+            self.draw_suit_symbol(suit, CardFrame { xy, transform: Transform::IDENTITY });
+        }
+
         let (line1, line2) = Self::label_lines_for(
             suit_opt,
             rank_opt,
@@ -834,7 +1015,7 @@ impl Commands {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum NineSlice {
     Window,
     Button,
@@ -994,13 +1175,92 @@ impl Commands {
     }
 }
 
+/// Where a card is on the screen, and how it is turned, so the parts of the
+/// card can be placed relative to its upright top left corner.
 #[derive(Clone, Copy)]
+struct CardFrame {
+    xy: unscaled::XY,
+    transform: Transform,
+}
+
+impl CardFrame {
+    const WHOLE_CARD: unscaled::Rect = unscaled::Rect {
+        x: unscaled::X(0),
+        y: unscaled::Y(0),
+        w: CARD_WIDTH,
+        h: CARD_HEIGHT,
+    };
+
+    /// Where `part`, given relative to the top left of the upright card,
+    /// ends up on the screen.
+    fn rect(self, part: unscaled::Rect) -> unscaled::Rect {
+        let Self { xy, transform } = self;
+        let unscaled::Rect { x, y, w, h } = part;
+        let (x, y, w, h) = (x.get(), y.get(), w.get(), h.get());
+
+        let (outer_w, outer_h, u, v, w, h) = if transform.transposes() {
+            (CARD_HEIGHT.get(), CARD_WIDTH.get(), y, x, h, w)
+        } else {
+            (CARD_WIDTH.get(), CARD_HEIGHT.get(), x, y, w, h)
+        };
+
+        let u = if transform.flips_x() {
+            outer_w.saturating_sub(u + w)
+        } else {
+            u
+        };
+        let v = if transform.flips_y() {
+            outer_h.saturating_sub(v + h)
+        } else {
+            v
+        };
+
+        unscaled::Rect {
+            x: xy.x + unscaled::W(u),
+            y: xy.y + unscaled::H(v),
+            w: unscaled::W(w),
+            h: unscaled::H(h),
+        }
+    }
+
+    fn sspr(
+        self,
+        commands: &mut Commands,
+        sprite_xy: sprite::XY,
+        part: unscaled::Rect,
+    ) {
+        commands.push_transformed(sprite_xy, self.rect(part), 0, self.transform);
+    }
+
+    fn print_line(
+        self,
+        commands: &mut Commands,
+        bytes: &[u8],
+        mut xy: unscaled::XY,
+        colour: ARGB,
+    ) {
+        for &c in bytes.iter() {
+            let (sprite_xy, part) = Commands::char_sprite_and_rect(c, xy);
+
+            commands.push_transformed(
+                sprite_xy,
+                self.rect(part),
+                colour,
+                self.transform,
+            );
+
+            xy.x += CHAR_ADVANCE_W.get();
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Highlighted {
     No,
     Yes,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ChevronDir {
     Up,
     Down,
@@ -1049,11 +1309,135 @@ impl Commands {
     }
 }
 
+/// Which third of a nine-slice a piece of it is from, along one axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Third {
+    Start,
+    Middle,
+    End,
+}
+
+/// What a command draws, as far as can be told from the part of the sprite
+/// sheet it draws from. This is for frontends that cannot show the sprites
+/// themselves, and so draw something else in their place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sprite {
+    /// A character, as it was passed to `print_char`.
+    Char(u8),
+    /// The front of a card, in the colour of its suit. `None` is for zingers.
+    CardBacking(Option<Suit>),
+    CardBack,
+    CardShadow,
+    Selectrum,
+    /// The picture in the middle of a card's front.
+    CardImage,
+    SuitSymbol(Suit),
+    NineSlice(NineSlice, Third, Third),
+    Chevron(ChevronDir, Highlighted),
+    /// A translucent fill, in `colour_override`. See `Commands::fill`.
+    Fill,
+}
+
+pub fn sprite_of(command: &Command) -> Sprite {
+    use sprite::Inner;
+
+    if command.fill == Fill::Yes {
+        return Sprite::Fill
+    }
+
+    let sprite::XY { x: sprite::X(x), y: sprite::Y(y) } = command.sprite_xy;
+
+    let font_y = FONT_OFFSET.get();
+    let nine_slice_y = NineSlice::BASE.y.0;
+    let nine_slice_grid_w = NineSlice::GRID_W.get();
+
+    if x < Inner::from(FONT_WIDTH) && y + 1 >= font_y {
+        let char_height = Inner::from(CHAR_HEIGHT);
+        let column = x / Inner::from(CHAR_WIDTH);
+
+        // The accented characters start a pixel higher up, to make room for
+        // their accents. See `Commands::char_sprite_and_rect`.
+        let character = if y >= font_y && (y - font_y).is_multiple_of(char_height) {
+            (y - font_y) / char_height * 16 + column
+        } else if (y + 1 - font_y).is_multiple_of(char_height) {
+            (y + 1 - font_y) / char_height * 16 + column + 0xD0
+        } else {
+            (y.saturating_sub(font_y)) / char_height * 16 + column
+        };
+
+        return Sprite::Char(character as u8)
+    }
+
+    if x >= NineSlice::BASE.x.0
+    && y >= nine_slice_y
+    && y < nine_slice_y + NineSlice::GRID_H.get() {
+        let grid_x = x - NineSlice::BASE.x.0;
+        let third = |offset: Inner| match offset / NineSlice::CELL_W.get() {
+            0 => Third::Start,
+            1 => Third::Middle,
+            _ => Third::End,
+        };
+
+        let nine_slice = match grid_x / nine_slice_grid_w {
+            0 => NineSlice::Window,
+            1 => NineSlice::Button,
+            2 => NineSlice::ButtonHot,
+            _ => NineSlice::ButtonPressed,
+        };
+
+        return Sprite::NineSlice(
+            nine_slice,
+            third(grid_x % nine_slice_grid_w),
+            third(y - nine_slice_y),
+        )
+    }
+
+    if x >= CHEVRON_BASE_X.0
+    && y >= CHEVRON_Y.0
+    && y < CHEVRON_Y.0 + CHEVRON_H.get() {
+        use ChevronDir::*;
+        use Highlighted::*;
+
+        let (dir, highlighted) = match (x - CHEVRON_BASE_X.0) / CHEVRON_W.get() {
+            0 => (Up, No),
+            1 => (Down, No),
+            2 => (Up, Yes),
+            _ => (Down, Yes),
+        };
+
+        return Sprite::Chevron(dir, highlighted)
+    }
+
+    if x >= card::BACKING_SPRITE_X.0 {
+        let row = (y - card::BACKING_SPRITE_BASE_Y.0) / card::HEIGHT.get().get();
+
+        return match row {
+            row if row <= Inner::from(models::SUIT_COUNT) =>
+                Sprite::CardBacking(Suit::ALL.get(usize::from(row)).copied()),
+            row if row == Inner::from(models::SUIT_COUNT + 1) => Sprite::CardShadow,
+            row if row == Inner::from(models::SUIT_COUNT + 2) => Sprite::CardBack,
+            _ => Sprite::Selectrum,
+        }
+    }
+
+    if x >= card::SUIT_SYMBOL_BASE_X.0
+    && y >= card::SUIT_SYMBOL_Y.0
+    && y < card::SUIT_SYMBOL_Y.0 + card::SUIT_SYMBOL_H.get().get() {
+        let index = (x - card::SUIT_SYMBOL_BASE_X.0) / card::SUIT_SYMBOL_W.get().get();
+
+        if let Some(&suit) = Suit::ALL.get(usize::from(index)) {
+            return Sprite::SuitSymbol(suit)
+        }
+    }
+
+    Sprite::CardImage
+}
+
 pub mod card {
     use super::*;
 
     use command::{W, H, Inner, w_const_mul, h_const_add};
-    use sprite::{x_const_add_w};
+    use sprite::{x_const_add_w, y_const_add_h};
 
     pub const WIDTH: W = W::clipped(CARD_WIDTH);
     pub const HEIGHT: H = H::clipped(CARD_HEIGHT);
@@ -1077,8 +1461,23 @@ pub mod card {
         );
     pub const BACKING_SPRITE_BASE_Y: sprite::Y = sprite::Y(0);
 
-    // TODO: refer to palette instead of hardcoding this.
-    pub const TEXT_COLOUR: platform_types::ARGB = 0xFF222222;
+    pub const TEXT_COLOUR: platform_types::ARGB = platform_types::BLACK;
+
+    /// The suit symbols are below the zinger images, in suit order.
+    pub const SUIT_SYMBOL_BASE_X: sprite::X = ZINGER_IMAGE_X;
+    pub const SUIT_SYMBOL_Y: sprite::Y =
+        y_const_add_h(
+            IMAGE_BASE_Y,
+            // This is below the screen, so the multiplication is done
+            // unclipped.
+            unscaled::h_const_mul(IMAGE_H.get(), models::ZINGER_COUNT as Inner)
+        );
+
+    pub const SUIT_SYMBOL_W: W = W::clipped_inner(11);
+    pub const SUIT_SYMBOL_H: H = H::clipped_inner(11);
+
+    pub const SUIT_SYMBOL_W_OFFSET: W = W::clipped_inner(2);
+    pub const SUIT_SYMBOL_H_OFFSET: H = H::clipped_inner(2);
 
     pub const IMAGE_W_OFFSET: W = W::clipped_inner(1);
     pub const IMAGE_H_OFFSET: H = H::clipped_inner(4);
@@ -1088,6 +1487,23 @@ pub mod card {
     pub const LINE_H_2_OFFSET: H = h_const_add(LINE_H_1_OFFSET, CHAR_ADVANCE_H);
 }
 
+pub const SCREEN_RECT: unscaled::Rect = unscaled::Rect {
+    x: unscaled::X(0),
+    y: unscaled::Y(0),
+    w: command::WIDTH_W,
+    h: command::HEIGHT_H,
+};
+
+/// What `dim_screen` fills the screen with. The colour is from the palette, so
+/// the themes can change it too.
+pub const DIM_COLOUR: ARGB = 0xC0_00_00_00 | (platform_types::BLACK & 0x00_FF_FF_FF);
+
+/// A colour to pass to `draw_glow`. Each layer of the glow is this faint, and
+/// they add up near the middle.
+pub const GLOW_COLOUR: ARGB = 0x70_00_00_00 | (platform_types::YELLOW & 0x00_FF_FF_FF);
+pub const GLOW_LAYERS: unscaled::Inner = 3;
+pub const GLOW_STEP: unscaled::Inner = 2;
+
 pub const WINDOW_CONTENT_OFFSET: unscaled::WH = unscaled::WH {
     w: unscaled::W(3),
     h: unscaled::H(3),
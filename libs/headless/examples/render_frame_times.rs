@@ -0,0 +1,98 @@
+//! Plays the start of a game with scripted input, and reports how long
+//! `render::render` took per frame. Run with:
+//!
+//! ```text
+//! cargo run --release -p headless --example render_frame_times
+//! ```
+
+use platform_types::{command, Button, State};
+use render::{clip, FrameBuffer, Threads};
+
+use std::{
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
+
+const SEED: [u8; 16] = [42; 16];
+
+const FRAME_COUNT: usize = 3000;
+
+const SIZES: [(clip::W, clip::H); 3] = [
+    (command::WIDTH, command::HEIGHT),
+    (command::WIDTH * 3 + 15, command::HEIGHT * 3 + 9),
+    // 4K
+    (3840, 2160),
+];
+
+const FOUR: NonZeroUsize = match NonZeroUsize::new(4) {
+    Some(n) => n,
+    None => unreachable!(),
+};
+
+const BUTTONS: [Button; 6] = [
+    Button::A,
+    Button::B,
+    Button::LEFT,
+    Button::RIGHT,
+    Button::UP,
+    Button::DOWN,
+];
+
+fn main() {
+    for (size, threads) in SIZES.into_iter()
+        .flat_map(|size| [(size, Threads::One), (size, Threads::Many(FOUR))]) {
+        let mut state = app::State::new((SEED, None, None));
+        let mut frame_buffer = FrameBuffer::from_size(size);
+        frame_buffer.threads = threads;
+
+        let mut rng = xs::from_seed(SEED);
+        let mut times = Vec::with_capacity(FRAME_COUNT);
+        let mut redrawn = 0;
+        let mut damaged_pixels = 0;
+
+        for frame in 0..FRAME_COUNT {
+            // Mash buttons, like the tournament's house player does, so the
+            // game keeps moving along.
+            if frame % 2 == 0 {
+                let i = xs::range(&mut rng, 0..BUTTONS.len() as u32) as usize;
+                state.press(BUTTONS[i]);
+            } else {
+                for button in BUTTONS {
+                    state.release(button);
+                }
+            }
+
+            let (commands, _) = state.frame();
+
+            let start = Instant::now();
+            let needs_redraw = render::render(&mut frame_buffer, commands);
+            times.push(start.elapsed());
+
+            if needs_redraw == render::NeedsRedraw::Yes {
+                redrawn += 1;
+            }
+
+            damaged_pixels += frame_buffer.damage.iter()
+                .map(|rect| u64::from(rect.width()) * u64::from(rect.height()))
+                .sum::<u64>();
+        }
+
+        times.sort();
+
+        let total: Duration = times.iter().sum();
+        let percentile = |p: usize| times[(times.len() - 1) * p / 100];
+
+        let total_pixels = FRAME_COUNT as u64 * u64::from(size.0) * u64::from(size.1);
+
+        println!(
+            "{}x{} with {threads:?}: {FRAME_COUNT} frames, {redrawn} redrawn, {:.1}% of pixels damaged. mean {:?}, median {:?}, p99 {:?}, max {:?}",
+            size.0,
+            size.1,
+            damaged_pixels as f64 * 100. / total_pixels as f64,
+            total / FRAME_COUNT as u32,
+            percentile(50),
+            percentile(99),
+            times[times.len() - 1],
+        );
+    }
+}
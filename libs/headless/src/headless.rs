@@ -0,0 +1,254 @@
+//! Runs a `State` without a window, sound output, or any OS input, so it can be
+//! driven by scripts in tests and tools, including on machines with no display.
+
+use platform_types::{unscaled, Button, Key, State, ARGB, SFX};
+use render::{clip, FrameBuffer};
+
+/// The index of a frame, counting from 0.
+pub type Frame = u64;
+
+/// Something the platform would otherwise have passed along from the OS.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Press(Button),
+    Release(Button),
+    KeyPress(Key),
+    KeyRelease(Key),
+    PointerMove(Option<unscaled::XY>),
+    PointerPress,
+    PointerRelease,
+}
+
+impl Event {
+    fn send_to<S: State>(self, state: &mut S) {
+        match self {
+            Self::Press(button) => state.press(button),
+            Self::Release(button) => state.release(button),
+            Self::KeyPress(key) => state.key_press(key),
+            Self::KeyRelease(key) => state.key_release(key),
+            Self::PointerMove(xy) => state.pointer_move(xy),
+            Self::PointerPress => state.pointer_press(),
+            Self::PointerRelease => state.pointer_release(),
+        }
+    }
+}
+
+/// Where the input for each frame comes from.
+pub trait InputSource {
+    /// Pushes the events that should happen before `frame` is run onto `events`.
+    fn events(&mut self, frame: Frame, events: &mut Vec<Event>);
+}
+
+impl <F: FnMut(Frame, &mut Vec<Event>)> InputSource for F {
+    fn events(&mut self, frame: Frame, events: &mut Vec<Event>) {
+        self(frame, events)
+    }
+}
+
+/// A list of events, each to happen before a given frame.
+#[derive(Clone, Debug, Default)]
+pub struct Script {
+    events: Vec<(Frame, Event)>,
+}
+
+impl Script {
+    pub fn at(mut self, frame: Frame, event: Event) -> Self {
+        // Keep events sorted by frame, while keeping events on the same frame in
+        // the order they were added.
+        let index = self.events.partition_point(|&(f, _)| f <= frame);
+        self.events.insert(index, (frame, event));
+        self
+    }
+
+    /// Presses `button` before `frame` and releases it before the frame after.
+    pub fn tap(self, frame: Frame, button: Button) -> Self {
+        self.at(frame, Event::Press(button))
+            .at(frame + 1, Event::Release(button))
+    }
+}
+
+impl InputSource for Script {
+    fn events(&mut self, frame: Frame, events: &mut Vec<Event>) {
+        let start = self.events.partition_point(|&(f, _)| f < frame);
+        events.extend(
+            self.events[start..]
+                .iter()
+                .take_while(|&&(f, _)| f == frame)
+                .map(|&(_, event)| event)
+        );
+    }
+}
+
+/// Owns a `State`, and renders what it produces into a `FrameBuffer`, the same
+/// way the real platform does, minus the window.
+pub struct Headless<S: State> {
+    pub state: S,
+    frame_buffer: FrameBuffer,
+    /// The index of the next frame to be run.
+    frame: Frame,
+    events: Vec<Event>,
+    sfx: Vec<SFX>,
+    sfx_log: Vec<(Frame, SFX)>,
+}
+
+impl <S: State> Headless<S> {
+    /// The size is the size of the output, which is the size the window would
+    /// be. It cannot be smaller than the game's screen.
+    pub fn new(state: S, size: (clip::W, clip::H)) -> Self {
+        Self {
+            state,
+            frame_buffer: FrameBuffer::from_size(size),
+            frame: 0,
+            events: Vec::with_capacity(16),
+            sfx: Vec::with_capacity(8),
+            sfx_log: Vec::new(),
+        }
+    }
+
+    /// Runs a single frame, with whatever events `source` has for it.
+    pub fn step(&mut self, source: &mut impl InputSource) {
+        self.events.clear();
+        source.events(self.frame, &mut self.events);
+        for &event in &self.events {
+            event.send_to(&mut self.state);
+        }
+
+        // Done before `frame`, the same way the platform does, since the
+        // commands borrow the state.
+        self.frame_buffer.set_theme(self.state.theme());
+
+        let (commands, sfx) = self.state.frame();
+
+        self.sfx.clear();
+        self.sfx.extend_from_slice(sfx);
+        let frame = self.frame;
+        self.sfx_log.extend(sfx.iter().map(|&sfx| (frame, sfx)));
+
+        render::render(&mut self.frame_buffer, commands);
+
+        self.frame += 1;
+    }
+
+    pub fn run(&mut self, source: &mut impl InputSource, frame_count: Frame) {
+        for _ in 0..frame_count {
+            self.step(source);
+        }
+    }
+
+    /// The index of the next frame to be run, which is also how many have been
+    /// run so far.
+    pub fn frame(&self) -> Frame {
+        self.frame
+    }
+
+    /// The pixels from the most recent frame, in rows from top to bottom.
+    pub fn pixels(&self) -> &[ARGB] {
+        &self.frame_buffer.buffer
+    }
+
+    /// The parts of `pixels` that changed on the most recent frame.
+    pub fn damage(&self) -> &[clip::Rect] {
+        &self.frame_buffer.damage
+    }
+
+    pub fn size(&self) -> (clip::W, clip::H) {
+        (self.frame_buffer.width, self.frame_buffer.height)
+    }
+
+    /// The sounds requested on the most recent frame.
+    pub fn sfx(&self) -> &[SFX] {
+        &self.sfx
+    }
+
+    /// Every sound requested so far, with the frame it was requested on.
+    pub fn sfx_log(&self) -> &[(Frame, SFX)] {
+        &self.sfx_log
+    }
+}
+
+#[cfg(test)]
+mod headless_runs_the_app {
+    use super::*;
+    use platform_types::command;
+
+    const SEED: [u8; 16] = [42; 16];
+
+    const SIZE: (clip::W, clip::H) = (command::WIDTH * 2, command::HEIGHT * 2);
+
+    fn app() -> app::State {
+        app::State::new((SEED, None, None))
+    }
+
+    fn pixels_after(script: Script, frame_count: Frame) -> Vec<ARGB> {
+        let mut headless = Headless::new(app(), SIZE);
+        headless.run(&mut script.clone(), frame_count);
+
+        assert_eq!(headless.frame(), frame_count);
+        assert_eq!(
+            headless.pixels().len(),
+            usize::from(SIZE.0) * usize::from(SIZE.1)
+        );
+
+        headless.pixels().to_vec()
+    }
+
+    #[test]
+    fn the_same_way_each_time() {
+        let script = Script::default().tap(10, Button::RIGHT);
+
+        assert_eq!(pixels_after(script.clone(), 60), pixels_after(script, 60));
+    }
+
+    #[test]
+    fn with_the_scripted_input() {
+        let without_help = pixels_after(Script::default(), 30);
+        let with_help = pixels_after(
+            Script::default().tap(10, Button::SELECT),
+            30
+        );
+
+        assert_ne!(without_help, with_help);
+    }
+
+    #[test]
+    fn with_keys_going_through_the_bindings() {
+        let pressed = pixels_after(Script::default().tap(10, Button::SELECT), 30);
+        let keyed = pixels_after(
+            Script::default()
+                .at(10, Event::KeyPress(Key::LShift))
+                .at(11, Event::KeyRelease(Key::LShift)),
+            30
+        );
+
+        assert_eq!(pressed, keyed);
+    }
+
+    #[test]
+    fn and_logs_the_sound_requests() {
+        let mut headless = Headless::new(app(), SIZE);
+        headless.run(&mut Script::default().tap(10, Button::SELECT), 30);
+
+        assert!(
+            headless.sfx_log().iter().any(|&(frame, sfx)|
+                frame == 10 && matches!(sfx, SFX::ButtonPress)
+            ),
+            "{:?}",
+            headless.sfx_log()
+        );
+    }
+
+    #[test]
+    fn with_a_closure_as_the_input_source() {
+        let mut presses = 0;
+        let mut headless = Headless::new(app(), SIZE);
+        headless.run(&mut |frame: Frame, events: &mut Vec<Event>| {
+            if frame == 10 {
+                events.push(Event::Press(Button::SELECT));
+                presses += 1;
+            }
+        }, 20);
+
+        assert_eq!(presses, 1);
+        assert_eq!(headless.frame(), 20);
+    }
+}
@@ -1,4 +1,5 @@
-use models::{Basket, Card, CpuId, Hand, HandId, NetPredicate, Predicate, Rank, Suit, DECK_SIZE};
+use models::{Basket, Card, CpuId, Hand, HandId, LurePredicate, NetPredicate, Predicate, Rank, Suit, DECK_SIZE};
+use xs::Xs;
 
 /// It seems intuitive that counting an amount of asks larger than the amount of
 /// suits would not be needed, but I don't have an explicitly worked out reason for
@@ -22,6 +23,17 @@ impl AskCount {
             Four | FivePlus => FivePlus,
         }
     }
+
+    fn get(self) -> u8 {
+        use AskCount::*;
+        match self {
+            One => 1,
+            Two => 2,
+            Three => 3,
+            Four => 4,
+            FivePlus => 5,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -29,10 +41,8 @@ enum Evidence {
     #[default]
     Unknown,
     AskedForSimilar(AskCount),
-    // TODO Allow marking that a player was asked for something and didn't have it.
-    // Will need to have a way to clear that as well, once they've drawn a card.
+    /// They were asked for it and didn't have it, and haven't drawn a card since.
     // TODO? Is it worth tracking how many cards they drew since they didn't have it?
-    #[allow(dead_code)]
     DidNotHave
 }
 
@@ -54,7 +64,27 @@ impl Default for Location {
     }
 }
 
-#[derive(Clone)]
+const KNOWN_SCORE: u32 = 3;
+
+/// A suggested question to ask, along with why it was suggested.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Hint {
+    pub rank: Rank,
+    pub suit: Suit,
+    pub target: HandId,
+    pub reason: HintReason,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HintReason {
+    /// The target was seen getting the card.
+    Seen,
+    /// The target asked for cards of the rank this many times. We stop counting
+    /// at five.
+    AskedFor(u8),
+}
+
+#[derive(Clone, Debug)]
 pub struct Memory {
     locations: [Location; DECK_SIZE as _],
 }
@@ -70,15 +100,18 @@ impl Default for Memory {
 impl Memory {
     fn question_for_known_card_with_rank(
         &self,
+        rng: &mut Xs,
         rank: Rank,
         my_id: HandId
     ) -> Option<(Suit, HandId)> {
-        // TODO? randomize order of suits? Prioritize them somehow?
+        let mut candidates = [None; Suit::COUNT as usize];
+        let mut len = 0;
         for suit in Suit::ALL {
             let location = self.locations[models::fish_card(rank, suit) as usize];
             match location {
                 Location::Known(id) if id != my_id => {
-                    return Some((suit, id));
+                    candidates[len] = Some((suit, id));
+                    len += 1;
                 },
                 Location::Incomplete(_)
                 | Location::Known(_)
@@ -86,16 +119,18 @@ impl Memory {
             }
         }
 
-        None
+        pick(rng, &candidates[..len])
     }
 
     fn question_for_likely_card_with_rank(
         &self,
+        rng: &mut Xs,
         rank: Rank,
         my_id: HandId
     ) -> Option<(Suit, HandId)> {
-        let mut best = None;
-        // TODO? randomize order of suits? Prioritize them somehow?
+        let mut best_count = None;
+        let mut candidates = [None; (Suit::COUNT * HandId::COUNT) as usize];
+        let mut len = 0;
         for suit in Suit::ALL {
             let location = self.locations[models::fish_card(rank, suit) as usize];
             match location {
@@ -104,15 +139,19 @@ impl Memory {
                     for (i, evidence) in incomplete.iter().enumerate() {
                         if i == my_index { continue }
 
-                        match (*evidence, best) {
-                            (Evidence::AskedForSimilar(count), None) => {
-                                best = Some((count, (suit, HandId::ALL[i])));
+                        match (*evidence, best_count) {
+                            (Evidence::AskedForSimilar(count), Some(prev_count))
+                            if count < prev_count => {},
+                            (Evidence::AskedForSimilar(count), Some(prev_count))
+                            if count == prev_count => {
+                                candidates[len] = Some((suit, HandId::ALL[i]));
+                                len += 1;
+                            },
+                            (Evidence::AskedForSimilar(count), _) => {
+                                best_count = Some(count);
+                                candidates[0] = Some((suit, HandId::ALL[i]));
+                                len = 1;
                             },
-                            (Evidence::AskedForSimilar(count), Some((prev_count, _))) => {
-                                if count > prev_count {
-                                    best = Some((count, (suit, HandId::ALL[i])));
-                                }
-                            }
                             (Evidence::Unknown | Evidence::DidNotHave, _) => {}
                         }
 
@@ -123,7 +162,7 @@ impl Memory {
             }
         }
 
-        best.map(|(_, out)| out)
+        pick(rng, &candidates[..len])
     }
 
     fn asked_for(&mut self, hand_id: HandId, predicate: Predicate) {
@@ -190,6 +229,62 @@ impl Memory {
         self.locations[card as usize] = Location::Known(hand_id);
     }
 
+    pub fn is_known_to_be_in(&self, card: Card, hand_id: HandId) -> bool {
+        matches!(
+            self.locations[card as usize],
+            Location::Known(id) if id == hand_id
+        )
+    }
+
+    /// `hand_id` was asked for anything matching `predicate`, and had none of it.
+    pub fn did_not_have(&mut self, hand_id: HandId, predicate: Predicate) {
+        for card in 0..DECK_SIZE {
+            if !predicate.matches(card) {
+                continue
+            }
+
+            let loc = &mut self.locations[card as usize];
+            match *loc {
+                Location::KnownGone => {},
+                Location::Known(id) if id != hand_id => {},
+                // What we thought we knew must be out of date.
+                Location::Known(_) => {
+                    let mut incomplete = Incomplete::default();
+                    incomplete[hand_id as usize] = Evidence::DidNotHave;
+                    *loc = Location::Incomplete(incomplete);
+                },
+                Location::Incomplete(ref mut incomplete) => {
+                    incomplete[hand_id as usize] = Evidence::DidNotHave;
+                },
+            }
+        }
+    }
+
+    /// `hand_id` drew a card that no one else saw, so it could be any of the
+    /// ones they didn't have before.
+    pub fn drew(&mut self, hand_id: HandId) {
+        for loc in self.locations.iter_mut() {
+            if let Location::Incomplete(incomplete) = loc {
+                let evidence = &mut incomplete[hand_id as usize];
+                if let Evidence::DidNotHave = evidence {
+                    *evidence = Evidence::Unknown;
+                }
+            }
+        }
+    }
+
+    /// Whether `hand_id` certainly does not have `card`.
+    pub fn is_known_not_to_be_in(&self, card: Card, hand_id: HandId) -> bool {
+        match self.locations[card as usize] {
+            Location::Incomplete(incomplete) => matches!(
+                incomplete[hand_id as usize],
+                Evidence::DidNotHave
+            ),
+            Location::Known(id) => id != hand_id,
+            Location::KnownGone => true,
+        }
+    }
+
     fn basket_removed(&mut self, basket: Basket) {
         for card in basket {
             self.locations[card as usize] = Location::KnownGone;
@@ -213,7 +308,6 @@ impl Memory {
         target_id: HandId,
         rank: Rank,
     ) -> bool {
-        const KNOWN_SCORE: u32 = 3;
         let mut score = 0;
         for suit in Suit::ALL {
             use Location::*;
@@ -242,19 +336,108 @@ impl Memory {
         score >= KNOWN_SCORE * (Suit::ALL.len() - 2) as u32
     }
 
+    /// How strongly the evidence suggests that `target_id` has `card`.
+    pub fn evidence_score(&self, card: Card, target_id: HandId) -> u32 {
+        use Location::*;
+        use Evidence::*;
+
+        match self.locations[card as usize] {
+            Incomplete(incomplete) => match incomplete[target_id as usize] {
+                Unknown | DidNotHave => 0,
+                AskedForSimilar(AskCount::One | AskCount::Two) => 1,
+                AskedForSimilar(_) => 2,
+            },
+            Known(id) if id == target_id => KNOWN_SCORE,
+            Known(_) | KnownGone => 0,
+        }
+    }
+
+    /// Returns the net predicate, and the player to use it on, that the evidence
+    /// suggests will catch the most cards, if there is any evidence at all.
+    pub fn best_net_predicate(
+        &self,
+        my_id: HandId,
+    ) -> Option<(NetPredicate, HandId)> {
+        let mut best = None;
+        for predicate in NetPredicate::ALL {
+            for target_id in my_id.besides() {
+                let mut score = 0;
+                for card in 0..models::FISH_COUNT {
+                    if predicate.matches(card) {
+                        score += self.evidence_score(card, target_id);
+                    }
+                }
+
+                match best {
+                    Some((prev_score, _)) if prev_score >= score => {},
+                    _ if score == 0 => {},
+                    _ => {
+                        best = Some((score, (predicate, target_id)));
+                    }
+                }
+            }
+        }
+
+        best.map(|(_, out)| out)
+    }
+
+    /// Returns the lure predicate, and the player to use it on, that the evidence
+    /// most strongly suggests will catch a card, if there is any evidence at all.
+    /// Cards of ranks that are already in `my_hand` are preferred, since they get
+    /// us closer to a basket.
+    pub fn best_lure_predicate(
+        &self,
+        my_hand: &Hand,
+        my_id: HandId,
+    ) -> Option<(LurePredicate, HandId)> {
+        let mut best = None;
+        for predicate in LurePredicate::ALL {
+            let card = models::fish_card(predicate.rank, predicate.suit);
+            if my_hand.contains(card) {
+                continue
+            }
+
+            let mut held_count = 0;
+            for held in my_hand.iter() {
+                if models::get_rank(held) == Some(predicate.rank) {
+                    held_count += 1;
+                }
+            }
+
+            for target_id in my_id.besides() {
+                let evidence = self.evidence_score(card, target_id);
+                if evidence == 0 {
+                    continue
+                }
+
+                let score = evidence * (Suit::COUNT as u32) + held_count;
+
+                match best {
+                    Some((prev_score, _)) if prev_score >= score => {},
+                    _ => {
+                        best = Some((score, (predicate, target_id)));
+                    }
+                }
+            }
+        }
+
+        best.map(|(_, out)| out)
+    }
+
     pub fn informed_question(
         &self,
+        rng: &mut Xs,
         my_hand: &Hand,
         my_hand_id: HandId
     ) -> Option<(Rank, Suit, HandId)> {
         // TODO? maybe prioritize questions which
         // are known to result in full baskets?
         // TODO Avoid asking for cards you just successfully got from
-        // another player's hand, but still ask for cards you have
-        // sometimes, to throw others off.
+        // another player's hand.
         for card in my_hand.iter() {
             if let Some(rank) = models::get_rank(card) {
                 let question = self.question_for_known_card_with_rank(
+                    rng,
                     rank,
                     my_hand_id,
                 );
@@ -268,6 +451,7 @@ impl Memory {
         for card in my_hand.iter() {
             if let Some(rank) = models::get_rank(card) {
                 let question = self.question_for_likely_card_with_rank(
+                    rng,
                     rank,
                     my_hand_id,
                 );
@@ -280,6 +464,121 @@ impl Memory {
 
         None
     }
+
+    /// Decides which suit to ask `target_id` for, when we want a card of `rank`
+    /// but don't have any particular card in mind. Occasionally this will be a
+    /// card we already have, to throw the others off.
+    pub fn suit_to_ask_for(
+        &self,
+        rng: &mut Xs,
+        my_hand: &Hand,
+        target_id: HandId,
+        rank: Rank,
+    ) -> Suit {
+        let mut held = [None; Suit::COUNT as usize];
+        let mut held_len = 0;
+
+        let mut best_score = 0;
+        let mut candidates = [None; Suit::COUNT as usize];
+        let mut len = 0;
+
+        for suit in Suit::ALL {
+            let card = models::fish_card(rank, suit);
+            if my_hand.contains(card) {
+                held[held_len] = Some(suit);
+                held_len += 1;
+                continue
+            }
+
+            if let Location::KnownGone = self.locations[card as usize] {
+                continue
+            }
+
+            let score = self.evidence_score(card, target_id);
+            if score > best_score || len == 0 {
+                best_score = score;
+                candidates[0] = Some(suit);
+                len = 1;
+            } else if score == best_score {
+                candidates[len] = Some(suit);
+                len += 1;
+            }
+        }
+
+        if held_len > 0
+        && (len == 0 || xs::range(rng, 0..BLUFF_ONE_IN) == 0) {
+            if let Some(suit) = pick(rng, &held[..held_len]) {
+                return suit
+            }
+        }
+
+        pick(rng, &candidates[..len])
+            .unwrap_or_else(|| Suit::from_rng(rng))
+    }
+
+    /// Suggests the question that the evidence most strongly supports, if there
+    /// is any evidence at all. Unlike `informed_question` this involves no
+    /// randomness, so the same situation always gets the same hint.
+    pub fn hint(&self, my_hand: &Hand, my_id: HandId) -> Option<Hint> {
+        let mut best: Option<(u32, Hint)> = None;
+        // Do high scoring ranks first so we will return them when there are
+        // multiple options.
+        let mut ranks = Rank::ALL;
+        ranks.sort_by_key(|&rank| (core::cmp::Reverse(rank.points()), core::cmp::Reverse(rank as u8)));
+        for rank in ranks {
+            let mut held_count = 0;
+            for held in my_hand.iter() {
+                if models::get_rank(held) == Some(rank) {
+                    held_count += 1;
+                }
+            }
+
+            if held_count == 0 {
+                continue
+            }
+
+            for suit in Suit::ALL {
+                let card = models::fish_card(rank, suit);
+                if my_hand.contains(card) {
+                    continue
+                }
+
+                for target in my_id.besides() {
+                    let reason = match self.locations[card as usize] {
+                        Location::Known(id) if id == target => HintReason::Seen,
+                        Location::Incomplete(incomplete) => match incomplete[target as usize] {
+                            Evidence::AskedForSimilar(count) => HintReason::AskedFor(count.get()),
+                            Evidence::Unknown | Evidence::DidNotHave => continue,
+                        },
+                        Location::Known(_) | Location::KnownGone => continue,
+                    };
+
+                    let score = self.evidence_score(card, target) * (Suit::COUNT as u32) + held_count;
+
+                    match best {
+                        Some((prev_score, _)) if prev_score >= score => {},
+                        _ => {
+                            best = Some((score, Hint { rank, suit, target, reason }));
+                        }
+                    }
+                }
+            }
+        }
+
+        best.map(|(_, hint)| hint)
+    }
+}
+
+/// How rarely we ask for a card we already have, as a bluff.
+const BLUFF_ONE_IN: u32 = 4;
+
+/// Picks one of the given options, treating them all as equally good.
+fn pick<A: Copy>(rng: &mut Xs, options: &[Option<A>]) -> Option<A> {
+    match options.len() {
+        0 => None,
+        1 => options[0],
+        len => options[xs::range(rng, 0..len as u32) as usize],
+    }
 }
 
 #[cfg(test)]
@@ -302,7 +601,7 @@ mod informed_question_returns_the_expected_result {
         mem.known(other_id, models::fish_card(R, Suit::Red));
 
         assert_eq!(
-            mem.informed_question(&my_hand, my_id),
+            mem.informed_question(&mut xs::from_seed([42; 16]), &my_hand, my_id),
             Some((R, Suit::Red, other_id))
         );
     }
@@ -323,17 +622,238 @@ mod informed_question_returns_the_expected_result {
         mem.asked_for(other_id_2, Predicate::RankSuit(R, Suit::Red));
 
         assert_eq!(
-            mem.informed_question(&my_hand, my_id),
+            mem.informed_question(&mut xs::from_seed([42; 16]), &my_hand, my_id),
             Some((R, Suit::Red, other_id))
         );
     }
 }
 
+#[cfg(test)]
+mod suit_to_ask_for_returns_the_expected_result {
+    use super::*;
+
+    const R: Rank = models::Rank::Barnacle;
+
+    #[test]
+    fn on_this_one_known_example() {
+        let mut my_hand = Hand::default();
+        my_hand.push(models::fish_card(R, Suit::Green));
+
+        let other_id = HandId::Cpu2;
+
+        let mut mem = Memory::default();
+
+        mem.known(other_id, models::fish_card(R, Suit::Blue));
+
+        let mut rng = xs::from_seed([42; 16]);
+
+        let mut asked_for_blue = false;
+        let mut bluffed = false;
+        for _ in 0..64 {
+            match mem.suit_to_ask_for(&mut rng, &my_hand, other_id, R) {
+                Suit::Blue => { asked_for_blue = true; },
+                Suit::Green => { bluffed = true; },
+                suit => panic!("Asked for {suit:?}, which there is no evidence for"),
+            }
+        }
+
+        assert!(asked_for_blue);
+        assert!(bluffed);
+    }
+
+    #[test]
+    fn on_this_no_evidence_example() {
+        let mut my_hand = Hand::default();
+        my_hand.push(models::fish_card(R, Suit::Green));
+
+        let other_id = HandId::Cpu2;
+
+        let mem = Memory::default();
+
+        let mut rng = xs::from_seed([42; 16]);
+
+        let mut asked = [false; Suit::COUNT as usize];
+        for _ in 0..256 {
+            let suit = mem.suit_to_ask_for(&mut rng, &my_hand, other_id, R);
+            asked[suit as usize] = true;
+        }
+
+        // Ties should be broken randomly, instead of always asking for the same suit.
+        assert_eq!(asked, [true; Suit::COUNT as usize]);
+    }
+}
+
+#[cfg(test)]
+mod hint_returns_the_expected_result {
+    use super::*;
+
+    const R: Rank = models::Rank::Whale;
+
+    #[test]
+    fn on_this_asked_twice_example() {
+        let mut my_hand = Hand::default();
+        my_hand.push(models::fish_card(R, Suit::Green));
+
+        let my_id = HandId::Player;
+        let other_id = HandId::Cpu2;
+
+        let mut mem = Memory::default();
+
+        mem.asked_for(other_id, Predicate::RankSuit(R, Suit::Red));
+        mem.asked_for(other_id, Predicate::RankSuit(R, Suit::Blue));
+
+        assert_eq!(
+            mem.hint(&my_hand, my_id),
+            Some(Hint { rank: R, suit: Suit::Red, target: other_id, reason: HintReason::AskedFor(2) })
+        );
+    }
+
+    #[test]
+    fn on_this_seen_beats_asked_example() {
+        let mut my_hand = Hand::default();
+        my_hand.push(models::fish_card(R, Suit::Green));
+
+        let my_id = HandId::Player;
+
+        let mut mem = Memory::default();
+
+        mem.asked_for(HandId::Cpu1, Predicate::RankSuit(R, Suit::Red));
+        mem.known(HandId::Cpu3, models::fish_card(R, Suit::Purple));
+
+        assert_eq!(
+            mem.hint(&my_hand, my_id),
+            Some(Hint { rank: R, suit: Suit::Purple, target: HandId::Cpu3, reason: HintReason::Seen })
+        );
+    }
+
+    #[test]
+    fn on_this_no_evidence_example() {
+        let mut my_hand = Hand::default();
+        my_hand.push(models::fish_card(R, Suit::Green));
+
+        let mem = Memory::default();
+
+        assert_eq!(mem.hint(&my_hand, HandId::Player), None);
+    }
+}
+
+#[cfg(test)]
+mod best_net_predicate_returns_the_expected_result {
+    use super::*;
+
+    const R: Rank = models::Rank::Dogfish;
+
+    #[test]
+    fn on_this_nearly_complete_basket_example() {
+        let my_id = HandId::Cpu1;
+        let other_id = HandId::Player;
+
+        let mut mem = Memory::default();
+
+        mem.asked_for(other_id, Predicate::RankSuit(R, Suit::Red));
+        mem.asked_for(other_id, Predicate::RankSuit(R, Suit::Green));
+        mem.asked_for(other_id, Predicate::RankSuit(R, Suit::Blue));
+        mem.known(other_id, models::fish_card(R, Suit::Red));
+        mem.known(other_id, models::fish_card(R, Suit::Green));
+        mem.known(other_id, models::fish_card(R, Suit::Blue));
+
+        assert_eq!(
+            mem.best_net_predicate(my_id),
+            Some((NetPredicate::Rank(R), other_id))
+        );
+    }
+
+    #[test]
+    fn on_this_no_evidence_example() {
+        let mem = Memory::default();
+
+        assert_eq!(mem.best_net_predicate(HandId::Cpu1), None);
+    }
+}
+
+#[cfg(test)]
+mod best_lure_predicate_returns_the_expected_result {
+    use super::*;
+
+    const R: Rank = models::Rank::Dogfish;
+
+    #[test]
+    fn on_this_lure_example() {
+        let mut my_hand = Hand::default();
+        my_hand.push(models::fish_card(R, Suit::Green));
+
+        let my_id = HandId::Cpu1;
+        let other_id = HandId::Cpu2;
+
+        let mut mem = Memory::default();
+
+        mem.known(other_id, models::fish_card(models::Rank::Whale, Suit::Red));
+        mem.known(other_id, models::fish_card(R, Suit::Red));
+
+        assert_eq!(
+            mem.best_lure_predicate(&my_hand, my_id),
+            Some((LurePredicate { rank: R, suit: Suit::Red }, other_id))
+        );
+    }
+}
+
+#[cfg(test)]
+mod is_known_not_to_be_in_returns_the_expected_result {
+    use super::*;
+
+    const R: Rank = models::Rank::Eel;
+
+    #[test]
+    fn on_this_did_not_have_example() {
+        let id = HandId::Cpu2;
+        let mut mem = Memory::default();
+
+        mem.did_not_have(id, Predicate::Net(NetPredicate::Rank(R)));
+
+        for suit in Suit::ALL {
+            assert!(mem.is_known_not_to_be_in(models::fish_card(R, suit), id));
+            assert!(!mem.is_known_not_to_be_in(models::fish_card(R, suit), HandId::Cpu1));
+        }
+        assert!(!mem.is_known_not_to_be_in(models::fish_card(Rank::Crab, Suit::Red), id));
+    }
+
+    #[test]
+    fn on_this_drew_afterwards_example() {
+        let id = HandId::Cpu2;
+        let card = models::fish_card(R, Suit::Red);
+        let mut mem = Memory::default();
+
+        mem.did_not_have(id, Predicate::RankSuit(R, Suit::Red));
+        mem.drew(HandId::Cpu1);
+
+        assert!(mem.is_known_not_to_be_in(card, id));
+
+        mem.drew(id);
+
+        assert!(!mem.is_known_not_to_be_in(card, id));
+    }
+
+    #[test]
+    fn on_this_out_of_date_known_example() {
+        let id = HandId::Cpu2;
+        let card = models::fish_card(R, Suit::Red);
+        let mut mem = Memory::default();
+
+        mem.known(id, card);
+        mem.did_not_have(id, Predicate::RankSuit(R, Suit::Red));
+
+        assert!(mem.is_known_not_to_be_in(card, id));
+        assert!(!mem.is_known_to_be_in(card, id));
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct Memories {
     cpu1: Memory,
     cpu2: Memory,
     cpu3: Memory,
+    /// What the human player has been able to see, used to give them hints.
+    player: Memory,
 }
 
 impl Memories {
@@ -353,10 +873,19 @@ impl Memories {
         }
     }
 
+    pub fn player(&self) -> &Memory {
+        &self.player
+    }
+
+    pub fn player_mut(&mut self) -> &mut Memory {
+        &mut self.player
+    }
+
     pub fn asked_for(&mut self, hand_id: HandId, predicate: Predicate) {
         for cpu_id in CpuId::ALL {
             self.memory_mut(cpu_id).asked_for(hand_id, predicate);
         }
+        self.player.asked_for(hand_id, predicate);
     }
 
     /// A player successfully got a card when asking and this was revealed to all.
@@ -364,11 +893,27 @@ impl Memories {
         for cpu_id in CpuId::ALL {
             self.memory_mut(cpu_id).known(hand_id, card);
         }
+        self.player.known(hand_id, card);
+    }
+
+    pub fn did_not_have(&mut self, hand_id: HandId, predicate: Predicate) {
+        for cpu_id in CpuId::ALL {
+            self.memory_mut(cpu_id).did_not_have(hand_id, predicate);
+        }
+        self.player.did_not_have(hand_id, predicate);
+    }
+
+    pub fn drew(&mut self, hand_id: HandId) {
+        for cpu_id in CpuId::ALL {
+            self.memory_mut(cpu_id).drew(hand_id);
+        }
+        self.player.drew(hand_id);
     }
 
     pub fn basket_removed(&mut self, basket: Basket) {
         for cpu_id in CpuId::ALL {
             self.memory_mut(cpu_id).basket_removed(basket);
         }
+        self.player.basket_removed(basket);
     }
 }
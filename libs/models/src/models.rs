@@ -18,7 +18,7 @@ pub type CardInner = u8;
 
 pub type Card = CardInner;
 
-pub fn fish_card(rank: Rank, suit: Suit) -> Card {
+pub const fn fish_card(rank: Rank, suit: Suit) -> Card {
     suit as CardInner * RANK_COUNT + (rank as CardInner)
 }
 
@@ -39,6 +39,35 @@ fn get_rank_and_get_suit_then_fish_card_works() {
     }
 }
 
+#[test]
+fn apply_ordering_sorts_the_hand() {
+    let mut hand = Hand::default();
+    for card in [
+        fish_card(Rank::Whale, Suit::Red),
+        zingers::NO_FISHING,
+        fish_card(Rank::Crab, Suit::Blue),
+        fish_card(Rank::Crab, Suit::Red),
+    ] {
+        hand.push(card);
+    }
+
+    let ordering = HandOrdering::sorted_by_key(&hand, |card| (get_rank(card).is_none(), get_rank(card).map(|rank| rank as u8)));
+
+    assert_eq!(ordering.position(1), 3);
+
+    hand.apply_ordering(&ordering);
+
+    assert_eq!(
+        hand.iter().collect::<Vec<_>>(),
+        [
+            fish_card(Rank::Crab, Suit::Blue),
+            fish_card(Rank::Crab, Suit::Red),
+            fish_card(Rank::Whale, Suit::Red),
+            zingers::NO_FISHING,
+        ]
+    );
+}
+
 pub fn gen_card(rng: &mut Xs) -> Card {
     xs::range(rng, 0..DECK_SIZE as _) as Card
 }
@@ -175,6 +204,26 @@ impl Rank {
         rank_text!(12).as_bytes(),
     ];
 
+    /// How many points a full basket of this rank is worth.
+    pub fn points(self) -> u8 {
+        use Rank::*;
+        match self {
+            Barnacle
+            | Crab
+            | Dogfish
+            | Eel
+            | FlyingFish
+            | Hammerhead
+            | Jellyfish
+            | Shrimp => 5,
+            Blowfish
+            | Clownfish
+            | Starfish
+            | Whale => 10,
+            CardShark => 15,
+        }
+    }
+
     pub fn wrapping_dec(mut self, acb: AlmostCompleteBaskets) -> Self {
         for _ in 0..acb.len() {
             self = match self {
@@ -560,6 +609,22 @@ impl Hand {
             self.get(i)
         })
     }
+
+    /// Rearranges the cards so they are in the order given by `ordering`.
+    pub fn apply_ordering(&mut self, ordering: &HandOrdering) {
+        let mut output = [CardOption::NONE; DECK_SIZE as usize];
+
+        for (i, card) in self.ordering_iter(ordering.clone()).enumerate() {
+            output[i] = CardOption::some(card);
+        }
+
+        debug_assert_eq!(
+            output.iter().filter(|co| co.is_some()).count(),
+            usize::from(self.len())
+        );
+
+        self.0 = output;
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -578,6 +643,40 @@ impl Default for HandOrdering {
 }
 
 impl HandOrdering {
+    /// An ordering of the cards in `hand`, sorted by the given key. Cards with
+    /// equal keys keep their current order.
+    pub fn sorted_by_key<K: Ord>(
+        hand: &Hand,
+        mut key: impl FnMut(Card) -> K,
+    ) -> Self {
+        let mut output = Self::default();
+
+        let len = usize::from(hand.len());
+        output.0[..len].sort_by_key(|&i| {
+            key(hand.get(i).expect("indexes below the len should have cards"))
+        });
+
+        output
+    }
+
+    /// Where the card currently at `index` will be once this ordering is applied.
+    pub fn position(&self, index: CardIndex) -> CardIndex {
+        let mut output = 0;
+        for (i, e) in self.0.iter().enumerate() {
+            if *e == index {
+                output = i as CardIndex;
+                break
+            }
+        }
+        output
+    }
+
+    pub fn swap(&mut self, a: CardIndex, b: CardIndex) {
+        self.0.swap(usize::from(a), usize::from(b));
+
+        self.assert_counts();
+    }
+
     pub fn move_to_first(&mut self, to_move: CardIndex) {
         let mut currently_at = 0;
         for (i, e) in self.0.iter().enumerate() {
@@ -1,4 +1,6 @@
 use platform_types::{
+    unscaled,
+    Key,
     State,
     StateParams,
 };
@@ -33,7 +35,17 @@ pub fn run<S: State + 'static>(mut state: S) {
     let mut output_frame_buffer = {
         let size = window.inner_size();
 
-        FrameBuffer::from_size((size.width as clip::W, size.height as clip::H))
+        #[allow(unused_mut)]
+        let mut frame_buffer = FrameBuffer::from_size(
+            (size.width as clip::W, size.height as clip::H)
+        );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            frame_buffer.threads = render::Threads::available();
+        }
+
+        frame_buffer
     };
 
     let mut graphics_context = unsafe { GraphicsContext::new(window) }.unwrap();
@@ -51,6 +63,23 @@ pub fn run<S: State + 'static>(mut state: S) {
 
     let mut just_gained_focus = true;
 
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut capture = capture::Capture::default();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut saved_bindings = {
+        let bindings = config::load_bindings();
+        state.set_bindings(bindings);
+        bindings
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut saved_theme = {
+        let theme = config::load_theme();
+        state.set_theme(theme);
+        theme
+    };
+
     event_loop.run(move |event, _, control_flow| {
         match event {
             Event::WindowEvent {
@@ -68,30 +97,77 @@ pub fn run<S: State + 'static>(mut state: S) {
                 },
                 window_id,
             } if window_id == graphics_context.window().id() => {
-                use winit::event::{ElementState, VirtualKeyCode as VK};
-                use platform_types::Button;
+                use winit::event::ElementState;
 
-                let button = match keycode {
-                    VK::Return => Button::START,
-                    VK::LShift | VK::RShift => Button::SELECT,
-                    VK::Up => Button::UP,
-                    VK::Left => Button::LEFT,
-                    VK::Right => Button::RIGHT,
-                    VK::Down => Button::DOWN,
-
-                    VK::Z => Button::A,
-                    VK::X => Button::B,
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(hotkey) = capture::Hotkey::from_keycode(keycode) {
+                    let pressed = element_state == ElementState::Pressed;
+                    let window = graphics_context.window();
+                    match capture.hotkey(hotkey, pressed, &output_frame_buffer) {
+                        Some(capture::Started) => window.set_title("twisted-fish (recording)"),
+                        Some(capture::Stopped) => window.set_title("twisted-fish"),
+                        None => {}
+                    }
+                    return
+                }
 
-                    // For those using the Dvorak layout.
-                    VK::Semicolon => Button::A,
-                    VK::Q => Button::B,
+                let Some(key) = to_key(keycode) else { return };
 
-                    _ => return,
-                };
+                match element_state {
+                    ElementState::Pressed => state.key_press(key),
+                    ElementState::Released => state.key_release(key),
+                }
+            }
+            Event::WindowEvent {
+                event: WindowEvent::CursorMoved { position, .. },
+                window_id,
+            } if window_id == graphics_context.window().id() => {
+                state.pointer_move(
+                    pointer_to_screen(&output_frame_buffer, (position.x, position.y))
+                );
+            }
+            Event::WindowEvent {
+                event: WindowEvent::CursorLeft { .. },
+                window_id,
+            } if window_id == graphics_context.window().id() => {
+                state.pointer_move(None);
+            }
+            Event::WindowEvent {
+                event: WindowEvent::MouseInput {
+                    state: element_state,
+                    button: winit::event::MouseButton::Left,
+                    ..
+                },
+                window_id,
+            } if window_id == graphics_context.window().id() => {
+                use winit::event::ElementState;
 
                 match element_state {
-                    ElementState::Pressed => state.press(button),
-                    ElementState::Released => state.release(button),
+                    ElementState::Pressed => state.pointer_press(),
+                    ElementState::Released => state.pointer_release(),
+                }
+            }
+            Event::WindowEvent {
+                event: WindowEvent::Touch(winit::event::Touch {
+                    phase,
+                    location,
+                    ..
+                }),
+                window_id,
+            } if window_id == graphics_context.window().id() => {
+                use winit::event::TouchPhase;
+
+                state.pointer_move(
+                    pointer_to_screen(&output_frame_buffer, (location.x, location.y))
+                );
+
+                match phase {
+                    TouchPhase::Started => state.pointer_press(),
+                    TouchPhase::Moved => {},
+                    // We leave the pointer where the finger was lifted, so
+                    // whatever was under it still counts as being released on.
+                    TouchPhase::Ended
+                    | TouchPhase::Cancelled => state.pointer_release(),
                 }
             }
             Event::WindowEvent {
@@ -101,6 +177,23 @@ pub fn run<S: State + 'static>(mut state: S) {
                 just_gained_focus = true;
             }
             Event::MainEventsCleared => {
+                // These are checked before `state.frame()`, since the commands
+                // it returns borrow the state until they are rendered. So
+                // changes made during a frame are seen on the next one.
+                #[cfg(not(target_arch = "wasm32"))]
+                if state.bindings() != &saved_bindings {
+                    saved_bindings = *state.bindings();
+                    config::save_bindings(&saved_bindings);
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if state.theme() != saved_theme {
+                    saved_theme = state.theme();
+                    config::save_theme(saved_theme);
+                }
+
+                output_frame_buffer.set_theme(state.theme());
+
                 let (commands, sounds) = state.frame();
 
                 handle_sounds(&mut sound_handler, sounds);
@@ -116,8 +209,15 @@ pub fn run<S: State + 'static>(mut state: S) {
                     commands,
                 );
 
+                #[cfg(not(target_arch = "wasm32"))]
+                capture.frame_rendered(&output_frame_buffer);
+
                 if NeedsRedraw::Yes == needs_redraw
                 || just_gained_focus {
+                    // `output_frame_buffer.damage` has the parts that changed,
+                    // but this version of softbuffer can only present the
+                    // whole buffer. Once we can update to a version with
+                    // `present_with_damage`, the damage should be passed to it.
                     graphics_context.set_buffer(
                         &output_frame_buffer.buffer,
                         output_frame_buffer.width,
@@ -156,6 +256,89 @@ pub fn run<S: State + 'static>(mut state: S) {
     });
 }
 
+fn to_key(keycode: winit::event::VirtualKeyCode) -> Option<Key> {
+    use winit::event::VirtualKeyCode as VK;
+
+    Some(match keycode {
+        VK::A => Key::A,
+        VK::B => Key::B,
+        VK::C => Key::C,
+        VK::D => Key::D,
+        VK::E => Key::E,
+        VK::F => Key::F,
+        VK::G => Key::G,
+        VK::H => Key::H,
+        VK::I => Key::I,
+        VK::J => Key::J,
+        VK::K => Key::K,
+        VK::L => Key::L,
+        VK::M => Key::M,
+        VK::N => Key::N,
+        VK::O => Key::O,
+        VK::P => Key::P,
+        VK::Q => Key::Q,
+        VK::R => Key::R,
+        VK::S => Key::S,
+        VK::T => Key::T,
+        VK::U => Key::U,
+        VK::V => Key::V,
+        VK::W => Key::W,
+        VK::X => Key::X,
+        VK::Y => Key::Y,
+        VK::Z => Key::Z,
+        VK::Key0 => Key::Key0,
+        VK::Key1 => Key::Key1,
+        VK::Key2 => Key::Key2,
+        VK::Key3 => Key::Key3,
+        VK::Key4 => Key::Key4,
+        VK::Key5 => Key::Key5,
+        VK::Key6 => Key::Key6,
+        VK::Key7 => Key::Key7,
+        VK::Key8 => Key::Key8,
+        VK::Key9 => Key::Key9,
+        VK::Up => Key::Up,
+        VK::Down => Key::Down,
+        VK::Left => Key::Left,
+        VK::Right => Key::Right,
+        VK::Return => Key::Return,
+        VK::Space => Key::Space,
+        VK::Escape => Key::Escape,
+        VK::Tab => Key::Tab,
+        VK::Back => Key::Back,
+        VK::LShift => Key::LShift,
+        VK::RShift => Key::RShift,
+        VK::LControl => Key::LControl,
+        VK::RControl => Key::RControl,
+        VK::LAlt => Key::LAlt,
+        VK::RAlt => Key::RAlt,
+        VK::Comma => Key::Comma,
+        VK::Period => Key::Period,
+        VK::Slash => Key::Slash,
+        VK::Backslash => Key::Backslash,
+        VK::Semicolon => Key::Semicolon,
+        VK::Apostrophe => Key::Apostrophe,
+        VK::Minus => Key::Minus,
+        VK::Equals => Key::Equals,
+        VK::LBracket => Key::LBracket,
+        VK::RBracket => Key::RBracket,
+        VK::Grave => Key::Grave,
+        _ => return None,
+    })
+}
+
+fn pointer_to_screen(
+    frame_buffer: &FrameBuffer,
+    window_xy: (f64, f64),
+) -> Option<unscaled::XY> {
+    #[cfg(target_arch = "wasm32")]
+    let window_xy = wasm::unstretch(window_xy);
+
+    render::frame_buffer_to_screen(
+        (frame_buffer.width, frame_buffer.height),
+        window_xy,
+    )
+}
+
 #[cfg(target_arch = "wasm32")]
 mod wasm {
     use winit::{
@@ -191,6 +374,28 @@ mod wasm {
         style.remove_property("height").unwrap();
     }
 
+    /// The canvas is stretched to fit the page with CSS, so positions on it
+    /// need to be scaled back to the size of the canvas itself.
+    pub fn unstretch((x, y): (f64, f64)) -> (f64, f64) {
+        let canvas = get_canvas();
+
+        let pixel_ratio = web_sys::window()
+            .map(|window| window.device_pixel_ratio())
+            .unwrap_or(1.);
+
+        let shown_w = f64::from(canvas.client_width()) * pixel_ratio;
+        let shown_h = f64::from(canvas.client_height()) * pixel_ratio;
+
+        if shown_w <= 0. || shown_h <= 0. {
+            return (x, y);
+        }
+
+        (
+            x * f64::from(canvas.width()) / shown_w,
+            y * f64::from(canvas.height()) / shown_h,
+        )
+    }
+
     fn get_canvas() -> HtmlCanvasElement {
         let window = web_sys::window().unwrap();
         let document = window.document().unwrap();
@@ -293,6 +498,242 @@ fn new_seed() -> xs::Seed {
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+mod config {
+    use platform_types::{Bindings, Theme};
+    use std::path::PathBuf;
+
+    fn path(file_name: &str) -> Option<PathBuf> {
+        #[cfg(windows)]
+        let dir = PathBuf::from(std::env::var_os("APPDATA")?);
+        #[cfg(not(windows))]
+        let dir = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+        };
+
+        Some(dir.join("twisted-fish").join(file_name))
+    }
+
+    const BINDINGS_FILE_NAME: &str = "bindings.txt";
+    const THEME_FILE_NAME: &str = "theme.txt";
+
+    fn load(file_name: &str) -> Option<String> {
+        path(file_name).and_then(|path| std::fs::read_to_string(path).ok())
+    }
+
+    fn save(file_name: &str, contents: &str) {
+        fn inner(file_name: &str, contents: &str) -> Option<()> {
+            let path = path(file_name)?;
+            std::fs::create_dir_all(path.parent()?).ok()?;
+            std::fs::write(path, contents).ok()
+        }
+
+        // Saving is inessential, so ignore errors. The settings still apply
+        // until the game is closed.
+        let _ = inner(file_name, contents);
+    }
+
+    pub fn load_bindings() -> Bindings {
+        load(BINDINGS_FILE_NAME)
+            .map(|config| Bindings::from_config(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn save_bindings(bindings: &Bindings) {
+        save(BINDINGS_FILE_NAME, &bindings.to_config());
+    }
+
+    pub fn load_theme() -> Theme {
+        load(THEME_FILE_NAME)
+            .and_then(|config| Theme::from_name(config.trim()))
+            .unwrap_or_default()
+    }
+
+    pub fn save_theme(theme: Theme) {
+        save(THEME_FILE_NAME, theme.name());
+    }
+}
+
+/// Screenshots and recordings of the unscaled screen, for bug reports and
+/// for making clips of real games.
+#[cfg(not(target_arch = "wasm32"))]
+mod capture {
+    use platform_types::{command, ARGB};
+    use render::FrameBuffer;
+    use std::{
+        path::{Path, PathBuf},
+        sync::mpsc::{sync_channel, SyncSender, TrySendError},
+    };
+    use winit::event::VirtualKeyCode as VK;
+
+    #[derive(Clone, Copy)]
+    pub enum Hotkey {
+        Screenshot,
+        ToggleRecording,
+    }
+
+    impl Hotkey {
+        pub fn from_keycode(keycode: VK) -> Option<Self> {
+            match keycode {
+                VK::F12 => Some(Self::Screenshot),
+                VK::F10 => Some(Self::ToggleRecording),
+                _ => None,
+            }
+        }
+    }
+
+    pub enum Recording {
+        Started,
+        Stopped,
+    }
+    pub use Recording::*;
+
+    type Pixels = Box<[ARGB; command::LENGTH]>;
+
+    struct Request {
+        path: PathBuf,
+        pixels: Pixels,
+    }
+
+    /// How many frames can be waiting to be written before more get dropped.
+    /// Each one is a full copy of the screen, so this is kept small.
+    const QUEUE_LENGTH: usize = 8;
+
+    struct Frames {
+        dir: PathBuf,
+        next: u32,
+        dropped: u32,
+    }
+
+    pub struct Capture {
+        /// Encoding happens on another thread, so recording doesn't slow down
+        /// the game.
+        sender: SyncSender<Request>,
+        recording: Option<Frames>,
+        /// Used to ignore key repeats.
+        held: [bool; 2],
+    }
+
+    impl Default for Capture {
+        fn default() -> Self {
+            let (sender, receiver) = sync_channel::<Request>(QUEUE_LENGTH);
+
+            std::thread::spawn(move || {
+                while let Ok(Request { path, pixels }) = receiver.recv() {
+                    if let Err(e) = write_png(&path, &pixels[..]) {
+                        eprintln!("Could not write {}: {e}", path.display());
+                    }
+                }
+            });
+
+            Self {
+                sender,
+                recording: None,
+                held: [false; 2],
+            }
+        }
+    }
+
+    fn timestamp() -> u128 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+    }
+
+    impl Capture {
+        pub fn hotkey(
+            &mut self,
+            hotkey: Hotkey,
+            pressed: bool,
+            frame_buffer: &FrameBuffer,
+        ) -> Option<Recording> {
+            let was_held = core::mem::replace(&mut self.held[hotkey as usize], pressed);
+            if !pressed || was_held {
+                return None
+            }
+
+            match hotkey {
+                Hotkey::Screenshot => {
+                    // Unlike a recording frame, a screenshot is worth waiting
+                    // for room in the queue for. The only way this can fail
+                    // is if the thread has died, in which case there is
+                    // nothing useful to do about it here.
+                    let _ = self.sender.send(Request {
+                        path: PathBuf::from(format!("twisted-fish-{}.png", timestamp())),
+                        pixels: frame_buffer.unscaled_buffer.clone(),
+                    });
+                    None
+                },
+                Hotkey::ToggleRecording => {
+                    if let Some(Frames { dir, dropped, .. }) = self.recording.take() {
+                        if dropped > 0 {
+                            eprintln!(
+                                "Skipped {dropped} frames of the recording in {}, since they could not be written fast enough",
+                                dir.display()
+                            );
+                        }
+                        return Some(Stopped)
+                    }
+
+                    let dir = PathBuf::from(format!("twisted-fish-recording-{}", timestamp()));
+                    if let Err(e) = std::fs::create_dir_all(&dir) {
+                        eprintln!("Could not create {}: {e}", dir.display());
+                        return None
+                    }
+
+                    self.recording = Some(Frames { dir, next: 0, dropped: 0 });
+                    Some(Started)
+                },
+            }
+        }
+
+        pub fn frame_rendered(&mut self, frame_buffer: &FrameBuffer) {
+            let Some(frames) = &mut self.recording else { return };
+
+            let path = frames.dir.join(format!("{:05}.png", frames.next));
+            // The number is used up even if the frame gets dropped, so the
+            // gaps show in the sequence.
+            frames.next += 1;
+
+            let request = Request {
+                path,
+                pixels: frame_buffer.unscaled_buffer.clone(),
+            };
+
+            match self.sender.try_send(request) {
+                Ok(()) => {},
+                Err(TrySendError::Full(_)) => frames.dropped += 1,
+                // The thread has died, so there is nothing useful to do
+                // about it here.
+                Err(TrySendError::Disconnected(_)) => {},
+            }
+        }
+    }
+
+    fn write_png(path: &Path, pixels: &[ARGB]) -> Result<(), png::EncodingError> {
+        let file = std::fs::File::create(path)?;
+
+        let mut encoder = png::Encoder::new(
+            std::io::BufWriter::new(file),
+            u32::from(command::WIDTH),
+            u32::from(command::HEIGHT),
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_compression(png::Compression::Fast);
+
+        let mut bytes = Vec::with_capacity(pixels.len() * 3);
+        for argb in pixels {
+            let [_, r, g, b] = argb.to_be_bytes();
+            bytes.extend_from_slice(&[r, g, b]);
+        }
+
+        encoder.write_header()?.write_image_data(&bytes)
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 use wasm::{init_sound_handler, handle_sounds};
 
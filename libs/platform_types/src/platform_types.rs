@@ -530,6 +530,13 @@ pub mod unscaled {
                 h: wh.h,
             }
         }
+
+        pub fn contains(self, xy: XY) -> bool {
+            xy.x >= self.x
+            && xy.x < self.x + self.w
+            && xy.y >= self.y
+            && xy.y < self.y + self.h
+        }
     }
 }
 
@@ -990,34 +997,218 @@ pub mod command {
         assert_eq!(expected, actual);
     }
 
+    /// How a sprite is turned before it is drawn into its rect. The flips
+    /// are in screen space, and happen after the sprite is transposed, if it
+    /// is. A transposed sprite is as wide on screen as it is tall on the
+    /// sprite sheet, and vice versa.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct Transform(u8);
+
+    impl Transform {
+        pub const IDENTITY: Self = Self(0);
+        pub const FLIP_X: Self = Self(1 << 0);
+        pub const FLIP_Y: Self = Self(1 << 1);
+        pub const TRANSPOSE: Self = Self(1 << 2);
+
+        /// A quarter turn clockwise.
+        pub const ROTATE_CW: Self = Self::TRANSPOSE.union(Self::FLIP_X);
+        /// A quarter turn counterclockwise.
+        pub const ROTATE_CCW: Self = Self::TRANSPOSE.union(Self::FLIP_Y);
+        pub const ROTATE_180: Self = Self::FLIP_X.union(Self::FLIP_Y);
+
+        pub const fn union(self, other: Self) -> Self {
+            Self(self.0 | other.0)
+        }
+
+        pub const fn bits(self) -> u8 {
+            self.0
+        }
+
+        pub const fn contains(self, other: Self) -> bool {
+            self.0 & other.0 == other.0
+        }
+
+        pub const fn is_identity(self) -> bool {
+            self.0 == 0
+        }
+
+        pub const fn flips_x(self) -> bool {
+            self.contains(Self::FLIP_X)
+        }
+
+        pub const fn flips_y(self) -> bool {
+            self.contains(Self::FLIP_Y)
+        }
+
+        pub const fn transposes(self) -> bool {
+            self.contains(Self::TRANSPOSE)
+        }
+
+        /// Where the pixel at `(u, v)` inside a `w` by `h` rect on screen
+        /// comes from, relative to the top left of the sprite.
+        pub const fn source(self, u: Inner, v: Inner, w: Inner, h: Inner) -> (Inner, Inner) {
+            let u = if self.flips_x() { w - 1 - u } else { u };
+            let v = if self.flips_y() { h - 1 - v } else { v };
+
+            if self.transposes() {
+                (v, u)
+            } else {
+                (u, v)
+            }
+        }
+    }
+
+    impl core::ops::BitOr for Transform {
+        type Output = Self;
+
+        fn bitor(self, other: Self) -> Self::Output {
+            self.union(other)
+        }
+    }
+
+    #[test]
+    fn quarter_turns_put_the_corners_in_the_right_places() {
+        // A 3 wide, 2 tall sprite, drawn into a 2 wide, 3 tall rect.
+        // Clockwise, the sprite's top left ends up at the top right.
+        assert_eq!(Transform::ROTATE_CW.source(1, 0, 2, 3), (0, 0));
+        assert_eq!(Transform::ROTATE_CW.source(0, 2, 2, 3), (2, 1));
+        // Counterclockwise, the sprite's top left ends up at the bottom left.
+        assert_eq!(Transform::ROTATE_CCW.source(0, 2, 2, 3), (0, 0));
+        assert_eq!(Transform::ROTATE_CCW.source(1, 0, 2, 3), (2, 1));
+        // Two quarter turns are a half turn.
+        for v in 0..2 {
+            for u in 0..3 {
+                let (s, t) = Transform::ROTATE_CW.source(u, v, 3, 2);
+                let (s, t) = Transform::ROTATE_CW.source(s, t, 2, 3);
+                assert_eq!((s, t), Transform::ROTATE_180.source(u, v, 3, 2));
+            }
+        }
+    }
+
+    /// Whether a command draws its sprite, or fills all of its rect with its
+    /// `colour_override` instead. A fill is blended over what is under it by
+    /// the alpha of that colour, so it can dim or tint what is under it.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum Fill {
+        #[default]
+        No,
+        Yes,
+    }
+
     #[derive(Clone, Copy, Debug, Default)]
     pub struct Command {
         pub rect: Rect,
         pub sprite_xy: sprite::XY,
         pub colour_override: ARGB,
+        pub transform: Transform,
+        pub fill: Fill,
     }    
 }
 pub use command::Command;
 
+/// A mouse, or a finger on a touch screen.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Pointer {
+    /// Where on the screen the pointer is, or `None` if it is not over the
+    /// screen at all.
+    pub xy: Option<unscaled::XY>,
+    /// Whether the mouse button is held down, or the finger is touching.
+    pub down: bool,
+}
+
+/// How held buttons repeat, like keys do in a text editor. Both values are
+/// in frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Repeat {
+    /// How long a button needs to be held before it starts repeating.
+    pub delay: u16,
+    /// How long to wait between each repeat, after the first one.
+    pub interval: u16,
+}
+
+impl Default for Repeat {
+    fn default() -> Self {
+        Self {
+            delay: 18,
+            interval: 4,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Default, Debug)]
 pub struct Input {
     pub gamepad: Button,
     pub previous_gamepad: Button,
+    pub pointer: Pointer,
+    pub previous_pointer: Pointer,
+    pub repeat: Repeat,
+    /// How many frames before this one each button has been held for, in
+    /// `Button::ALL` order.
+    held_frames: [u16; Button::ALL.len()],
 }
 
 impl Input {
+    /// Moves the current state into the previous state, and counts how long
+    /// each button has been held. Call this once at the end of each frame.
+    pub fn end_frame(&mut self) {
+        for button in Button::ALL {
+            let held_frames = &mut self.held_frames[button.index()];
+            *held_frames = if self.gamepad.contains(button) {
+                held_frames.saturating_add(1)
+            } else {
+                0
+            };
+        }
+
+        self.previous_gamepad = self.gamepad;
+        self.previous_pointer = self.pointer;
+    }
+
     pub fn pressed_this_frame(&self, buttons: Button) -> bool {
         !self.previous_gamepad.contains(buttons) && self.gamepad.contains(buttons)
     }
 
+    /// How many frames before this one `button` has been held for. Only
+    /// counts frames that ended with `end_frame`.
+    pub fn held_frames(&self, button: Button) -> u16 {
+        self.held_frames[button.index()]
+    }
+
+    /// True on the frame `button` was pressed, and then periodically while
+    /// it is held, as determined by `self.repeat`.
+    pub fn repeated_this_frame(&self, button: Button) -> bool {
+        if self.pressed_this_frame(button) {
+            return true
+        }
+
+        if !self.gamepad.contains(button) {
+            return false
+        }
+
+        let Repeat { delay, interval } = self.repeat;
+
+        match self.held_frames(button).checked_sub(delay) {
+            Some(since_first_repeat) => since_first_repeat % interval.max(1) == 0,
+            None => false,
+        }
+    }
+
     pub fn dir_pressed_this_frame(&self) -> Option<Dir> {
-        if self.pressed_this_frame(Button::UP) {
+        self.dir_where(Self::pressed_this_frame)
+    }
+
+    pub fn dir_repeated_this_frame(&self) -> Option<Dir> {
+        self.dir_where(Self::repeated_this_frame)
+    }
+
+    fn dir_where(&self, predicate: fn(&Self, Button) -> bool) -> Option<Dir> {
+        if predicate(self, Button::UP) {
             Some(Dir::Up)
-        } else if self.pressed_this_frame(Button::DOWN) {
+        } else if predicate(self, Button::DOWN) {
             Some(Dir::Down)
-        } else if self.pressed_this_frame(Button::LEFT) {
+        } else if predicate(self, Button::LEFT) {
             Some(Dir::Left)
-        } else if self.pressed_this_frame(Button::RIGHT) {
+        } else if predicate(self, Button::RIGHT) {
             Some(Dir::Right)
         } else {
             None
@@ -1028,6 +1219,25 @@ impl Input {
     pub fn released_this_frame(&self, buttons: Button) -> bool {
         self.previous_gamepad.contains(buttons) && !self.gamepad.contains(buttons)
     }
+
+    pub fn pointer_pressed_this_frame(&self) -> bool {
+        !self.previous_pointer.down && self.pointer.down
+    }
+
+    pub fn pointer_released_this_frame(&self) -> bool {
+        self.previous_pointer.down && !self.pointer.down
+    }
+
+    pub fn pointer_moved_this_frame(&self) -> bool {
+        self.previous_pointer.xy != self.pointer.xy
+    }
+
+    pub fn pointer_is_over(&self, rect: unscaled::Rect) -> bool {
+        match self.pointer.xy {
+            Some(xy) => rect.contains(xy),
+            None => false,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1080,6 +1290,36 @@ pub mod button {
 
         pub const HELP: Self = Self::SELECT;
 
+        pub const ALL: [Self; 8] = [
+            Self::A,
+            Self::B,
+            Self::SELECT,
+            Self::START,
+            Self::UP,
+            Self::DOWN,
+            Self::LEFT,
+            Self::RIGHT,
+        ];
+
+        /// The index of this button in `ALL`. Only meaningful for single buttons.
+        pub const fn index(self) -> usize {
+            self.0.trailing_zeros() as usize
+        }
+
+        pub const fn text(self) -> &'static str {
+            match self {
+                Self::A => "A",
+                Self::B => "B",
+                Self::SELECT => "Select",
+                Self::START => "Start",
+                Self::UP => "Up",
+                Self::DOWN => "Down",
+                Self::LEFT => "Left",
+                Self::RIGHT => "Right",
+                _ => "Buttons",
+            }
+        }
+
         pub const fn contains(&self, other: Self) -> bool {
             self.0 & other.0 == other.0
         }
@@ -1095,6 +1335,238 @@ pub mod button {
 }
 pub use button::Button;
 
+/// Which keyboard keys press which `Button`s.
+pub mod bindings {
+    use super::Button;
+
+    macro_rules! keys {
+        ($($variant: ident $text: literal)+) => {
+            /// A keyboard key, independent of whichever windowing library
+            /// reported it.
+            #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+            pub enum Key {
+                $($variant,)+
+            }
+
+            impl Key {
+                pub const COUNT: usize = [$(stringify!($variant)),+].len();
+
+                pub const ALL: [Self; Self::COUNT] = [$(Self::$variant),+];
+
+                /// What to show the player.
+                pub const fn text(self) -> &'static str {
+                    match self {
+                        $(Self::$variant => $text,)+
+                    }
+                }
+
+                /// What to write in config files. Unlike `text`, this never
+                /// contains whitespace.
+                pub const fn name(self) -> &'static str {
+                    match self {
+                        $(Self::$variant => stringify!($variant),)+
+                    }
+                }
+            }
+        }
+    }
+
+    keys!{
+        A "A" B "B" C "C" D "D" E "E" F "F" G "G" H "H" I "I" J "J" K "K"
+        L "L" M "M" N "N" O "O" P "P" Q "Q" R "R" S "S" T "T" U "U" V "V"
+        W "W" X "X" Y "Y" Z "Z"
+        Key0 "0" Key1 "1" Key2 "2" Key3 "3" Key4 "4" Key5 "5" Key6 "6"
+        Key7 "7" Key8 "8" Key9 "9"
+        Up "Up" Down "Down" Left "Left" Right "Right"
+        Return "Enter" Space "Space" Escape "Escape" Tab "Tab"
+        Back "Backspace"
+        LShift "Left Shift" RShift "Right Shift"
+        LControl "Left Ctrl" RControl "Right Ctrl"
+        LAlt "Left Alt" RAlt "Right Alt"
+        Comma "," Period "." Slash "/" Backslash "\\" Semicolon ";"
+        Apostrophe "'" Minus "-" Equals "=" LBracket "[" RBracket "]"
+        Grave "`"
+    }
+
+    impl Key {
+        pub fn from_name(name: &str) -> Option<Self> {
+            Self::ALL.into_iter().find(|key| key.name() == name)
+        }
+    }
+
+    pub const MAX_KEYS_PER_BUTTON: usize = 4;
+
+    type Keys = [Option<Key>; MAX_KEYS_PER_BUTTON];
+
+    /// The keys for each button are kept packed at the front of their array,
+    /// in the order they were bound.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Bindings([Keys; Button::ALL.len()]);
+
+    impl Default for Bindings {
+        fn default() -> Self {
+            use Key::*;
+
+            // In `Button::ALL` order.
+            Self([
+                // Semicolon and Q are for those using the Dvorak layout.
+                [Some(Z), Some(Semicolon), None, None],
+                [Some(X), Some(Q), None, None],
+                [Some(LShift), Some(RShift), None, None],
+                [Some(Return), None, None, None],
+                [Some(Up), None, None, None],
+                [Some(Down), None, None, None],
+                [Some(Left), None, None, None],
+                [Some(Right), None, None, None],
+            ])
+        }
+    }
+
+    impl Bindings {
+        pub fn keys(&self, button: Button) -> impl Iterator<Item = Key> + '_ {
+            self.0[button.index()].iter().flatten().copied()
+        }
+
+        pub fn button_for(&self, key: Key) -> Option<Button> {
+            Button::ALL.into_iter()
+                .find(|&button| self.keys(button).any(|k| k == key))
+        }
+
+        /// Binds `key` to `button`, taking it away from whichever button it was
+        /// bound to before. If `button` already has as many keys as it can, the
+        /// one that was bound the longest ago is dropped.
+        ///
+        /// If taking the key away would leave the other button with no keys at
+        /// all, nothing changes, and that other button is returned.
+        pub fn bind(&mut self, button: Button, key: Key) -> Result<(), Button> {
+            if let Some(other) = self.button_for(key) {
+                if other == button {
+                    return Ok(());
+                }
+
+                if self.keys(other).count() <= 1 {
+                    return Err(other);
+                }
+
+                let keys = &mut self.0[other.index()];
+                if let Some(i) = keys.iter().position(|&k| k == Some(key)) {
+                    keys[i..].rotate_left(1);
+                    keys[MAX_KEYS_PER_BUTTON - 1] = None;
+                }
+            }
+
+            let keys = &mut self.0[button.index()];
+            match keys.iter().position(Option::is_none) {
+                Some(i) => keys[i] = Some(key),
+                None => {
+                    keys.rotate_left(1);
+                    keys[MAX_KEYS_PER_BUTTON - 1] = Some(key);
+                }
+            }
+
+            Ok(())
+        }
+
+        /// One line per button, like `A = Z Semicolon`.
+        pub fn to_config(&self) -> String {
+            let mut output = String::new();
+
+            for button in Button::ALL {
+                output.push_str(button.text());
+                output.push_str(" =");
+                for key in self.keys(button) {
+                    output.push(' ');
+                    output.push_str(key.name());
+                }
+                output.push('\n');
+            }
+
+            output
+        }
+
+        /// The inverse of `to_config`. Any button without a line that can be
+        /// understood keeps its default keys.
+        ///
+        /// Each key ends up bound to at most one button, like with `bind`: a key
+        /// on a button's line is taken away from the button it is bound to by
+        /// default, or from any button with a line further down. A button left
+        /// with no keys that way gets its default keys back, as long as that
+        /// leaves every other button with at least one key. If not, the
+        /// config is not used at all.
+        pub fn from_config(config: &str) -> Self {
+            let mut lines: [Option<Keys>; Button::ALL.len()] = [None; Button::ALL.len()];
+
+            for line in config.lines() {
+                let Some((button_text, key_names)) = line.split_once('=') else {
+                    continue
+                };
+
+                let Some(button) = Button::ALL.into_iter()
+                    .find(|button| button.text() == button_text.trim()) else {
+                    continue
+                };
+
+                let mut keys: Keys = [None; MAX_KEYS_PER_BUTTON];
+                for (slot, key) in keys.iter_mut().zip(
+                    key_names.split_whitespace().filter_map(Key::from_name)
+                ) {
+                    *slot = Some(key);
+                }
+
+                if keys[0].is_some() {
+                    lines[button.index()] = Some(keys);
+                }
+            }
+
+            let mut output = Self([[None; MAX_KEYS_PER_BUTTON]; Button::ALL.len()]);
+
+            let mut add_unbound = |button: Button, keys: Keys| {
+                for key in keys.into_iter().flatten() {
+                    if output.button_for(key).is_none() {
+                        let keys = &mut output.0[button.index()];
+                        if let Some(i) = keys.iter().position(Option::is_none) {
+                            keys[i] = Some(key);
+                        }
+                    }
+                }
+            };
+
+            for (button, keys) in Button::ALL.into_iter().zip(lines) {
+                if let Some(keys) = keys {
+                    add_unbound(button, keys);
+                }
+            }
+
+            let defaults = Self::default();
+
+            for (button, keys) in Button::ALL.into_iter().zip(lines) {
+                if keys.is_none() {
+                    add_unbound(button, defaults.0[button.index()]);
+                }
+            }
+
+            for button in Button::ALL {
+                if output.keys(button).next().is_some() {
+                    continue
+                }
+
+                for key in defaults.keys(button) {
+                    // An error leaves this button with no keys, which is
+                    // caught below.
+                    let _ = output.bind(button, key);
+                }
+
+                if output.keys(button).next().is_none() {
+                    return defaults
+                }
+            }
+
+            output
+        }
+    }
+}
+pub use bindings::{Bindings, Key};
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Dir {
     Up,
@@ -1113,6 +1585,29 @@ pub trait State {
     fn press(&mut self, button: Button);
 
     fn release(&mut self, button: Button);
+
+    /// `xy` is `None` when the pointer has left the screen.
+    fn pointer_move(&mut self, xy: Option<unscaled::XY>);
+
+    fn pointer_press(&mut self);
+
+    fn pointer_release(&mut self);
+
+    /// Keys are passed along as they are, rather than turned into `Button`s by
+    /// the platform, so that the state can rebind them.
+    fn key_press(&mut self, key: Key);
+
+    fn key_release(&mut self, key: Key);
+
+    fn bindings(&self) -> &Bindings;
+
+    /// Used to restore bindings the platform saved earlier.
+    fn set_bindings(&mut self, bindings: Bindings);
+
+    fn theme(&self) -> Theme;
+
+    /// Used to restore a theme the platform saved earlier.
+    fn set_theme(&mut self, theme: Theme);
 }
 
 // reportedly colourblind friendly colours
@@ -1146,6 +1641,138 @@ pub const PALETTE: [ARGB; 8] = [
     BLACK,
 ];
 
+/// Which colour each of the `PALETTE` colours is shown as. The graphics, and
+/// the colours the game asks for, are all in terms of `PALETTE`, and the
+/// renderer swaps in the theme's colours as it draws.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Default,
+    HighContrast,
+    /// Suit colours that can be told apart with deuteranopia, where green
+    /// looks like red.
+    Deuteranopia,
+    /// Suit colours that can be told apart with protanopia, where red looks
+    /// like green, and darker than it should.
+    Protanopia,
+    /// Darker all round, for dim rooms.
+    DarkTable,
+}
+
+impl Theme {
+    pub const COUNT: usize = 5;
+
+    pub const ALL: [Self; Self::COUNT] = [
+        Self::Default,
+        Self::HighContrast,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::DarkTable,
+    ];
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::COUNT]
+    }
+
+    /// What to show the player.
+    pub const fn text(self) -> &'static str {
+        match self {
+            Self::Default => "Default",
+            Self::HighContrast => "High contrast",
+            Self::Deuteranopia => "Deuteranopia friendly",
+            Self::Protanopia => "Protanopia friendly",
+            Self::DarkTable => "Dark table",
+        }
+    }
+
+    /// What to write in config files. Unlike `text`, this never contains
+    /// whitespace.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Default => "Default",
+            Self::HighContrast => "HighContrast",
+            Self::Deuteranopia => "Deuteranopia",
+            Self::Protanopia => "Protanopia",
+            Self::DarkTable => "DarkTable",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|theme| theme.name() == name)
+    }
+
+    /// The colours to show in place of the colours in `PALETTE`, in the same
+    /// order.
+    pub const fn palette(self) -> [ARGB; 8] {
+        match self {
+            Self::Default => PALETTE,
+            Self::HighContrast => [
+                0xFF1F5BFF,
+                0xFF00C853,
+                0xFFFF1744,
+                0xFFFFE600,
+                0xFFB040FF,
+                0xFF9AAAB0,
+                0xFFFFFFFF,
+                0xFF000000,
+            ],
+            // The suit colours for these two are from the Okabe-Ito palette.
+            // https://jfly.uni-koeln.de/color/
+            Self::Deuteranopia => [
+                0xFF0072B2,
+                0xFF56B4E9,
+                0xFFE69F00,
+                0xFFF0E442,
+                0xFFCC79A7,
+                GREY,
+                WHITE,
+                BLACK,
+            ],
+            Self::Protanopia => [
+                0xFF0072B2,
+                0xFF009E73,
+                0xFFD55E00,
+                0xFFF0E442,
+                0xFFCC79A7,
+                GREY,
+                WHITE,
+                BLACK,
+            ],
+            Self::DarkTable => [
+                0xFF263E9E,
+                0xFF237F52,
+                0xFFA03636,
+                0xFFB88A2A,
+                0xFF4A2F4B,
+                0xFF3F5761,
+                0xFFB4B4B4,
+                0xFF0C0C0C,
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod themes_round_trip {
+    use super::*;
+
+    #[test]
+    fn through_their_names() {
+        for theme in Theme::ALL {
+            assert_eq!(Theme::from_name(theme.name()), Some(theme));
+        }
+    }
+
+    #[test]
+    fn through_next() {
+        let mut theme = Theme::default();
+        for _ in 0..Theme::COUNT {
+            theme = theme.next();
+        }
+        assert_eq!(theme, Theme::default());
+    }
+}
+
 pub fn bytes_lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
     bytes.split(|&b| b == b'\n')
 }
@@ -1159,3 +1786,152 @@ pub fn longest_line_of(bytes: &[u8]) -> &[u8] {
     }
     output
 }
+
+#[cfg(test)]
+mod bindings_work {
+    use super::*;
+
+    #[test]
+    fn when_round_tripped_through_a_config() {
+        let mut bindings = Bindings::default();
+        bindings.bind(Button::START, Key::Space).unwrap();
+        bindings.bind(Button::A, Key::Key1).unwrap();
+
+        assert_eq!(Bindings::from_config(&bindings.to_config()), bindings);
+    }
+
+    #[test]
+    fn when_the_config_is_missing_lines_or_has_junk_in_it() {
+        let bindings = Bindings::from_config("B = Escape NotAKey Back\nnonsense\nUp =\n");
+
+        assert_eq!(bindings.keys(Button::B).collect::<Vec<_>>(), [Key::Escape, Key::Back]);
+        assert_eq!(bindings.keys(Button::UP).collect::<Vec<_>>(), [Key::Up]);
+        assert_eq!(bindings.keys(Button::A).collect::<Vec<_>>(), [Key::Z, Key::Semicolon]);
+    }
+
+    #[test]
+    fn when_the_config_binds_a_key_to_two_buttons() {
+        let bindings = Bindings::from_config("B = Z\nSelect = Return LShift\nStart = Return\n");
+
+        assert_eq!(bindings.button_for(Key::Z), Some(Button::B));
+        assert_eq!(bindings.keys(Button::A).collect::<Vec<_>>(), [Key::Semicolon]);
+        // Start would have no keys left, so it gets its default back.
+        assert_eq!(bindings.keys(Button::START).collect::<Vec<_>>(), [Key::Return]);
+        assert_eq!(bindings.keys(Button::SELECT).collect::<Vec<_>>(), [Key::LShift]);
+        assert_eq!(bindings.button_for(Key::RShift), None);
+    }
+
+    #[test]
+    fn when_buttons_have_swapped_keys() {
+        let mut bindings = Bindings::default();
+        bindings.bind(Button::UP, Key::Key1).unwrap();
+        bindings.bind(Button::START, Key::Up).unwrap();
+        bindings.bind(Button::UP, Key::Return).unwrap();
+
+        assert_eq!(Bindings::from_config(&bindings.to_config()), bindings);
+    }
+
+    #[test]
+    fn when_a_key_is_moved_to_another_button() {
+        let mut bindings = Bindings::default();
+
+        assert_eq!(bindings.bind(Button::B, Key::Z), Ok(()));
+        assert_eq!(bindings.button_for(Key::Z), Some(Button::B));
+        assert_eq!(bindings.keys(Button::A).collect::<Vec<_>>(), [Key::Semicolon]);
+    }
+
+    #[test]
+    fn unless_that_would_leave_a_button_with_no_keys() {
+        let mut bindings = Bindings::default();
+
+        assert_eq!(bindings.bind(Button::A, Key::Return), Err(Button::START));
+        assert_eq!(bindings, Bindings::default());
+    }
+
+    #[test]
+    fn when_a_button_has_too_many_keys() {
+        let mut bindings = Bindings::default();
+
+        for key in [Key::Key1, Key::Key2, Key::Key3] {
+            bindings.bind(Button::A, key).unwrap();
+        }
+
+        assert_eq!(
+            bindings.keys(Button::A).collect::<Vec<_>>(),
+            [Key::Semicolon, Key::Key1, Key::Key2, Key::Key3]
+        );
+        assert_eq!(bindings.button_for(Key::Z), None);
+    }
+
+    #[test]
+    fn by_default_like_they_used_to_be_hard_coded() {
+        let bindings = Bindings::default();
+
+        for (key, button) in [
+            (Key::Return, Button::START),
+            (Key::LShift, Button::SELECT),
+            (Key::RShift, Button::SELECT),
+            (Key::Up, Button::UP),
+            (Key::Left, Button::LEFT),
+            (Key::Right, Button::RIGHT),
+            (Key::Down, Button::DOWN),
+            (Key::Z, Button::A),
+            (Key::X, Button::B),
+            (Key::Semicolon, Button::A),
+            (Key::Q, Button::B),
+        ] {
+            assert_eq!(bindings.button_for(key), Some(button), "{key:?}");
+        }
+
+        assert_eq!(bindings.button_for(Key::Space), None);
+    }
+}
+
+#[cfg(test)]
+mod held_buttons_repeat {
+    use super::*;
+
+    fn repeated_frames(input: &mut Input, frame_count: u16) -> Vec<u16> {
+        let mut output = Vec::new();
+
+        for frame in 0..frame_count {
+            if input.repeated_this_frame(Button::RIGHT) {
+                output.push(frame);
+            }
+            input.end_frame();
+        }
+
+        output
+    }
+
+    #[test]
+    fn after_the_delay_and_then_at_the_interval() {
+        let mut input = Input {
+            repeat: Repeat { delay: 5, interval: 3 },
+            ..Input::default()
+        };
+        input.gamepad.insert(Button::RIGHT);
+
+        assert_eq!(repeated_frames(&mut input, 15), [0, 5, 8, 11, 14]);
+    }
+
+    #[test]
+    fn only_while_held() {
+        let mut input = Input {
+            repeat: Repeat { delay: 2, interval: 1 },
+            ..Input::default()
+        };
+        input.gamepad.insert(Button::RIGHT);
+
+        assert_eq!(repeated_frames(&mut input, 4), [0, 2, 3]);
+
+        input.gamepad.remove(Button::RIGHT);
+
+        assert_eq!(repeated_frames(&mut input, 4), []);
+        assert_eq!(input.held_frames(Button::RIGHT), 0);
+
+        input.gamepad.insert(Button::RIGHT);
+
+        assert_eq!(repeated_frames(&mut input, 2), [0]);
+    }
+}
@@ -1,8 +1,11 @@
 #![deny(clippy::shadow_unrelated)]
 use platform_types::{
     Command,
+    GFX_LENGTH,
     GFX_WIDTH,
     ARGB,
+    PALETTE,
+    Theme,
     colours,
     command::{self, Rect},
     sprite,
@@ -18,7 +21,7 @@ pub mod clip {
     pub type W = u16;
     pub type H = u16;
 
-    #[derive(Clone, Debug)]
+    #[derive(Clone, Debug, PartialEq, Eq)]
     pub struct Rect {
         pub x: Range<X>,
         pub y: Range<Y>,
@@ -76,6 +79,8 @@ mod hash {
             },
             sprite_xy,
             colour_override,
+            transform,
+            fill,
         } = command;
 
         u16(hash, x_min.get().get());
@@ -87,6 +92,10 @@ mod hash {
         u16(hash, sprite_xy.y.0);
 
         bytes(hash, &colour_override.to_ne_bytes());
+
+        byte(hash, transform.bits());
+
+        byte(hash, fill as u8);
     }
 }
 
@@ -103,6 +112,52 @@ pub struct FrameBuffer {
     pub width: clip::W,
     pub height: clip::H,
     pub cells: HashCells,
+    /// The parts of `buffer` that changed during the last call to `render`.
+    pub damage: Vec<clip::Rect>,
+    /// The parts of `unscaled_buffer` that changed during the last call to
+    /// `render`.
+    pub unscaled_damage: Vec<clip::Rect>,
+    /// The size `buffer` was last rendered at, if it has been rendered to.
+    rendered_size: Option<(clip::W, clip::H)>,
+    pub threads: Threads,
+    /// Kept between frames, so they are only started once.
+    #[cfg(not(target_arch = "wasm32"))]
+    workers: workers::Workers,
+    theme: Theme,
+    gfx: Gfx,
+}
+
+/// How many threads to use when scaling up `unscaled_buffer` into `buffer`.
+/// Each thread gets a band of rows of `buffer`, and the result is the same
+/// no matter how many there are.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Threads {
+    #[default]
+    One,
+    /// Up to this many, with fewer used when there are not enough changed
+    /// pixels to make it worth it. wasm has no threads, so this is not there.
+    #[cfg(not(target_arch = "wasm32"))]
+    Many(core::num::NonZeroUsize),
+}
+
+impl Threads {
+    /// Enough to get most of the benefit, without taking up every core that
+    /// the rest of the program, and everything else, might want.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub const MAX_AVAILABLE: usize = 4;
+
+    /// As many as the machine can run in parallel, up to `MAX_AVAILABLE`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn available() -> Self {
+        match std::thread::available_parallelism()
+            .ok()
+            .and_then(|n| core::num::NonZeroUsize::new(
+                core::cmp::min(n.get(), Self::MAX_AVAILABLE)
+            )) {
+            Some(n) if n.get() > 1 => Self::Many(n),
+            _ => Self::One,
+        }
+    }
 }
 
 impl FrameBuffer {
@@ -115,32 +170,216 @@ impl FrameBuffer {
             width,
             height,
             cells: HashCells::default(),
+            damage: Vec::with_capacity(tile::COUNT),
+            unscaled_damage: Vec::with_capacity(tile::COUNT),
+            rendered_size: None,
+            threads: Threads::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            workers: workers::Workers::default(),
+            theme: Theme::default(),
+            gfx: Gfx::Default,
+        }
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme
+    }
+
+    /// Changing the theme redraws everything on the next call to `render`.
+    pub fn set_theme(&mut self, theme: Theme) {
+        if theme == self.theme {
+            return
+        }
+
+        self.theme = theme;
+        self.gfx = Gfx::for_theme(theme);
+        // Forget the last size, so the next frame is drawn from scratch.
+        self.rendered_size = None;
+    }
+}
+
+/// The graphics to draw from, with the colours of the current theme.
+enum Gfx {
+    /// The default theme's colours are the ones the graphics already have.
+    Default,
+    Themed(Box<[ARGB; GFX_LENGTH]>),
+}
+
+impl Gfx {
+    fn for_theme(theme: Theme) -> Self {
+        if theme == Theme::default() {
+            return Self::Default
+        }
+
+        let palette = theme.palette();
+
+        let themed: Box<[ARGB]> = GFX.iter()
+            .map(|&argb| recolour(argb, &palette))
+            .collect();
+
+        match themed.try_into() {
+            Ok(themed) => Self::Themed(themed),
+            Err(_) => {
+                debug_assert!(false, "themed gfx should be the same length");
+                Self::Default
+            },
+        }
+    }
+
+    fn pixels(&self) -> &[ARGB; GFX_LENGTH] {
+        match self {
+            Self::Default => &GFX,
+            Self::Themed(pixels) => pixels,
+        }
+    }
+}
+
+/// How far each channel of a colour can be from one in `PALETTE` and still be
+/// recoloured. The graphics have some shading that is a little off of the
+/// palette colours, which should follow the colour it is shading.
+const RECOLOUR_DISTANCE: i32 = 16;
+
+/// Swaps a colour that is at or near one of the colours in `PALETTE` for the
+/// matching colour in `palette`, keeping the alpha, and any offset from the
+/// `PALETTE` colour. Other colours are returned as is.
+pub fn recolour(argb: ARGB, palette: &[ARGB; PALETTE.len()]) -> ARGB {
+    let channel = |colour: ARGB, shift: u32| ((colour >> shift) & 0xFF) as i32;
+
+    for (&from, &to) in PALETTE.iter().zip(palette.iter()) {
+        let is_near = [16, 8, 0].into_iter().all(|shift|
+            (channel(argb, shift) - channel(from, shift)).abs() <= RECOLOUR_DISTANCE
+        );
+
+        if is_near {
+            return [16, 8, 0].into_iter().fold(
+                argb & 0xFF00_0000,
+                |output, shift| {
+                    let offset = channel(argb, shift) - channel(from, shift);
+                    let value = (channel(to, shift) + offset).clamp(0, 0xFF);
+                    output | ((value as ARGB) << shift)
+                }
+            );
         }
     }
+
+    argb
+}
+
+/// The unscaled screen is split into square tiles, so that only the tiles that
+/// changed since the last frame need to be redrawn.
+pub mod tile {
+    use super::*;
+
+    pub const SIZE: unscaled::Inner = 16;
+
+    pub const COLUMNS: usize = (command::WIDTH / SIZE) as usize;
+    pub const ROWS: usize = (command::HEIGHT / SIZE) as usize;
+    pub const COUNT: usize = COLUMNS * ROWS;
+
+    const _: () = assert!(
+        command::WIDTH.is_multiple_of(SIZE)
+            && command::HEIGHT.is_multiple_of(SIZE),
+        "Tiles should cover the screen exactly"
+    );
+
+    /// The columns and rows of the tiles that the given rect touches, as
+    /// inclusive ranges.
+    pub fn covered_by(rect: &Rect) -> ((usize, usize), (usize, usize)) {
+        let column = |x: unscaled::Inner| {
+            core::cmp::min(usize::from(x / SIZE), COLUMNS - 1)
+        };
+        let row = |y: unscaled::Inner| {
+            core::cmp::min(usize::from(y / SIZE), ROWS - 1)
+        };
+
+        (
+            (column(rect.x_min.get().get()), column(rect.x_max.get().get())),
+            (row(rect.y_min.get().get()), row(rect.y_max.get().get())),
+        )
+    }
 }
 
-#[derive(Default)]
+type Cells = [hash::Cell; tile::COUNT];
+
+/// One hash per tile, of every command that touches that tile, in the order
+/// they are drawn in. So if a tile's hash is the same as last frame's, it will
+/// be drawn the same as last frame.
 pub struct HashCells {
     current_cells: CurrentCells,
-    cells_a: hash::Cell,
-    cells_b: hash::Cell,
+    cells_a: Cells,
+    cells_b: Cells,
+}
+
+impl Default for HashCells {
+    fn default() -> Self {
+        Self {
+            current_cells: <_>::default(),
+            cells_a: [<_>::default(); tile::COUNT],
+            cells_b: [<_>::default(); tile::COUNT],
+        }
+    }
 }
 
 impl HashCells {
-    fn current_mut(&mut self) -> &mut hash::Cell {
+    fn current_mut(&mut self) -> &mut Cells {
         match self.current_cells {
             CurrentCells::A => &mut self.cells_a,
             CurrentCells::B => &mut self.cells_b,
         }
     }
 
-    fn current_and_prev(&self) -> (&hash::Cell, &hash::Cell) {
+    fn current_and_prev(&self) -> (&Cells, &Cells) {
         match self.current_cells {
             CurrentCells::A => (&self.cells_a, &self.cells_b),
             CurrentCells::B => (&self.cells_b, &self.cells_a),
         }
     }
 
+    /// Pushes rects, in unscaled pixels, covering every tile whose hash changed
+    /// since the last frame. Runs of changed tiles in a row become one rect,
+    /// and those are merged with matching runs in the rows below them.
+    fn push_damaged_rects(&self, rects: &mut Vec<clip::Rect>) {
+        let (cells, cells_prev) = self.current_and_prev();
+        let is_damaged = |row: usize, column: usize| {
+            let i = row * tile::COLUMNS + column;
+            cells[i] != cells_prev[i]
+        };
+        let to_pixels = |tiles: usize| tiles as unscaled::Inner * tile::SIZE;
+
+        let first = rects.len();
+
+        for row in 0..tile::ROWS {
+            let mut column = 0;
+            while column < tile::COLUMNS {
+                if !is_damaged(row, column) {
+                    column += 1;
+                    continue
+                }
+
+                let start = column;
+                while column < tile::COLUMNS && is_damaged(row, column) {
+                    column += 1;
+                }
+
+                let x = to_pixels(start)..to_pixels(column);
+                let y_start = to_pixels(row);
+
+                match rects[first..].iter_mut()
+                    .find(|rect| rect.x == x && rect.y.end == y_start) {
+                    Some(above) => {
+                        above.y.end = to_pixels(row + 1);
+                    },
+                    None => {
+                        rects.push(clip::Rect {
+                            x,
+                            y: y_start..to_pixels(row + 1),
+                        });
+                    },
+                }
+            }
+        }
+    }
+
     fn swap(&mut self) {
         self.current_cells = match self.current_cells {
             CurrentCells::A => CurrentCells::B,
@@ -154,13 +393,23 @@ impl HashCells {
         (w, h): (u16, u16),
     ) {
         let cells = self.current_mut();
-        *cells = <_>::default();
 
-        hash::u16(cells, w);
-        hash::u16(cells, h);
+        for cell in cells.iter_mut() {
+            *cell = <_>::default();
+
+            hash::u16(cell, w);
+            hash::u16(cell, h);
+        }
 
         for command in commands {
-            hash::command(cells, command);
+            let ((column_min, column_max), (row_min, row_max)) =
+                tile::covered_by(&command.rect);
+
+            for row in row_min..=row_max {
+                for column in column_min..=column_max {
+                    hash::command(&mut cells[row * tile::COLUMNS + column], command);
+                }
+            }
         }
     }
 }
@@ -511,7 +760,14 @@ mod wide {
             $a: expr $(,)?
         ) => ({#[allow(unused_unsafe)]
             unsafe {
-                core::arch::x86_64::_mm_rcp_ps($a)
+                // `_mm_rcp_ps` would be faster, but it is only an approximation,
+                // and how close it gets differs between CPUs. So using it would
+                // make the output differ from the other paths, and from machine
+                // to machine.
+                core::arch::x86_64::_mm_div_ps(
+                    core::arch::x86_64::_mm_set_ps1(1.),
+                    $a
+                )
             }
         });
     }
@@ -763,7 +1019,11 @@ mod wide {
             $a: expr $(,)?
         ) => ({#[allow(unused_unsafe)]
             unsafe {
-                core::arch::wasm32::u32x4_trunc_sat_f32x4($a)
+                // Round to the nearest, like `_mm_cvtps_epi32` does, instead of
+                // truncating, so the paths all agree.
+                core::arch::wasm32::u32x4_trunc_sat_f32x4(
+                    core::arch::wasm32::f32x4_nearest($a)
+                )
             }
         });
     }
@@ -939,10 +1199,204 @@ mod wide {
     ),
 )))]
 mod wide {
+    pub use super::scalar::*;
+}
+
+/// The same operations as `wide`, done one lane at a time, so they work on
+/// every target. Used in place of `wide` where there is no SIMD version, and
+/// available everywhere through `Path::Scalar`, so the paths can be checked
+/// against each other.
+mod scalar {
     use super::*;
     pub const WIDTH: unscaled::Inner = 4;
 
-    compile_error!("wide not implemented for this target");
+    pub type I32x4 = [i32; WIDTH as usize];
+    pub type F32x4 = [f32; WIDTH as usize];
+
+    pub mod lanes {
+        use super::{F32x4, I32x4};
+
+        fn map<A: Copy, B>(a: [A; 4], f: impl Fn(A) -> B) -> [B; 4] {
+            [f(a[0]), f(a[1]), f(a[2]), f(a[3])]
+        }
+
+        fn zip<A: Copy, B: Copy, C>(a: [A; 4], b: [B; 4], f: impl Fn(A, B) -> C) -> [C; 4] {
+            [f(a[0], b[0]), f(a[1], b[1]), f(a[2], b[2]), f(a[3], b[3])]
+        }
+
+        fn mask(b: bool) -> i32 {
+            if b { -1 } else { 0 }
+        }
+
+        pub fn pick_via_mask(zero_case: I32x4, one_case: I32x4, mask: I32x4) -> I32x4 {
+            zip(zip(zero_case, one_case, |z, o| (z, o)), mask, |(z, o), m| (m & o) | (!m & z))
+        }
+
+        pub fn and(a: I32x4, b: I32x4) -> I32x4 {
+            zip(a, b, |a, b| a & b)
+        }
+
+        pub fn and_not(just_anded: I32x4, notted: I32x4) -> I32x4 {
+            zip(just_anded, notted, |a, n| a & !n)
+        }
+
+        pub fn or(a: I32x4, b: I32x4) -> I32x4 {
+            zip(a, b, |a, b| a | b)
+        }
+
+        pub fn left_shift_32(a: I32x4, bits: u32) -> I32x4 {
+            map(a, |a| ((a as u32) << bits) as i32)
+        }
+
+        pub fn right_shift_32(a: I32x4, bits: u32) -> I32x4 {
+            map(a, |a| ((a as u32) >> bits) as i32)
+        }
+
+        pub fn f32_to_u32(a: F32x4) -> I32x4 {
+            map(a, |a| a.round_ties_even() as i32)
+        }
+
+        pub fn u32_to_f32(a: I32x4) -> F32x4 {
+            map(a, |a| a as f32)
+        }
+
+        pub fn add_f32(a: F32x4, b: F32x4) -> F32x4 {
+            zip(a, b, |a, b| a + b)
+        }
+
+        pub fn add_i32(a: I32x4, b: I32x4) -> I32x4 {
+            zip(a, b, i32::wrapping_add)
+        }
+
+        pub fn sub(a: F32x4, b: F32x4) -> F32x4 {
+            zip(a, b, |a, b| a - b)
+        }
+
+        pub fn mul(a: F32x4, b: F32x4) -> F32x4 {
+            zip(a, b, |a, b| a * b)
+        }
+
+        pub fn recip(a: F32x4) -> F32x4 {
+            map(a, |a| 1. / a)
+        }
+
+        pub fn sqrt(a: F32x4) -> F32x4 {
+            map(a, f32::sqrt)
+        }
+
+        pub fn lt_mask_32(lhs: I32x4, rhs: I32x4) -> I32x4 {
+            zip(lhs, rhs, |l, r| mask(l < r))
+        }
+
+        pub fn eq_mask_u32(lhs: I32x4, rhs: I32x4) -> I32x4 {
+            zip(lhs, rhs, |l, r| mask(l == r))
+        }
+
+        #[allow(dead_code)]
+        pub fn gt_mask_32(lhs: I32x4, rhs: I32x4) -> I32x4 {
+            zip(lhs, rhs, |l, r| mask(l > r))
+        }
+    }
+
+    /// SAFETY: The pointers produced by the code generated by these macros must
+    /// be valid to read 128 bytes from.
+    /// We omit unsafe in the macro's code in order to encourage the user to notice
+    /// the invariants they are expected to uphold.
+    macro_rules! load {
+        (
+            $addr: expr $(,)?
+        ) => ({
+            let addr: *const _ = $addr;
+            core::ptr::read_unaligned(addr as *const $crate::scalar::I32x4)
+        });
+        (
+            $addr: expr,
+            $index: expr $(,)?
+        ) => ({
+            let addr: *const _ = $addr;
+            core::ptr::read_unaligned(addr.add($index) as *const $crate::scalar::I32x4)
+        });
+    }
+    pub(crate) use load;
+
+    /// SAFETY: The pointers produced by the code generated by these macros must
+    /// be valid to write 128 bytes to.
+    /// We omit unsafe in the macro's code in order to encourage the user to notice
+    /// the invariants they are expected to uphold.
+    macro_rules! store {
+        (
+            $vector: expr,
+            $addr: expr $(,)?
+        ) => ({
+            let vector: $crate::scalar::I32x4 = $vector;
+            let addr: *mut _ = $addr;
+            core::ptr::write_unaligned(addr as *mut $crate::scalar::I32x4, vector)
+        });
+        (
+            $vector: expr,
+            $addr: expr,
+            $index: expr $(,)?
+        ) => ({
+            let vector: $crate::scalar::I32x4 = $vector;
+            let addr: *mut _ = $addr;
+            core::ptr::write_unaligned(addr.add($index) as *mut $crate::scalar::I32x4, vector)
+        });
+    }
+    pub(crate) use store;
+
+    macro_rules! i32x4 {
+        (
+            $e0: expr,
+            $e1: expr,
+            $e2: expr,
+            $e3: expr $(,)?
+        ) => ({
+            let lanes: $crate::scalar::I32x4 = [$e0, $e1, $e2, $e3];
+            lanes
+        });
+    }
+    pub(crate) use i32x4;
+
+    macro_rules! i32 {
+        (
+            $a: expr $(,)?
+        ) => ({
+            let lanes: $crate::scalar::I32x4 = [$a; 4];
+            lanes
+        });
+    }
+    pub(crate) use i32;
+
+    macro_rules! f32 {
+        (
+            $a: expr $(,)?
+        ) => ({
+            let lanes: $crate::scalar::F32x4 = [$a; 4];
+            lanes
+        });
+    }
+    pub(crate) use f32;
+
+    // The rest just forward to the functions in `lanes`, and are macros so
+    // they can be used the same way as the ones in `wide`.
+    macro_rules! forward {
+        ($d: tt $($name: ident)+) => {$(
+            #[allow(unused_macros)]
+            macro_rules! $name {
+                ($d($d arg: expr),+ $d(,)?) => {
+                    $crate::scalar::lanes::$name($d($d arg),+)
+                };
+            }
+            #[allow(unused_imports)]
+            pub(crate) use $name;
+        )+}
+    }
+
+    forward!{$
+        pick_via_mask and and_not or left_shift_32 right_shift_32 f32_to_u32
+        u32_to_f32 add_f32 add_i32 sub mul recip sqrt lt_mask_32 eq_mask_u32
+        gt_mask_32
+    }
 }
 
 #[cfg(test)]
@@ -990,438 +1444,1421 @@ mod wide_tests {
     }
 }
 
-pub fn render(
-    frame_buffer: &mut FrameBuffer,
-    commands: &[Command],
-) -> NeedsRedraw {
-    if frame_buffer.width < command::WIDTH
-    || frame_buffer.height < command::HEIGHT {
-        frame_buffer.width = command::WIDTH;
-        frame_buffer.height = command::HEIGHT;
-    }
+/// How the screen fits into a frame buffer: scaled up by a whole number, with
+/// black bars around it to fill the rest.
+struct Letterboxing {
+    multiplier: u16,
+    left_bar_width: clip::W,
+    right_bar_width: clip::W,
+    top_bar_height: clip::H,
+    bottom_bar_height: clip::H,
+}
 
-    let width_multiplier = frame_buffer.width / command::WIDTH;
-    let height_multiplier = frame_buffer.height / command::HEIGHT;
-    let multiplier = core::cmp::min(width_multiplier, height_multiplier);
-    if multiplier == 0 {
-        debug_assert!(multiplier != 0);
-        return NeedsRedraw::No;
-    }
+impl Letterboxing {
+    fn new(width: clip::W, height: clip::H) -> Option<Self> {
+        let width_multiplier = width / command::WIDTH;
+        let height_multiplier = height / command::HEIGHT;
+        let multiplier = core::cmp::min(width_multiplier, height_multiplier);
+        if multiplier == 0 {
+            return None;
+        }
 
-    let vertical_bars_width: clip::W = frame_buffer.width - (multiplier * command::WIDTH);
+        let vertical_bars_width: clip::W = width - (multiplier * command::WIDTH);
 
-    let left_bar_width: clip::W = (vertical_bars_width + 1) / 2;
+        let horizontal_bars_height: clip::H = height - (multiplier * command::HEIGHT);
 
-    let right_bar_width: clip::W = vertical_bars_width / 2;
+        Some(Self {
+            multiplier,
+            left_bar_width: (vertical_bars_width + 1) / 2,
+            right_bar_width: vertical_bars_width / 2,
+            top_bar_height: (horizontal_bars_height + 1) / 2,
+            bottom_bar_height: horizontal_bars_height / 2,
+        })
+    }
+}
 
-    let horizontal_bars_height: clip::H = frame_buffer.height - (multiplier * command::HEIGHT);
+/// Maps a position in a frame buffer of the given size, as `render` would draw
+/// into it, back to where that is on the screen that the commands draw on.
+/// Returns `None` for positions in the bars around the screen.
+pub fn frame_buffer_to_screen(
+    (width, height): (clip::W, clip::H),
+    (x, y): (f64, f64),
+) -> Option<unscaled::XY> {
+    let (width, height) = if width < command::WIDTH
+    || height < command::HEIGHT {
+        (command::WIDTH, command::HEIGHT)
+    } else {
+        (width, height)
+    };
 
-    let top_bar_height: clip::H = (horizontal_bars_height + 1) / 2;
+    let Letterboxing {
+        multiplier,
+        left_bar_width,
+        top_bar_height,
+        ..
+    } = Letterboxing::new(width, height)?;
 
-    let bottom_bar_height: clip::H = horizontal_bars_height / 2;
+    let multiplier = f64::from(multiplier);
+    let screen_x = (x - f64::from(left_bar_width)) / multiplier;
+    let screen_y = (y - f64::from(top_bar_height)) / multiplier;
 
-    let outer_clip_rect = clip::Rect {
-        x: left_bar_width..(
-            frame_buffer.width - right_bar_width
-        ),
-        y: top_bar_height..(
-            frame_buffer.height - bottom_bar_height
-        ),
-    };
+    if screen_x < 0.
+    || screen_y < 0.
+    || screen_x >= f64::from(command::WIDTH)
+    || screen_y >= f64::from(command::HEIGHT) {
+        return None;
+    }
 
+    Some(unscaled::XY {
+        x: unscaled::X(screen_x as unscaled::Inner),
+        y: unscaled::Y(screen_y as unscaled::Inner),
+    })
+}
 
-    // This started as cached software rendering based on:
-    // https://rxi.github.io/cached_software_rendering.html
-    // But, a single cell turned out to be the most performant,
-    // in this particular case, so the code was simplifed with
-    // that in mind. Maybe it faster to have a single cell, just
-    // because the rendered size is so small. Not sure.
+/// Draws the parts of the commands inside the clip rect onto the unscaled
+/// buffer, with the operations from the given module, which is expected to have
+/// the same interface as `wide`.
+macro_rules! draw_commands_via {
+    (
+        $wide: ident,
+        $unscaled_buffer: expr,
+        $commands: expr,
+        $clip_rect: expr,
+        $gfx: expr,
+        $palette: expr $(,)?
+    ) => ({
+        let clip_rect: &clip::Rect = $clip_rect;
+        let gfx: &[ARGB; GFX_LENGTH] = $gfx;
+        let palette: &[ARGB; PALETTE.len()] = $palette;
+        let recolouring = palette != &PALETTE;
+        let wide_0 = $wide::i32!(0);
+        let wide_1_f32 = $wide::f32!(1.);
+        let wide_255_i32 = $wide::i32!(255);
+        let wide_inv_255_f32 = $wide::f32!(1./255.);
+        let wide_255_f32 = $wide::f32!(255.);
+        let wide_0_to_w = $wide::i32x4!(0, 1, 2, 3);
+
+        for &Command {
+            sprite_xy: sprite::XY { x: sprite_x, y: sprite_y },
+            colour_override,
+            rect,
+            transform,
+            fill,
+        } in $commands.iter() {
+            let colour_override = if recolouring && colour_override != 0 {
+                recolour(colour_override, palette)
+            } else {
+                colour_override
+            };
+            let colour_override_value = $wide::i32!(colour_override as i32);
+
+            let not_colour_override_mask = $wide::eq_mask_u32!(
+                colour_override_value,
+                wide_0
+            );
+
+            let unscaled::Rect { w: rect_w, h: rect_h, .. } = rect.unscaled();
+
+            let Rect {
+                x_min,
+                y_min,
+                x_max,
+                y_max,
+            } = rect;
 
-    frame_buffer.cells.reset_then_hash_commands(
-        commands,
-        (frame_buffer.width, frame_buffer.height),
-    );
+            // TODO make this wide too?
+            let unclipped_x_min = clip::X::from(x_min);
+            let unclipped_y_min = clip::Y::from(y_min);
+            let x_max = clip::W::from(x_max);
+            let y_max = clip::H::from(y_max);
 
-    let expected_length = usize::from(frame_buffer.width)
-    * usize::from(frame_buffer.height);
+            let x_min = core::cmp::max(unclipped_x_min, clip_rect.x.start);
+            let y_min = core::cmp::max(unclipped_y_min, clip_rect.y.start);
+            let x_end = core::cmp::min(x_max + 1, clip_rect.x.end);
+            let y_end = core::cmp::min(y_max + 1, clip_rect.y.end);
 
-    if frame_buffer.buffer.len() != expected_length {
-        frame_buffer.buffer.clear();
-        // Hopefully this compiles to something not inefficent
-        frame_buffer.buffer.reserve(expected_length);
-        for _ in 0..expected_length {
-            frame_buffer.buffer.push(0);
-        }
-    }
+            if x_min >= x_end || y_min >= y_end {
+                continue
+            }
 
-    let (cells, cells_prev) = frame_buffer.cells.current_and_prev();
+            let wide_x_end = $wide::i32!(x_end.into());
 
-    if cells == cells_prev {
-        frame_buffer.cells.swap();
-        return NeedsRedraw::No;
-    }
+            let sprite_x = usize::from(sprite_x);
+            let sprite_y = usize::from(sprite_y);
 
-    let wide_0 = wide::i32!(0);
-    let wide_1_f32 = wide::f32!(1.);
-    let wide_255_i32 = wide::i32!(255);
-    let wide_inv_255_f32 = wide::f32!(1./255.);
-    let wide_255_f32 = wide::f32!(255.);
-    let wide_0_to_w = wide::i32x4!(0, 1, 2, 3);
+            let src_w = GFX_WIDTH as usize;
 
-    // Hopefully this compiles to something not inefficent
-    for i in 0..frame_buffer.unscaled_buffer.len() {
-        frame_buffer.unscaled_buffer[i] = colours::BLACK;
-    }
+            let mut y_iter_count = usize::from(y_min - unclipped_y_min);
+            for y in y_min..y_end {
+                let mut x_iter_count = usize::from(x_min - unclipped_x_min);
+                let mut x = x_min;
 
-    for &Command {
-        sprite_xy: sprite::XY { x: sprite_x, y: sprite_y },
-        colour_override,
-        rect,
-    } in commands.iter() {
-        let colour_override_value = wide::i32!(colour_override as i32);
+                while x < x_end {
+                    let wide_xs = $wide::add_i32!(
+                        $wide::i32!(x.into()),
+                        wide_0_to_w
+                    );
 
-        let not_colour_override_mask = wide::eq_mask_u32!(
-            colour_override_value,
-            wide_0
-        );
+                    let dest_index = usize::from(y)
+                        * usize::from(command::WIDTH)
+                        + usize::from(x);
 
-        let Rect {
-            x_min,
-            y_min,
-            x_max,
-            y_max,
-        } = rect;
-
-        // TODO make this wide too?
-        let x_min = clip::X::from(x_min);
-        let y_min = clip::Y::from(y_min);
-        let x_max = clip::W::from(x_max);
-        let y_max = clip::H::from(y_max);
-
-        let x_end = x_max + 1;
-        let y_end = y_max + 1;
-        let wide_x_end = wide::i32!(x_end.into());
-
-        let sprite_x = usize::from(sprite_x);
-        let sprite_y = usize::from(sprite_y);
-
-        let src_w = GFX_WIDTH as usize;
-
-        let mut y_iter_count = 0;
-        for y in y_min..y_end {
-            let mut x_iter_count = 0;
-            let mut x = x_min;
-
-            while x < x_end {
-                let wide_xs = wide::add_i32!(
-                    wide::i32!(x.into()),
-                    wide_0_to_w
-                );
-
-                let dest_index = usize::from(y)
-                    * usize::from(command::WIDTH)
-                    + usize::from(x);
-
-                let unders = unsafe {
-                    wide::load!(
-                        frame_buffer.unscaled_buffer.as_ptr(),
-                        dest_index,
-                    )
-                };
+                    let unders = unsafe {
+                        $wide::load!(
+                            $unscaled_buffer.as_ptr(),
+                            dest_index,
+                        )
+                    };
+
+                    let gfx_colours = if fill == command::Fill::Yes {
+                        // Every pixel is the override colour, which is then
+                        // blended by its own alpha, like any other pixel.
+                        colour_override_value
+                    } else if transform.is_identity() {
+                        let base_src_i =
+                            (sprite_y + y_iter_count) * src_w
+                            + (sprite_x + x_iter_count);
+
+                        unsafe {
+                            $wide::load!(
+                                gfx.as_ptr(),
+                                base_src_i
+                            )
+                        }
+                    } else {
+                        // The source pixels are no longer next to each other,
+                        // so we gather them one at a time.
+                        let gather = |lane: usize| {
+                            // Lanes past the end of the rect are never written,
+                            // so any pixel from the sprite will do for them.
+                            let u = core::cmp::min(
+                                x_iter_count + lane,
+                                usize::from(rect_w.get() - 1),
+                            );
+                            let (s, t) = transform.source(
+                                u as unscaled::Inner,
+                                y_iter_count as unscaled::Inner,
+                                rect_w.get(),
+                                rect_h.get(),
+                            );
+
+                            gfx[
+                                (sprite_y + usize::from(t)) * src_w
+                                + sprite_x + usize::from(s)
+                            ] as i32
+                        };
+
+                        $wide::i32x4!(gather(0), gather(1), gather(2), gather(3))
+                    };
+
+                    let is_full_alpha_mask = $wide::eq_mask_u32!(
+                        $wide::right_shift_32!(
+                            gfx_colours,
+                            24
+                        ),
+                        wide_255_i32
+                    );
 
-                let base_src_i =
-                    (sprite_y + y_iter_count) * src_w
-                    + (sprite_x + x_iter_count);
+                    let do_override_mask = $wide::and_not!(
+                        is_full_alpha_mask,
+                        not_colour_override_mask
+                    );
 
-                let gfx_colours = unsafe {
-                    wide::load!(
-                        GFX.as_ptr(),
-                        base_src_i
-                    )
-                };
+                    let gfx_colours = $wide::pick_via_mask!(
+                        gfx_colours,
+                        colour_override_value,
+                        do_override_mask,
+                    );
+
+                    let should_write =
+                        $wide::lt_mask_32!(
+                            wide_xs,
+                            wide_x_end
+                        );
 
-                let is_full_alpha_mask = wide::eq_mask_u32!(
-                    wide::right_shift_32!(
+                    // Don't need to mask the shifted in zeroes.
+                    let gfx_colour_a = $wide::right_shift_32!(
                         gfx_colours,
                         24
-                    ),
-                    wide_255_i32
-                );
-
-                let do_override_mask = wide::and_not!(
-                    is_full_alpha_mask,
-                    not_colour_override_mask
-                );
-
-                let gfx_colours = wide::pick_via_mask!(
-                    gfx_colours,
-                    colour_override_value,
-                    do_override_mask,
-                );
-
-                let should_write =
-                    wide::lt_mask_32!(
-                        wide_xs,
-                        wide_x_end
                     );
 
-                // Don't need to mask the shifted in zeroes.
-                let gfx_colour_a = wide::right_shift_32!(
-                    gfx_colours,
-                    24
-                );
+                    let gfx_colour_r = $wide::and!(
+                        $wide::right_shift_32!(
+                            gfx_colours,
+                            16
+                        ),
+                        wide_255_i32
+                    );
 
-                let gfx_colour_r = wide::and!(
-                    wide::right_shift_32!(
-                        gfx_colours,
-                        16
-                    ),
-                    wide_255_i32
-                );
+                    let gfx_colour_g = $wide::and!(
+                        $wide::right_shift_32!(
+                            gfx_colours,
+                            8
+                        ),
+                        wide_255_i32
+                    );
 
-                let gfx_colour_g = wide::and!(
-                    wide::right_shift_32!(
+                    // Don't need to shift since it's already in the right spot
+                    let gfx_colour_b = $wide::and!(
                         gfx_colours,
-                        8
-                    ),
-                    wide_255_i32
-                );
-
-                // Don't need to shift since it's already in the right spot
-                let gfx_colour_b = wide::and!(
-                    gfx_colours,
-                    wide_255_i32
-                );
-
-                // Don't need to mask the shifted in zeroes.
-                let under_a = wide::right_shift_32!(
-                    unders,
-                    24
-                );
-
-                let under_r = wide::and!(
-                    wide::right_shift_32!(
-                        unders,
-                        16
-                    ),
-                    wide_255_i32
-                );
+                        wide_255_i32
+                    );
 
-                let under_g = wide::and!(
-                    wide::right_shift_32!(
+                    // Don't need to mask the shifted in zeroes.
+                    let under_a = $wide::right_shift_32!(
                         unders,
-                        8
-                    ),
-                    wide_255_i32
-                );
-
-                // Don't need to shift since it's already in the right spot
-                let under_b = wide::and!(
-                    unders,
-                    wide_255_i32
-                );
-
-                // gamma to linear
-                let mut a_g = wide::mul!(
-                    wide::u32_to_f32!(
-                        gfx_colour_a
-                    ),
-                    wide_inv_255_f32
-                );
-                a_g = wide::mul!(a_g, a_g);
-                let mut r_g = wide::mul!(
-                    wide::u32_to_f32!(
-                        gfx_colour_r
-                    ),
-                    wide_inv_255_f32
-                );
-                r_g = wide::mul!(r_g, r_g);
-                let mut g_g = wide::mul!(
-                    wide::u32_to_f32!(
-                        gfx_colour_g
-                    ),
-                    wide_inv_255_f32
-                );
-                g_g = wide::mul!(g_g, g_g);
-                let mut b_g = wide::mul!(
-                    wide::u32_to_f32!(
-                        gfx_colour_b
-                    ),
-                    wide_inv_255_f32
-                );
-                b_g = wide::mul!(b_g, b_g);
+                        24
+                    );
 
-                let mut a_u = wide::mul!(
-                    wide::u32_to_f32!(
-                        under_a
-                    ),
-                    wide_inv_255_f32
-                );
-                a_u = wide::mul!(a_u, a_u);
-                let mut r_u = wide::mul!(
-                    wide::u32_to_f32!(
-                        under_r
-                    ),
-                    wide_inv_255_f32
-                );
-                r_u = wide::mul!(r_u, r_u);
-                let mut g_u = wide::mul!(
-                    wide::u32_to_f32!(
-                        under_g
-                    ),
-                    wide_inv_255_f32
-                );
-                g_u = wide::mul!(g_u, g_u);
-                let mut b_u = wide::mul!(
-                    wide::u32_to_f32!(
-                        under_b
-                    ),
-                    wide_inv_255_f32
-                );
-                b_u = wide::mul!(b_u, b_u);
-
-                // perform alpha blending
-                let o_a = wide::add_f32!(
-                    a_g,
-                    wide::mul!(
-                        a_u,
-                        wide::sub!(wide_1_f32, a_g)
-                    )
-                );
+                    let under_r = $wide::and!(
+                        $wide::right_shift_32!(
+                            unders,
+                            16
+                        ),
+                        wide_255_i32
+                    );
 
-                let inv_o_a = wide::recip!(o_a);
+                    let under_g = $wide::and!(
+                        $wide::right_shift_32!(
+                            unders,
+                            8
+                        ),
+                        wide_255_i32
+                    );
 
-                let one_minus_a_g = wide::sub!(wide_1_f32, a_g);
+                    // Don't need to shift since it's already in the right spot
+                    let under_b = $wide::and!(
+                        unders,
+                        wide_255_i32
+                    );
+
+                    // gamma to linear
+                    let mut a_g = $wide::mul!(
+                        $wide::u32_to_f32!(
+                            gfx_colour_a
+                        ),
+                        wide_inv_255_f32
+                    );
+                    a_g = $wide::mul!(a_g, a_g);
+                    let mut r_g = $wide::mul!(
+                        $wide::u32_to_f32!(
+                            gfx_colour_r
+                        ),
+                        wide_inv_255_f32
+                    );
+                    r_g = $wide::mul!(r_g, r_g);
+                    let mut g_g = $wide::mul!(
+                        $wide::u32_to_f32!(
+                            gfx_colour_g
+                        ),
+                        wide_inv_255_f32
+                    );
+                    g_g = $wide::mul!(g_g, g_g);
+                    let mut b_g = $wide::mul!(
+                        $wide::u32_to_f32!(
+                            gfx_colour_b
+                        ),
+                        wide_inv_255_f32
+                    );
+                    b_g = $wide::mul!(b_g, b_g);
 
-                let o_r = wide::mul!(
-                    wide::add_f32!(
-                        wide::mul!(r_g, a_g),
-                        wide::mul!(
-                            r_u,
-                            one_minus_a_g
+                    let mut a_u = $wide::mul!(
+                        $wide::u32_to_f32!(
+                            under_a
+                        ),
+                        wide_inv_255_f32
+                    );
+                    a_u = $wide::mul!(a_u, a_u);
+                    let mut r_u = $wide::mul!(
+                        $wide::u32_to_f32!(
+                            under_r
+                        ),
+                        wide_inv_255_f32
+                    );
+                    r_u = $wide::mul!(r_u, r_u);
+                    let mut g_u = $wide::mul!(
+                        $wide::u32_to_f32!(
+                            under_g
+                        ),
+                        wide_inv_255_f32
+                    );
+                    g_u = $wide::mul!(g_u, g_u);
+                    let mut b_u = $wide::mul!(
+                        $wide::u32_to_f32!(
+                            under_b
+                        ),
+                        wide_inv_255_f32
+                    );
+                    b_u = $wide::mul!(b_u, b_u);
+
+                    // perform alpha blending
+                    let o_a = $wide::add_f32!(
+                        a_g,
+                        $wide::mul!(
+                            a_u,
+                            $wide::sub!(wide_1_f32, a_g)
                         )
-                    ),
-                    inv_o_a
-                );
-                let o_g = wide::mul!(
-                    wide::add_f32!(
-                        wide::mul!(g_g, a_g),
-                        wide::mul!(
-                            g_u,
-                            one_minus_a_g
+                    );
+
+                    let inv_o_a = $wide::recip!(o_a);
+
+                    let one_minus_a_g = $wide::sub!(wide_1_f32, a_g);
+
+                    let o_r = $wide::mul!(
+                        $wide::add_f32!(
+                            $wide::mul!(r_g, a_g),
+                            $wide::mul!(
+                                r_u,
+                                one_minus_a_g
+                            )
+                        ),
+                        inv_o_a
+                    );
+                    let o_g = $wide::mul!(
+                        $wide::add_f32!(
+                            $wide::mul!(g_g, a_g),
+                            $wide::mul!(
+                                g_u,
+                                one_minus_a_g
+                            )
+                        ),
+                        inv_o_a
+                    );
+                    let o_b = $wide::mul!(
+                        $wide::add_f32!(
+                            $wide::mul!(b_g, a_g),
+                            $wide::mul!(
+                                b_u,
+                                one_minus_a_g
+                            )
+                        ),
+                        inv_o_a
+                    );
+
+                    // linear to gamma
+                    let rendered_a = $wide::f32_to_u32!(
+                        $wide::mul!(
+                            wide_255_f32,
+                            $wide::sqrt!(o_a)
                         )
-                    ),
-                    inv_o_a
-                );
-                let o_b = wide::mul!(
-                    wide::add_f32!(
-                        wide::mul!(b_g, a_g),
-                        wide::mul!(
-                            b_u,
-                            one_minus_a_g
+                    );
+                    let rendered_r = $wide::f32_to_u32!(
+                        $wide::mul!(
+                            wide_255_f32,
+                            $wide::sqrt!(o_r)
                         )
-                    ),
-                    inv_o_a
-                );
-
-                // linear to gamma
-                let rendered_a = wide::f32_to_u32!(
-                    wide::mul!(
-                        wide_255_f32,
-                        wide::sqrt!(o_a)
-                    )
-                );
-                let rendered_r = wide::f32_to_u32!(
-                    wide::mul!(
-                        wide_255_f32,
-                        wide::sqrt!(o_r)
-                    )
-                );
-                let rendered_g = wide::f32_to_u32!(
-                    wide::mul!(
-                        wide_255_f32,
-                        wide::sqrt!(o_g)
-                    )
-                );
-                let rendered_b = wide::f32_to_u32!(
-                    wide::mul!(
-                        wide_255_f32,
-                        wide::sqrt!(o_b)
-                    )
-                );
+                    );
+                    let rendered_g = $wide::f32_to_u32!(
+                        $wide::mul!(
+                            wide_255_f32,
+                            $wide::sqrt!(o_g)
+                        )
+                    );
+                    let rendered_b = $wide::f32_to_u32!(
+                        $wide::mul!(
+                            wide_255_f32,
+                            $wide::sqrt!(o_b)
+                        )
+                    );
 
-                let rendered = wide::or!(
-                    wide::or!(
-                        wide::left_shift_32!(rendered_a, 24),
-                        wide::left_shift_32!(rendered_r, 16),
-                    ),
-                    wide::or!(
-                        wide::left_shift_32!(rendered_g, 8),
-                        rendered_b,
-                    )
-                );
-
-                let to_store = wide::pick_via_mask!(
-                    unders,
-                    rendered,
-                    should_write
-                );
-
-                // SAFETY: The pointers produced by the code generated by
-                // this macro is valid to write 128 bytes to.
-                unsafe {
-                    wide::store!(
-                        to_store,
-                        frame_buffer.unscaled_buffer.as_mut_ptr(),
-                        dest_index,
+                    let rendered = $wide::or!(
+                        $wide::or!(
+                            $wide::left_shift_32!(rendered_a, 24),
+                            $wide::left_shift_32!(rendered_r, 16),
+                        ),
+                        $wide::or!(
+                            $wide::left_shift_32!(rendered_g, 8),
+                            rendered_b,
+                        )
+                    );
+
+                    let to_store = $wide::pick_via_mask!(
+                        unders,
+                        rendered,
+                        should_write
                     );
+
+                    // SAFETY: The pointers produced by the code generated by
+                    // this macro is valid to write 128 bytes to.
+                    unsafe {
+                        $wide::store!(
+                            to_store,
+                            $unscaled_buffer.as_mut_ptr(),
+                            dest_index,
+                        );
+                    }
+
+                    x_iter_count += $wide::WIDTH as usize;
+                    x += $wide::WIDTH;
                 }
 
-                x_iter_count += wide::WIDTH as usize;
-                x += wide::WIDTH;
+                y_iter_count += 1;
             }
+        }
+    })
+}
+
+/// Which implementation of the per-pixel maths `render_via` uses. They should
+/// all produce the same pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Path {
+    /// The SIMD instructions for the target, where we have a version for it,
+    /// otherwise the same as `Scalar`.
+    Wide,
+    /// One lane at a time. Available on every target.
+    Scalar,
+}
+
+impl Path {
+    pub const ALL: [Self; 2] = [Self::Wide, Self::Scalar];
+}
+
+pub fn render(
+    frame_buffer: &mut FrameBuffer,
+    commands: &[Command],
+) -> NeedsRedraw {
+    render_via(frame_buffer, commands, Path::Wide)
+}
+
+pub fn render_via(
+    frame_buffer: &mut FrameBuffer,
+    commands: &[Command],
+    path: Path,
+) -> NeedsRedraw {
+    if frame_buffer.width < command::WIDTH
+    || frame_buffer.height < command::HEIGHT {
+        frame_buffer.width = command::WIDTH;
+        frame_buffer.height = command::HEIGHT;
+    }
+
+    let Some(Letterboxing {
+        multiplier,
+        left_bar_width,
+        right_bar_width,
+        top_bar_height,
+        bottom_bar_height,
+    }) = Letterboxing::new(frame_buffer.width, frame_buffer.height) else {
+        debug_assert!(false, "multiplier should not be 0");
+        return NeedsRedraw::No;
+    };
+
+    let outer_clip_rect = clip::Rect {
+        x: left_bar_width..(
+            frame_buffer.width - right_bar_width
+        ),
+        y: top_bar_height..(
+            frame_buffer.height - bottom_bar_height
+        ),
+    };
+
+    // This is cached software rendering based on:
+    // https://rxi.github.io/cached_software_rendering.html
+    // The commands are hashed into a hash per tile, and only the tiles whose
+    // hash changed since last frame are drawn and scaled up again.
+
+    frame_buffer.cells.reset_then_hash_commands(
+        commands,
+        (frame_buffer.width, frame_buffer.height),
+    );
 
-            y_iter_count += 1;
+    let expected_length = usize::from(frame_buffer.width)
+    * usize::from(frame_buffer.height);
+
+    let size = (frame_buffer.width, frame_buffer.height);
+    let resized = frame_buffer.buffer.len() != expected_length
+        || frame_buffer.rendered_size != Some(size);
+
+    frame_buffer.damage.clear();
+    frame_buffer.unscaled_damage.clear();
+
+    if resized {
+        // The bars need to be black, and the previous frame's pixels would be
+        // in the wrong places anyway.
+        frame_buffer.buffer.clear();
+        frame_buffer.buffer.resize(expected_length, 0);
+
+        frame_buffer.rendered_size = Some(size);
+
+        frame_buffer.unscaled_damage.push(clip::Rect {
+            x: 0..command::WIDTH,
+            y: 0..command::HEIGHT,
+        });
+    } else {
+        frame_buffer.cells.push_damaged_rects(&mut frame_buffer.unscaled_damage);
+    }
+
+    if frame_buffer.unscaled_damage.is_empty() {
+        frame_buffer.cells.swap();
+        return NeedsRedraw::No;
+    }
+
+    let palette = frame_buffer.theme.palette();
+    let background = recolour(colours::BLACK, &palette);
+    let gfx = frame_buffer.gfx.pixels();
+
+    for clip_rect in &frame_buffer.unscaled_damage {
+        for y in clip_rect.y.clone() {
+            let row_start = usize::from(y) * usize::from(command::WIDTH);
+            frame_buffer.unscaled_buffer[
+                row_start + usize::from(clip_rect.x.start)
+                ..row_start + usize::from(clip_rect.x.end)
+            ].fill(background);
         }
+
+        match path {
+            Path::Wide => draw_commands_via!(
+                wide,
+                frame_buffer.unscaled_buffer,
+                commands,
+                clip_rect,
+                gfx,
+                &palette,
+            ),
+            Path::Scalar => draw_commands_via!(
+                scalar,
+                frame_buffer.unscaled_buffer,
+                commands,
+                clip_rect,
+                gfx,
+                &palette,
+            ),
+        }
+
+        frame_buffer.damage.push(
+            scaled_rect(&outer_clip_rect, multiplier, clip_rect)
+        );
     }
 
+    scale_up(
+        &mut frame_buffer.buffer,
+        Band {
+            unscaled_buffer: &frame_buffer.unscaled_buffer,
+            unscaled_rects: &frame_buffer.unscaled_damage,
+            scaled_rects: &frame_buffer.damage,
+            buffer_width: usize::from(frame_buffer.width),
+            multiplier: usize::from(multiplier),
+        },
+        frame_buffer.threads,
+        #[cfg(not(target_arch = "wasm32"))]
+        &mut frame_buffer.workers,
+    );
+
+    if resized {
+        frame_buffer.damage.clear();
+        frame_buffer.damage.push(clip::Rect {
+            x: 0..frame_buffer.width,
+            y: 0..frame_buffer.height,
+        });
+    }
+
+    frame_buffer.cells.swap();
+
+    NeedsRedraw::Yes
+}
+
+/// The rect of the frame buffer that the given rect of the unscaled buffer
+/// ends up in, when scaled up by the multiplier, with the top left of the
+/// unscaled buffer at the top left of the outer clip rect.
+fn scaled_rect(
+    outer_clip_rect: &clip::Rect,
+    multiplier: u16,
+    rect: &clip::Rect,
+) -> clip::Rect {
+    clip::Rect {
+        x: outer_clip_rect.x.start + rect.x.start * multiplier
+            ..outer_clip_rect.x.start + rect.x.end * multiplier,
+        y: outer_clip_rect.y.start + rect.y.start * multiplier
+            ..outer_clip_rect.y.start + rect.y.end * multiplier,
+    }
+}
+
+/// Below this many changed pixels per thread, handing a band to another thread
+/// costs more than it saves.
+#[cfg(not(target_arch = "wasm32"))]
+const MIN_PIXELS_PER_THREAD: usize = 1 << 16;
+
+/// Copies each of the band's unscaled rects into the matching scaled rect of
+/// the frame buffer, with the given number of threads.
+fn scale_up(
+    buffer: &mut [ARGB],
+    band: Band<'_>,
+    threads: Threads,
+    #[cfg(not(target_arch = "wasm32"))]
+    workers: &mut workers::Workers,
+) {
+    match threads {
+        Threads::One => band.scale_up(buffer, 0),
+        #[cfg(not(target_arch = "wasm32"))]
+        Threads::Many(max) => {
+            let pixel_count: usize = band.scaled_rects.iter()
+                .map(|rect| usize::from(rect.width()) * usize::from(rect.height()))
+                .sum();
+            let thread_count = (pixel_count / MIN_PIXELS_PER_THREAD)
+                .clamp(1, max.get());
+
+            if thread_count == 1 {
+                band.scale_up(buffer, 0);
+                return
+            }
+
+            let row_count = buffer.len() / band.buffer_width;
+            let rows_per_band = row_count.div_ceil(thread_count);
+
+            workers.scale_up(band, buffer, rows_per_band);
+        },
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod workers {
+    //! Threads that scale up bands of rows for `scale_up`, started once and
+    //! then kept waiting for the next frame.
+    use super::{ARGB, Band};
+    use std::sync::mpsc::{channel, Receiver, Sender};
+
+    /// A band of rows, and what to fill them in from. The borrows in `band`,
+    /// and the rows, really only last until `Workers::scale_up` returns.
+    struct Job {
+        band: Band<'static>,
+        rows: *mut ARGB,
+        len: usize,
+        first_y: usize,
+    }
+
+    // SAFETY: Each job's rows do not overlap any other job's, and nothing
+    // else touches them until the job is done.
+    unsafe impl Send for Job {}
+
+    struct Worker {
+        jobs: Sender<Job>,
+        done: Receiver<()>,
+    }
+
+    impl Worker {
+        fn spawn() -> Self {
+            let (jobs, job_receiver) = channel::<Job>();
+            let (done_sender, done) = channel();
+
+            std::thread::spawn(move || {
+                while let Ok(Job { band, rows, len, first_y }) = job_receiver.recv() {
+                    // SAFETY: `Workers::scale_up` does not return until this
+                    // job is done, so the rows are still there, and nothing
+                    // else is using them.
+                    let rows = unsafe { core::slice::from_raw_parts_mut(rows, len) };
+
+                    band.scale_up(rows, first_y);
+
+                    if done_sender.send(()).is_err() {
+                        break
+                    }
+                }
+            });
+
+            Self { jobs, done }
+        }
+    }
+
+    #[derive(Default)]
+    pub struct Workers(Vec<Worker>);
+
+    impl Workers {
+        #[cfg(test)]
+        pub fn count(&self) -> usize {
+            self.0.len()
+        }
+
+        /// Scales up `buffer`, `rows_per_band` rows at a time. This thread
+        /// does the first band, and the workers do the rest.
+        pub fn scale_up(&mut self, band: Band<'_>, buffer: &mut [ARGB], rows_per_band: usize) {
+            let band_len = rows_per_band * band.buffer_width;
+            let band_count = buffer.len().div_ceil(band_len);
+
+            while self.0.len() < band_count - 1 {
+                self.0.push(Worker::spawn());
+            }
+
+            // SAFETY: Only the lifetime changes. `Waiting` makes sure that
+            // the workers are done with `band` before this function returns,
+            // even if this thread panics.
+            let band_static = unsafe {
+                core::mem::transmute::<Band<'_>, Band<'static>>(band)
+            };
+
+            let mut bands = buffer.chunks_mut(band_len).enumerate();
+            let first = bands.next();
+
+            let mut waiting = Waiting { workers: &[], waited: false };
+
+            for ((i, rows), (sent, worker)) in bands.zip(self.0.iter().enumerate()) {
+                let job = Job {
+                    band: band_static,
+                    rows: rows.as_mut_ptr(),
+                    len: rows.len(),
+                    first_y: i * rows_per_band,
+                };
+
+                // A worker only stops after panicking, which `Waiting::wait`
+                // has already reported.
+                if worker.jobs.send(job).is_err() {
+                    panic!("a scale-up worker is not running");
+                }
+                waiting.workers = &self.0[..=sent];
+            }
+
+            if let Some((_, rows)) = first {
+                band.scale_up(rows, 0);
+            }
+
+            assert!(waiting.wait(), "a scale-up worker panicked");
+        }
+    }
+
+    /// Waits for the workers that were sent a job, when dropped if not before.
+    struct Waiting<'w> {
+        workers: &'w [Worker],
+        waited: bool,
+    }
+
+    impl Waiting<'_> {
+        /// Returns false if any of the workers panicked instead of finishing.
+        fn wait(&mut self) -> bool {
+            self.waited = true;
+
+            // Every worker is waited for, even after one is found to have
+            // panicked.
+            self.workers.iter()
+                .filter(|worker| worker.done.recv().is_err())
+                .count() == 0
+        }
+    }
+
+    impl Drop for Waiting<'_> {
+        fn drop(&mut self) {
+            if !self.waited {
+                self.wait();
+            }
+        }
+    }
+}
+
+/// What is needed to scale up any band of rows of the frame buffer.
+#[derive(Clone, Copy)]
+struct Band<'a> {
+    unscaled_buffer: &'a [ARGB; command::LENGTH],
+    unscaled_rects: &'a [clip::Rect],
+    scaled_rects: &'a [clip::Rect],
+    buffer_width: usize,
+    multiplier: usize,
+}
+
+impl Band<'_> {
+    /// Writes the parts of the scaled rects that are in `rows`, which holds the
+    /// rows of the frame buffer starting at `first_y`.
     // TODO If we need some more render speed, this could be made wide as well.
-    let mut src_i = 0;
-    let mut src_i_row_start;
-    let mut y_remaining = multiplier;
-    for y in outer_clip_rect.y {
-        let mut x_remaining = multiplier;
-
-        src_i_row_start = src_i;
-
-        for x in outer_clip_rect.x.clone() {
-            let d_i = usize::from(y)
-            * usize::from(frame_buffer.width)
-            + usize::from(x);
-
-            if d_i < frame_buffer.buffer.len() {
-                frame_buffer.buffer[d_i] =
-                    frame_buffer.unscaled_buffer[src_i];
+    fn scale_up(self, rows: &mut [ARGB], first_y: usize) {
+        let buffer_width = self.buffer_width;
+        let multiplier = self.multiplier;
+        let band_ys = first_y..first_y + rows.len() / buffer_width;
+
+        for (rect, scaled) in self.unscaled_rects.iter().zip(self.scaled_rects) {
+            let scaled_x = usize::from(scaled.x.start)..usize::from(scaled.x.end);
+
+            for (i, y) in rect.y.clone().enumerate() {
+                let scaled_y_start = usize::from(scaled.y.start) + i * multiplier;
+                let dest_ys = core::cmp::max(scaled_y_start, band_ys.start)
+                    ..core::cmp::min(scaled_y_start + multiplier, band_ys.end);
+
+                if dest_ys.is_empty() {
+                    continue
+                }
+
+                let src_row_start = usize::from(y) * usize::from(command::WIDTH);
+                let src_row = &self.unscaled_buffer[
+                    src_row_start + usize::from(rect.x.start)
+                    ..src_row_start + usize::from(rect.x.end)
+                ];
+
+                let first_row_start = (dest_ys.start - band_ys.start) * buffer_width;
+                let first_row = first_row_start + scaled_x.start
+                    ..first_row_start + scaled_x.end;
+
+                for (dest, &colour) in rows[first_row.clone()]
+                    .chunks_exact_mut(multiplier)
+                    .zip(src_row) {
+                    dest.fill(colour);
+                }
+
+                for dest_y in dest_ys.start + 1..dest_ys.end {
+                    let dest_row_start = (dest_y - band_ys.start) * buffer_width;
+                    rows.copy_within(
+                        first_row.clone(),
+                        dest_row_start + scaled_x.start,
+                    );
+                }
             }
+        }
+    }
+}
 
-            x_remaining -= 1;
-            if x_remaining == 0 {
-                src_i += 1;
-                x_remaining = multiplier;
+#[cfg(test)]
+mod frame_buffer_to_screen_returns_the_expected_result {
+    use super::*;
+
+    const W: clip::W = command::WIDTH;
+    const H: clip::H = command::HEIGHT;
+
+    fn xy(x: unscaled::Inner, y: unscaled::Inner) -> Option<unscaled::XY> {
+        Some(unscaled::XY { x: unscaled::X(x), y: unscaled::Y(y) })
+    }
+
+    #[test]
+    fn at_the_native_size() {
+        assert_eq!(frame_buffer_to_screen((W, H), (0., 0.)), xy(0, 0));
+        assert_eq!(frame_buffer_to_screen((W, H), (10.5, 20.)), xy(10, 20));
+        assert_eq!(frame_buffer_to_screen((W, H), (f64::from(W), 0.)), None);
+    }
+
+    #[test]
+    fn when_letterboxed_on_the_sides() {
+        // Twice the size, with 11 extra pixels: 6 on the left, 5 on the right.
+        let size = (W * 2 + 11, H * 2);
+
+        assert_eq!(frame_buffer_to_screen(size, (5., 0.)), None);
+        assert_eq!(frame_buffer_to_screen(size, (6., 0.)), xy(0, 0));
+        assert_eq!(frame_buffer_to_screen(size, (7., 3.)), xy(0, 1));
+        assert_eq!(frame_buffer_to_screen(size, (8., 3.)), xy(1, 1));
+        assert_eq!(
+            frame_buffer_to_screen(size, (f64::from(W * 2 + 5), 0.)),
+            xy(W - 1, 0)
+        );
+        assert_eq!(frame_buffer_to_screen(size, (f64::from(W * 2 + 6), 0.)), None);
+    }
+
+    #[test]
+    fn when_letterboxed_on_the_top_and_bottom() {
+        let size = (W * 3, H * 3 + 2);
+
+        assert_eq!(frame_buffer_to_screen(size, (0., 0.)), None);
+        assert_eq!(frame_buffer_to_screen(size, (0., 1.)), xy(0, 0));
+        assert_eq!(frame_buffer_to_screen(size, (3., 4.)), xy(1, 1));
+        assert_eq!(frame_buffer_to_screen(size, (-1., 1.)), None);
+    }
+}
+
+#[cfg(test)]
+mod partial_redraws_match_full_redraws {
+    use super::*;
+
+    const SIZES: [(clip::W, clip::H); 2] = [
+        (command::WIDTH, command::HEIGHT),
+        (command::WIDTH * 2 + 3, command::HEIGHT * 2 + 1),
+    ];
+
+    fn command(
+        (x, y): (unscaled::Inner, unscaled::Inner),
+        (w, h): (unscaled::Inner, unscaled::Inner),
+        (sprite_x, sprite_y): (sprite::Inner, sprite::Inner),
+        colour_override: ARGB,
+    ) -> Command {
+        Command {
+            rect: command::Rect::from_unscaled(unscaled::Rect {
+                x: unscaled::X(x),
+                y: unscaled::Y(y),
+                w: unscaled::W(w),
+                h: unscaled::H(h),
+            }),
+            sprite_xy: sprite::XY { x: sprite::X(sprite_x), y: sprite::Y(sprite_y) },
+            colour_override,
+            transform: <_>::default(),
+            fill: <_>::default(),
+        }
+    }
+
+    fn fresh(commands: &[Command], path: Path, size: (clip::W, clip::H)) -> Vec<ARGB> {
+        let mut frame_buffer = FrameBuffer::from_size(size);
+        render_via(&mut frame_buffer, commands, path);
+        frame_buffer.buffer
+    }
+
+    fn assert_each_frame_matches_a_fresh_render(frames: &[Vec<Command>]) {
+        for path in Path::ALL {
+            for size in SIZES {
+                let mut frame_buffer = FrameBuffer::from_size(size);
+
+                for (i, commands) in frames.iter().enumerate() {
+                    render_via(&mut frame_buffer, commands, path);
+
+                    assert!(
+                        frame_buffer.buffer == fresh(commands, path, size),
+                        "frame {i} differed from a fresh render via {path:?} at {size:?}"
+                    );
+                }
             }
         }
+    }
+
+    fn background() -> Command {
+        command((0, 0), (command::WIDTH, command::HEIGHT), (0, 0), 0xFF_20_40_20)
+    }
+
+    #[test]
+    fn when_a_command_moves() {
+        let frames: Vec<Vec<Command>> = (0..8)
+            .map(|i| vec![
+                background(),
+                command((10 + i * 13, 7 + i * 5), (37, 51), (0, 0), 0),
+            ])
+            .collect();
+
+        assert_each_frame_matches_a_fresh_render(&frames);
+    }
+
+    #[test]
+    fn when_a_command_changes_colour() {
+        let frames = vec![
+            vec![background(), command((100, 100), (20, 20), (0, 0), 0xFF_FF_00_00)],
+            vec![background(), command((100, 100), (20, 20), (0, 0), 0xFF_00_FF_00)],
+        ];
+
+        assert_each_frame_matches_a_fresh_render(&frames);
+    }
+
+    #[test]
+    fn when_overlapping_commands_are_reordered() {
+        let under = command((50, 50), (40, 40), (0, 0), 0xFF_00_00_FF);
+        let over = command((70, 70), (40, 40), (0, 0), 0xFF_FF_FF_00);
+
+        let frames = vec![
+            vec![background(), under, over],
+            vec![background(), over, under],
+            vec![background(), under, over],
+        ];
+
+        assert_each_frame_matches_a_fresh_render(&frames);
+    }
+
+    #[test]
+    fn when_commands_are_removed() {
+        let frames = vec![
+            vec![
+                command((3, 5), (200, 100), (0, 0), 0xFF_80_80_80),
+                command((400, 300), (80, 20), (0, 0), 0),
+            ],
+            vec![command((3, 5), (200, 100), (0, 0), 0xFF_80_80_80)],
+            vec![],
+        ];
+
+        assert_each_frame_matches_a_fresh_render(&frames);
+    }
+
+    #[test]
+    fn when_the_size_changes() {
+        let commands = [background(), command((33, 44), (55, 66), (0, 0), 0)];
+
+        for path in Path::ALL {
+            let mut frame_buffer = FrameBuffer::from_size(SIZES[1]);
+            render_via(&mut frame_buffer, &commands, path);
+
+            (frame_buffer.width, frame_buffer.height) = SIZES[0];
+            render_via(&mut frame_buffer, &commands, path);
+
+            assert!(frame_buffer.buffer == fresh(&commands, path, SIZES[0]));
+            assert_eq!(
+                frame_buffer.damage,
+                [clip::Rect { x: 0..SIZES[0].0, y: 0..SIZES[0].1 }]
+            );
+        }
+    }
 
-        src_i = src_i_row_start;
+    #[test]
+    fn and_only_the_changed_tiles_are_damaged() {
+        let still = command((300, 200), (30, 30), (0, 0), 0xFF_FF_00_FF);
+        let before = [background(), still, command((20, 20), (8, 8), (0, 0), 0)];
+        let after = [background(), still, command((24, 20), (8, 8), (0, 0), 0)];
+
+        for size in SIZES {
+            let mut frame_buffer = FrameBuffer::from_size(size);
+
+            assert!(render_via(&mut frame_buffer, &before, Path::Wide) == NeedsRedraw::Yes);
+            assert!(render_via(&mut frame_buffer, &before, Path::Wide) == NeedsRedraw::No);
+            assert!(frame_buffer.damage.is_empty());
+
+            let previous = frame_buffer.buffer.clone();
+
+            assert!(render_via(&mut frame_buffer, &after, Path::Wide) == NeedsRedraw::Yes);
+
+            // Both versions of the moved command fit in the second tile of the
+            // second row.
+            assert_eq!(
+                frame_buffer.unscaled_damage,
+                [clip::Rect { x: 16..32, y: 16..32 }]
+            );
+
+            let width = usize::from(frame_buffer.width);
+            for (i, (&now, &was)) in frame_buffer.buffer.iter()
+                .zip(previous.iter())
+                .enumerate() {
+                let (x, y) = ((i % width) as clip::X, (i / width) as clip::Y);
+                let damaged = frame_buffer.damage.iter()
+                    .any(|rect| rect.x.contains(&x) && rect.y.contains(&y));
+
+                if !damaged {
+                    assert_eq!(now, was, "({x}, {y}) changed outside the damage");
+                }
+            }
+        }
+    }
+}
 
-        y_remaining -= 1;
-        if y_remaining == 0 {
-            y_remaining = multiplier;
-            let src_w = usize::from(command::WIDTH);
-            src_i += src_w;
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod many_threads_render_the_same_bytes_as_one {
+    use super::*;
+    use core::num::NonZeroUsize;
+
+    const SIZES: [(clip::W, clip::H); 3] = [
+        (command::WIDTH, command::HEIGHT),
+        (command::WIDTH * 2 + 17, command::HEIGHT * 2 + 1),
+        (command::WIDTH * 8, command::HEIGHT * 7 + 3),
+    ];
+
+    fn command(
+        (x, y): (unscaled::Inner, unscaled::Inner),
+        (w, h): (unscaled::Inner, unscaled::Inner),
+        colour_override: ARGB,
+    ) -> Command {
+        Command {
+            rect: command::Rect::from_unscaled(unscaled::Rect {
+                x: unscaled::X(x),
+                y: unscaled::Y(y),
+                w: unscaled::W(w),
+                h: unscaled::H(h),
+            }),
+            sprite_xy: <_>::default(),
+            colour_override,
+            transform: <_>::default(),
+            fill: <_>::default(),
         }
     }
 
-    frame_buffer.cells.swap();
+    fn frames() -> Vec<Vec<Command>> {
+        (0..6)
+            .map(|i| vec![
+                command((0, 0), (command::WIDTH, command::HEIGHT), 0xFF_20_40_20),
+                command((5 + i * 41, 3 + i * 29), (63, 95), 0xFF_C0_30_30),
+                command((470 - i * 37, 10), (9, 300 - i * 40), 0xFF_30_30_C0),
+            ])
+            .collect()
+    }
 
-    NeedsRedraw::Yes
+    #[test]
+    fn for_each_thread_count() {
+        for size in SIZES {
+            let mut one = FrameBuffer::from_size(size);
+
+            let mut many: Vec<FrameBuffer> = [2, 3, 4, 7]
+                .into_iter()
+                .map(|n| {
+                    let mut frame_buffer = FrameBuffer::from_size(size);
+                    frame_buffer.threads = Threads::Many(
+                        NonZeroUsize::new(n).unwrap()
+                    );
+                    frame_buffer
+                })
+                .collect();
+
+            for (i, commands) in frames().iter().enumerate() {
+                render(&mut one, commands);
+
+                for frame_buffer in many.iter_mut() {
+                    render(frame_buffer, commands);
+
+                    assert!(
+                        frame_buffer.buffer == one.buffer,
+                        "frame {i} differed with {:?} at {size:?}",
+                        frame_buffer.threads,
+                    );
+                    assert_eq!(frame_buffer.damage, one.damage);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn with_the_workers_started_only_once() {
+        let mut frame_buffer = FrameBuffer::from_size(SIZES[2]);
+        frame_buffer.threads = Threads::Many(NonZeroUsize::new(4).unwrap());
+
+        let frames = frames();
+        render(&mut frame_buffer, &frames[0]);
+
+        // The first frame is a full redraw, which is plenty to use them all.
+        assert_eq!(frame_buffer.workers.count(), 3);
+
+        for commands in &frames[1..] {
+            render(&mut frame_buffer, commands);
+
+            assert_eq!(frame_buffer.workers.count(), 3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod turned_sprites_are_drawn_from_the_same_pixels {
+    use super::*;
+    use command::Transform;
+
+    // Not a multiple of the lane count, so the partly filled lanes are
+    // covered, and not square, so mixing up the dimensions would show.
+    const W: unscaled::Inner = 30;
+    const H: unscaled::Inner = 21;
+    const SPRITE_XY: sprite::XY = sprite::XY { x: sprite::X(20), y: sprite::Y(25) };
+
+    const ALL: [Transform; 8] = [
+        Transform::IDENTITY,
+        Transform::FLIP_X,
+        Transform::FLIP_Y,
+        Transform::ROTATE_180,
+        Transform::TRANSPOSE,
+        Transform::ROTATE_CW,
+        Transform::ROTATE_CCW,
+        Transform::TRANSPOSE.union(Transform::ROTATE_180),
+    ];
+
+    fn command(xy: (unscaled::Inner, unscaled::Inner), transform: Transform) -> Command {
+        let (w, h) = if transform.transposes() { (H, W) } else { (W, H) };
+
+        Command {
+            rect: command::Rect::from_unscaled(unscaled::Rect {
+                x: unscaled::X(xy.0),
+                y: unscaled::Y(xy.1),
+                w: unscaled::W(w),
+                h: unscaled::H(h),
+            }),
+            sprite_xy: SPRITE_XY,
+            colour_override: 0,
+            transform,
+            fill: <_>::default(),
+        }
+    }
+
+    #[test]
+    fn for_each_transform_via_each_path() {
+        let background = Command {
+            rect: command::Rect::from_unscaled(unscaled::Rect {
+                x: unscaled::X(0),
+                y: unscaled::Y(0),
+                w: unscaled::W(command::WIDTH),
+                h: unscaled::H(command::HEIGHT),
+            }),
+            sprite_xy: <_>::default(),
+            colour_override: 0xFF_20_40_20,
+            transform: <_>::default(),
+            fill: <_>::default(),
+        };
+        let upright_xy = (7, 9);
+        let turned_xy = (101, 53);
+
+        for path in Path::ALL {
+            for transform in ALL {
+                let commands = [
+                    background,
+                    command(upright_xy, Transform::IDENTITY),
+                    command(turned_xy, transform),
+                ];
+
+                let mut frame_buffer = FrameBuffer::from_size((command::WIDTH, command::HEIGHT));
+                render_via(&mut frame_buffer, &commands, path);
+
+                let pixel = |x: unscaled::Inner, y: unscaled::Inner| {
+                    frame_buffer.unscaled_buffer[
+                        usize::from(y) * usize::from(command::WIDTH) + usize::from(x)
+                    ]
+                };
+
+                let (w, h) = if transform.transposes() { (H, W) } else { (W, H) };
+                for v in 0..h {
+                    for u in 0..w {
+                        let (s, t) = transform.source(u, v, w, h);
+
+                        assert_eq!(
+                            pixel(turned_xy.0 + u, turned_xy.1 + v),
+                            pixel(upright_xy.0 + s, upright_xy.1 + t),
+                            "{transform:?} via {path:?} at {u}, {v}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn and_a_change_of_transform_alone_is_redrawn() {
+        for path in Path::ALL {
+            let mut frame_buffer = FrameBuffer::from_size((command::WIDTH, command::HEIGHT));
+
+            let upright = [command((40, 40), Transform::IDENTITY)];
+            let flipped = [command((40, 40), Transform::FLIP_X)];
+
+            render_via(&mut frame_buffer, &upright, path);
+            assert!(render_via(&mut frame_buffer, &flipped, path) == NeedsRedraw::Yes);
+            assert!(!frame_buffer.damage.is_empty());
+
+            let mut fresh = FrameBuffer::from_size((command::WIDTH, command::HEIGHT));
+            render_via(&mut fresh, &flipped, path);
+            assert!(frame_buffer.buffer == fresh.buffer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod fills_blend_over_what_is_under_them {
+    use super::*;
+
+    const UNDER: ARGB = 0xFF_20_40_80;
+
+    fn command(colour_override: ARGB, fill: command::Fill) -> Command {
+        Command {
+            rect: command::Rect::from_unscaled(unscaled::Rect {
+                x: unscaled::X(10),
+                y: unscaled::Y(10),
+                w: unscaled::W(21),
+                h: unscaled::H(13),
+            }),
+            // A part of the sprite sheet with transparent pixels in it, which
+            // a fill should not care about.
+            sprite_xy: sprite::XY { x: sprite::X(0), y: sprite::Y(0) },
+            colour_override,
+            transform: <_>::default(),
+            fill,
+        }
+    }
+
+    fn rendered(colour: ARGB, path: Path) -> Vec<ARGB> {
+        let commands = [
+            Command {
+                colour_override: UNDER,
+                ..command(UNDER, command::Fill::Yes)
+            },
+            command(colour, command::Fill::Yes),
+        ];
+
+        let mut frame_buffer = FrameBuffer::from_size((command::WIDTH, command::HEIGHT));
+        render_via(&mut frame_buffer, &commands, path);
+
+        let mut pixels = Vec::new();
+        for y in 10..23 {
+            for x in 10..31 {
+                pixels.push(frame_buffer.unscaled_buffer[y * usize::from(command::WIDTH) + x]);
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn by_the_alpha_of_their_colour() {
+        for path in Path::ALL {
+            let opaque = rendered(colours::RED, path);
+            assert!(opaque.iter().all(|&p| p == colours::RED), "{path:?}");
+
+            let clear = rendered(colours::RED & 0x00_FF_FF_FF, path);
+            assert!(clear.iter().all(|&p| p == UNDER), "{path:?}");
+
+            let half = rendered(0x80_00_00_00 | (colours::RED & 0x00_FF_FF_FF), path);
+            let first = half[0];
+            assert!(half.iter().all(|&p| p == first), "{path:?}");
+            assert_ne!(first, UNDER);
+            assert_ne!(first, colours::RED);
+            // Red moves towards the fill, and blue away from what was under.
+            assert!((first >> 16) & 0xFF > (UNDER >> 16) & 0xFF, "{first:08X}");
+            assert!(first & 0xFF < UNDER & 0xFF, "{first:08X}");
+        }
+    }
+
+    #[test]
+    fn and_are_redrawn_when_only_the_fill_changes() {
+        for path in Path::ALL {
+            let mut frame_buffer = FrameBuffer::from_size((command::WIDTH, command::HEIGHT));
+
+            let sprite = [command(0, command::Fill::No)];
+            let fill = [command(0, command::Fill::Yes)];
+
+            render_via(&mut frame_buffer, &sprite, path);
+            assert!(render_via(&mut frame_buffer, &fill, path) == NeedsRedraw::Yes);
+            assert!(!frame_buffer.damage.is_empty());
+        }
+    }
+}
+
+#[cfg(test)]
+mod recolour_swaps_palette_colours {
+    use super::*;
+
+    #[test]
+    fn for_each_theme() {
+        for theme in Theme::ALL {
+            let palette = theme.palette();
+
+            for (&from, &to) in PALETTE.iter().zip(palette.iter()) {
+                assert_eq!(recolour(from, &palette), to, "{theme:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn keeping_the_alpha_and_the_offset_of_nearby_colours() {
+        let palette = Theme::Deuteranopia.palette();
+        assert_eq!(palette[4], 0xFF_CC_79_A7);
+
+        // Off of `PURPLE` by 1 in red and blue, and partly transparent.
+        let shade = 0x80_52_33_55;
+
+        assert_eq!(recolour(shade, &palette), 0x80_CB_79_A8);
+    }
+
+    #[test]
+    fn but_not_other_colours() {
+        let palette = Theme::DarkTable.palette();
+
+        assert_eq!(recolour(0, &palette), 0);
+        assert_eq!(recolour(0xFF_FF_FF_FF, &palette), 0xFF_FF_FF_FF);
+    }
+
+    #[test]
+    fn and_not_at_all_in_the_default_theme() {
+        let palette = Theme::Default.palette();
+
+        assert!(GFX.iter().all(|&argb| recolour(argb, &palette) == argb));
+    }
+
+    #[test]
+    fn and_changing_the_theme_redraws_everything() {
+        let commands = [Command {
+            rect: command::Rect::from_unscaled(unscaled::Rect {
+                x: unscaled::X(10),
+                y: unscaled::Y(10),
+                w: unscaled::W(20),
+                h: unscaled::H(20),
+            }),
+            sprite_xy: <_>::default(),
+            colour_override: colours::RED,
+            transform: <_>::default(),
+            fill: <_>::default(),
+        }];
+
+        let mut frame_buffer = FrameBuffer::from_size((command::WIDTH, command::HEIGHT));
+        render(&mut frame_buffer, &commands);
+
+        frame_buffer.set_theme(Theme::Protanopia);
+        assert!(render(&mut frame_buffer, &commands) == NeedsRedraw::Yes);
+        assert_eq!(
+            frame_buffer.damage,
+            [clip::Rect { x: 0..command::WIDTH, y: 0..command::HEIGHT }]
+        );
+
+        let mut fresh = FrameBuffer::from_size((command::WIDTH, command::HEIGHT));
+        fresh.set_theme(Theme::Protanopia);
+        render(&mut fresh, &commands);
+
+        assert!(frame_buffer.buffer == fresh.buffer);
+    }
 }
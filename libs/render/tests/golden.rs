@@ -0,0 +1,335 @@
+//! Renders known command lists through each `render::Path`, at several output
+//! sizes, and compares the results to the PNGs in `tests/goldens`. Every path
+//! is compared to the same golden, so the paths also have to agree with each
+//! other.
+//!
+//! To create or update the goldens, after a change that is meant to change
+//! the output, run:
+//!
+//! ```text
+//! UPDATE_GOLDENS=1 cargo test -p render --test golden
+//! ```
+
+use gfx::{ChevronDir, Commands, Highlighted, NineSlice};
+use models::{NetPredicate, Rank, Suit, Zinger, fish_card, zinger_card};
+use platform_types::{ARGB, PALETTE, Theme, WHITE, command::{self, Transform}, unscaled::{self, X, Y, W, H, XY}};
+use render::{clip, FrameBuffer, Path};
+
+use std::path::PathBuf;
+
+const SIZES: [(clip::W, clip::H); 3] = [
+    (command::WIDTH, command::HEIGHT),
+    (command::WIDTH * 2, command::HEIGHT * 2),
+    // Not a multiple of the screen size, so there are bars around the screen.
+    (command::WIDTH * 2 + 37, command::HEIGHT * 2 + 21),
+];
+
+fn goldens_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("goldens")
+}
+
+fn updating() -> bool {
+    std::env::var_os("UPDATE_GOLDENS").is_some()
+}
+
+fn write_png(path: &std::path::Path, (width, height): (clip::W, clip::H), pixels: &[ARGB]) {
+    let file = std::fs::File::create(path)
+        .unwrap_or_else(|e| panic!("Could not create {}: {e}", path.display()));
+
+    let mut encoder = png::Encoder::new(
+        std::io::BufWriter::new(file),
+        u32::from(width),
+        u32::from(height),
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(png::Compression::Best);
+
+    let mut bytes = Vec::with_capacity(pixels.len() * 4);
+    for argb in pixels {
+        let [a, r, g, b] = argb.to_be_bytes();
+        bytes.extend_from_slice(&[r, g, b, a]);
+    }
+
+    encoder.write_header()
+        .and_then(|mut writer| writer.write_image_data(&bytes))
+        .unwrap_or_else(|e| panic!("Could not write {}: {e}", path.display()));
+}
+
+fn read_png(path: &std::path::Path) -> Result<((clip::W, clip::H), Vec<ARGB>), String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+
+    let mut reader = png::Decoder::new(file).read_info().map_err(|e| e.to_string())?;
+
+    let mut bytes = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut bytes).map_err(|e| e.to_string())?;
+
+    if (info.color_type, info.bit_depth) != (png::ColorType::Rgba, png::BitDepth::Eight) {
+        return Err(format!("expected 8 bit RGBA, got {:?}", (info.color_type, info.bit_depth)));
+    }
+
+    let pixels = bytes[..info.buffer_size()]
+        .chunks_exact(4)
+        .map(|rgba| ARGB::from_be_bytes([rgba[3], rgba[0], rgba[1], rgba[2]]))
+        .collect();
+
+    Ok(((info.width as clip::W, info.height as clip::H), pixels))
+}
+
+fn check(name: &str, commands: &Commands) {
+    check_themed(name, commands, Theme::Default, &SIZES);
+}
+
+fn check_themed(
+    name: &str,
+    commands: &Commands,
+    theme: Theme,
+    sizes: &[(clip::W, clip::H)],
+) {
+    for &size in sizes {
+        let file_name = format!("{name}_{}x{}.png", size.0, size.1);
+        let golden_path = goldens_dir().join(&file_name);
+
+        let rendered: Vec<(Path, Vec<ARGB>)> = Path::ALL.into_iter()
+            .map(|path| {
+                let mut frame_buffer = FrameBuffer::from_size(size);
+                frame_buffer.set_theme(theme);
+                render::render_via(&mut frame_buffer, commands.slice(), path);
+                (path, frame_buffer.buffer)
+            })
+            .collect();
+
+        if updating() {
+            // Any path that disagrees with the first one will fail below.
+            write_png(&golden_path, size, &rendered[0].1);
+        }
+
+        let (golden_size, golden) = read_png(&golden_path).unwrap_or_else(|e| panic!(
+            "Could not read {}: {e}\nRun with UPDATE_GOLDENS=1 to create it.",
+            golden_path.display(),
+        ));
+        assert_eq!(golden_size, size, "{file_name}");
+
+        for (path, pixels) in rendered {
+            if pixels == golden {
+                continue
+            }
+
+            let actual_path = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+                .join(format!("{path:?}_{file_name}"));
+            write_png(&actual_path, size, &pixels);
+
+            let differing = pixels.iter().zip(&golden).filter(|(a, g)| a != g).count();
+
+            panic!(
+                "{differing} pixels of {file_name}, as rendered via {path:?}, differ from the golden. What was rendered is in {}",
+                actual_path.display(),
+            );
+        }
+    }
+}
+
+fn cards_scene() -> Commands {
+    let mut commands = Commands::default();
+
+    let step = W(command::WIDTH / 6);
+    let mut xy = XY { x: X(4), y: Y(4) };
+    for (i, suit) in Suit::ALL.into_iter().enumerate() {
+        commands.draw_card(
+            fish_card(Rank::ALL[i * 2], suit),
+            xy,
+        );
+        xy += step;
+    }
+
+    let mut xy = XY { x: X(4), y: Y(command::HEIGHT / 2) };
+    commands.draw_card(zinger_card(Zinger::DivineIntervention), xy);
+    xy += step;
+    commands.draw_card(zinger_card(Zinger::TheNet), xy);
+    xy += step;
+    // Includes a partly transparent shadow.
+    commands.draw_card_back(xy);
+    xy += step;
+    commands.draw_net_predicate_card(NetPredicate::Suit(Suit::Purple), xy);
+    xy += step;
+    commands.draw_selectrum(xy);
+
+    commands
+}
+
+#[test]
+fn cards() {
+    check("cards", &cards_scene());
+}
+
+#[test]
+fn themed_cards() {
+    let mut commands = cards_scene();
+
+    let mut y = Y(command::HEIGHT - 60);
+    for colour in PALETTE {
+        commands.print_line(b"Text", XY { x: X(4), y }, colour);
+        y += H(7);
+    }
+
+    for theme in Theme::ALL {
+        if theme == Theme::Default {
+            continue
+        }
+
+        check_themed(
+            &format!("themed_cards_{}", theme.name()),
+            &commands,
+            theme,
+            // One size is enough, since the scaling is covered by the others.
+            &SIZES[..1],
+        );
+    }
+}
+
+#[test]
+fn turned_cards() {
+    let mut commands = Commands::default();
+
+    let card = fish_card(Rank::Barnacle, Suit::Blue);
+
+    let mut xy = XY { x: X(4), y: Y(4) };
+    for transform in [
+        Transform::ROTATE_CW,
+        Transform::ROTATE_CCW,
+        Transform::TRANSPOSE,
+    ] {
+        commands.draw_card_turned(card, xy, transform);
+        xy += W(109);
+    }
+    commands.draw_card_turned(card, xy, Transform::ROTATE_180);
+
+    let mut xy = XY { x: X(4), y: Y(120) };
+    commands.draw_card_back_turned(xy, Transform::ROTATE_CW);
+    xy += W(109);
+    commands.draw_card_turned(zinger_card(Zinger::TheNet), xy, Transform::FLIP_Y);
+
+    // Partly off the right and bottom edges, so the flipped sprites have to
+    // be lined up with the parts of them that are still visible.
+    commands.draw_card_turned(
+        card,
+        XY { x: X(command::WIDTH - 50), y: Y(120) },
+        Transform::ROTATE_CCW,
+    );
+    commands.draw_card_turned(
+        card,
+        XY { x: X(200), y: Y(command::HEIGHT - 40) },
+        Transform::ROTATE_CW,
+    );
+    commands.draw_card_turned(
+        card,
+        XY { x: X(320), y: Y(command::HEIGHT - 60) },
+        Transform::ROTATE_180,
+    );
+
+    check("turned_cards", &commands);
+}
+
+#[test]
+fn dimmed_cards() {
+    let mut commands = cards_scene();
+
+    commands.dim_screen();
+
+    let card_rect = unscaled::Rect {
+        x: X(200),
+        y: Y(100),
+        w: platform_types::CARD_WIDTH,
+        h: platform_types::CARD_HEIGHT,
+    };
+    commands.draw_glow(card_rect, gfx::GLOW_COLOUR);
+    commands.draw_card(fish_card(Rank::Eel, Suit::Red), card_rect.xy());
+
+    // Partly off the screen, and translucent white over the dimmed cards.
+    commands.fill(
+        unscaled::Rect {
+            x: X(command::WIDTH - 40),
+            y: Y(command::HEIGHT - 30),
+            w: W(80),
+            h: H(80),
+        },
+        0x60_00_00_00 | (WHITE & 0x00_FF_FF_FF),
+    );
+
+    check("dimmed_cards", &commands);
+}
+
+#[test]
+fn nine_slices() {
+    let mut commands = Commands::default();
+
+    let mut x = X(4);
+    for (i, nine_slice) in [
+        NineSlice::Window,
+        NineSlice::Button,
+        NineSlice::ButtonHot,
+        NineSlice::ButtonPressed,
+    ].into_iter().enumerate() {
+        let i = i as unscaled::Inner;
+        let w = W(40 + i * 17);
+        commands.draw_nine_slice(
+            nine_slice,
+            unscaled::Rect { x, y: Y(4), w, h: H(30 + i * 23) },
+        );
+        x += w + W(4);
+    }
+
+    let mut x = X(4);
+    for dir in [ChevronDir::Up, ChevronDir::Down] {
+        for highlighted in [Highlighted::No, Highlighted::Yes] {
+            commands.draw_chevron(XY { x, y: Y(200) }, dir, highlighted);
+            x += gfx::CHEVRON_W + W(4);
+        }
+    }
+
+    check("nine_slices", &commands);
+}
+
+#[test]
+fn text() {
+    let mut commands = Commands::default();
+
+    commands.print_line(
+        b" !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        XY { x: X(4), y: Y(4) },
+        WHITE,
+    );
+    commands.print_line(
+        b"[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~",
+        XY { x: X(4), y: Y(20) },
+        WHITE,
+    );
+    commands.print_centered(
+        b"Centered\nacross\nseveral lines",
+        unscaled::Rect { x: X(0), y: Y(40), w: command::WIDTH_W, h: H(100) },
+        WHITE,
+    );
+
+    check("text", &commands);
+}
+
+#[test]
+fn colour_overrides() {
+    let mut commands = Commands::default();
+
+    let mut y = Y(4);
+    for colour in PALETTE {
+        commands.print_line(b"The quick brown fish", XY { x: X(4), y }, colour);
+        y += H(14);
+    }
+
+    // Overlapping, so the later ones are drawn over the earlier ones.
+    let mut xy = XY { x: X(240), y: Y(4) };
+    for colour in PALETTE {
+        commands.print_line(b"Over", xy, colour);
+        xy += W(3);
+        xy += H(2);
+    }
+
+    check("colour_overrides", &commands);
+}
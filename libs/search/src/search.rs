@@ -0,0 +1,874 @@
+//! The "Hard+" Cpu strategy: a determinized Monte Carlo search.
+//!
+//! Each iteration samples an arrangement of the cards we cannot see that is
+//! consistent with our `Memory`, then plays out the rest of the game from there,
+//! starting with one of the asks we could make right now. The ask that leads to
+//! the best basket points on average is the one we go with.
+//!
+//! The zingers are played in the playouts too, with a simple policy: whoever
+//! holds one plays it as soon as it would help them, and whoever holds the
+//! Divine Intervention uses it on the first zinger someone else plays. The
+//! Glass Bottom Boat only tells a player something, and everyone in a playout
+//! already knows where everything is, so it just takes up room in a hand.
+use memories::Memory;
+use models::{Card, Hand, HandId, Rank, Suit, DECK_SIZE, FISH_COUNT, fish_card, get_rank, zingers};
+use xs::Xs;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+/// A set of cards, with one bit per card.
+type CardSet = u128;
+
+const FISH: CardSet = (1 << FISH_COUNT) - 1;
+const ALL: CardSet = (1 << DECK_SIZE) - 1;
+
+const HAND_COUNT: usize = HandId::COUNT as usize;
+
+/// How many asks a playout can go on for before we stop and count the points.
+const MAX_PLAYOUT_ASKS: u16 = 256;
+
+/// How much to favour trying out asks we haven't looked at much, over the ones
+/// that look best so far. In basket points.
+const EXPLORATION: f32 = 10.0;
+
+fn bit(card: Card) -> CardSet {
+    1 << card
+}
+
+fn rank_set(rank: Rank) -> CardSet {
+    let mut output = 0;
+    for suit in Suit::ALL {
+        output |= bit(fish_card(rank, suit));
+    }
+    output
+}
+
+fn set_of(hand: &Hand) -> CardSet {
+    let mut output = 0;
+    for card in hand.iter() {
+        output |= bit(card);
+    }
+    output
+}
+
+fn cards_of(set: CardSet) -> impl Iterator<Item = Card> {
+    (0..DECK_SIZE).filter(move |&card| set & bit(card) != 0)
+}
+
+fn nth_card_of(set: CardSet, n: u32) -> Option<Card> {
+    cards_of(set).nth(n as usize)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ask {
+    pub rank: Rank,
+    pub suit: Suit,
+    pub target: HandId,
+}
+
+/// How much work to do before deciding on a move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Budget {
+    /// A fixed amount of playouts. With this, the choice only depends on the seed.
+    Iterations { per_move: u32, per_frame: u32 },
+    /// Keep going until the time runs out. Not available on wasm, since we don't
+    /// have `Instant` there.
+    #[cfg(not(target_arch = "wasm32"))]
+    Time { per_move: Duration, per_frame: Duration },
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        // At 60 FPS, this is about half a second of thinking per move.
+        Self::Iterations { per_move: 2048, per_frame: 64 }
+    }
+}
+
+/// What a Cpu player can see, when it is their turn to ask.
+#[derive(Clone, Debug)]
+pub struct Position {
+    my_id: HandId,
+    my_hand: CardSet,
+    hand_lens: [u8; HAND_COUNT],
+    deck_len: u8,
+    /// Every card that is not in our hand, and not somewhere everyone can see.
+    unseen: CardSet,
+}
+
+impl Position {
+    /// `visible` should have every pile of cards that everyone can see, like the
+    /// baskets and the discard pile. `hand_lens` is indexed by `HandId`.
+    pub fn new(
+        my_id: HandId,
+        my_hand: &Hand,
+        hand_lens: [u8; HAND_COUNT],
+        deck_len: u8,
+        visible: &[&Hand],
+    ) -> Self {
+        let my_hand = set_of(my_hand);
+
+        let mut seen = my_hand;
+        for hand in visible {
+            seen |= set_of(hand);
+        }
+
+        Self {
+            my_id,
+            my_hand,
+            hand_lens,
+            deck_len,
+            unseen: ALL & !seen,
+        }
+    }
+
+    fn options(&self) -> Vec<Ask> {
+        let mut output = Vec::new();
+
+        for rank in Rank::ALL {
+            if self.my_hand & rank_set(rank) == 0 {
+                continue
+            }
+
+            for suit in Suit::ALL {
+                // This skips the ones we have and the ones that are gone.
+                if self.unseen & bit(fish_card(rank, suit)) == 0 {
+                    continue
+                }
+
+                for target in self.my_id.besides() {
+                    if self.hand_lens[target as usize] > 0 {
+                        output.push(Ask { rank, suit, target });
+                    }
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Deal out the unseen cards in a way that matches what we know.
+    fn determinize(&self, rng: &mut Xs, memory: &Memory) -> Sim {
+        let mut hands = [0; HAND_COUNT];
+        hands[self.my_id as usize] = self.my_hand;
+
+        let mut room = self.hand_lens;
+        room[self.my_id as usize] = 0;
+
+        let mut pool: Vec<Card> = cards_of(self.unseen).collect();
+        xs::shuffle(rng, &mut pool);
+
+        // Place the cards the evidence points to first, most convincing first.
+        // Since the pool was shuffled, and the sort is stable, ties are broken
+        // randomly.
+        let mut scored: Vec<(u32, HandId, Card)> = Vec::with_capacity(pool.len());
+        for &card in pool.iter() {
+            let mut best = (0, self.my_id);
+            for target in self.my_id.besides() {
+                let score = memory.evidence_score(card, target);
+                if score > best.0 {
+                    best = (score, target);
+                }
+            }
+            scored.push((best.0, best.1, card));
+        }
+        scored.sort_by_key(|&(score, _, _)| core::cmp::Reverse(score));
+
+        let mut rest = Vec::with_capacity(scored.len());
+        for (score, id, card) in scored {
+            if score > 0 && room[id as usize] > 0 {
+                hands[id as usize] |= bit(card);
+                room[id as usize] -= 1;
+            } else {
+                rest.push(card);
+            }
+        }
+
+        // Everything else goes wherever there is room, and the leftovers go in
+        // the deck. We won't put a card in a hand that we know doesn't have it,
+        // unless a few tries at dealing that way all get stuck. Then we deal
+        // without that evidence, rather than not at all.
+        let (dealt, deck) = (0..DEAL_TRIES)
+            .find_map(|_| deal_rest(rng, &rest, room, self.deck_len, |card, id| {
+                !memory.is_known_not_to_be_in(card, id)
+            }))
+            .or_else(|| deal_rest(rng, &rest, room, self.deck_len, |_, _| true))
+            .unwrap_or_default();
+
+        for id in HandId::ALL {
+            hands[id as usize] |= dealt[id as usize];
+        }
+
+        Sim {
+            hands,
+            deck,
+            points: [0; HAND_COUNT],
+        }
+    }
+}
+
+/// How many times to try dealing out the unseen cards in a way that matches
+/// every did-not-have, before giving up on those.
+const DEAL_TRIES: u8 = 8;
+
+/// Deals `rest` into the `room` left in each hand, only where `allowed`, with
+/// the leftovers going in the deck. Returns `None` if that ran into a card
+/// that had nowhere to go.
+fn deal_rest(
+    rng: &mut Xs,
+    rest: &[Card],
+    mut room: [u8; HAND_COUNT],
+    deck_len: u8,
+    allowed: impl Fn(Card, HandId) -> bool,
+) -> Option<([CardSet; HAND_COUNT], Vec<Card>)> {
+    let mut deck_room = usize::from(deck_len);
+
+    // The cards that can go in the fewest places go first, so the room they
+    // need doesn't get used up by cards that could go anywhere.
+    let mut rest = rest.to_vec();
+    rest.sort_by_key(|&card| {
+        HandId::ALL.iter().filter(|&&id| allowed(card, id)).count()
+    });
+
+    let mut hands = [0; HAND_COUNT];
+    let mut deck = Vec::with_capacity(deck_room);
+    for card in rest {
+        let mut total = deck_room as u32;
+        for id in HandId::ALL {
+            if allowed(card, id) {
+                total += u32::from(room[id as usize]);
+            }
+        }
+
+        if total == 0 {
+            return None
+        }
+
+        // Picking each spot with equal chance, like dealing into shuffled
+        // slots would.
+        let mut pick = xs::range(rng, 0..total);
+        let mut placed = false;
+        for id in HandId::ALL {
+            if !allowed(card, id) {
+                continue
+            }
+
+            let r = u32::from(room[id as usize]);
+            if pick < r {
+                hands[id as usize] |= bit(card);
+                room[id as usize] -= 1;
+                placed = true;
+                break
+            }
+            pick -= r;
+        }
+
+        if !placed {
+            deck.push(card);
+            deck_room -= 1;
+        }
+    }
+
+    xs::shuffle(rng, &mut deck);
+
+    Some((hands, deck))
+}
+
+/// The rules, as the playouts see them. This is public so the game can check
+/// that it agrees with them.
+#[derive(Clone, Debug)]
+pub struct Sim {
+    hands: [CardSet; HAND_COUNT],
+    deck: Vec<Card>,
+    points: [u32; HAND_COUNT],
+}
+
+impl Sim {
+    /// `hands` is indexed by `HandId`. The deck is drawn from the end, the same
+    /// way `Hand::draw` does it.
+    pub fn new(hands: [&Hand; HAND_COUNT], deck: &Hand) -> Self {
+        Self {
+            hands: hands.map(set_of),
+            deck: deck.iter().collect(),
+            points: [0; HAND_COUNT],
+        }
+    }
+
+    /// The cards in `id`'s hand, in order.
+    pub fn hand(&self, id: HandId) -> Vec<Card> {
+        cards_of(self.hands[id as usize]).collect()
+    }
+
+    pub fn deck(&self) -> &[Card] {
+        &self.deck
+    }
+
+    /// The basket points `id` has made since the `Sim` was made.
+    pub fn points(&self, id: HandId) -> u32 {
+        self.points[id as usize]
+    }
+
+    fn has(&self, id: HandId, card: Card) -> bool {
+        self.hands[id as usize] & bit(card) != 0
+    }
+
+    fn give(&mut self, id: HandId, card: Card) {
+        let hand = &mut self.hands[id as usize];
+        *hand |= bit(card);
+
+        if let Some(rank) = get_rank(card) {
+            let set = rank_set(rank);
+            if *hand & set == set {
+                *hand &= !set;
+                self.points[id as usize] += u32::from(rank.points());
+            }
+        }
+
+        self.try_dead_scuba_diver(id);
+    }
+
+    /// Takes `zinger` out of `id`'s hand, and returns whether it goes ahead,
+    /// which it does unless someone else cancels it with the Divine
+    /// Intervention.
+    fn play(&mut self, id: HandId, zinger: Card) -> bool {
+        self.hands[id as usize] &= !bit(zinger);
+
+        for other in id.besides() {
+            if self.has(other, zingers::DIVINE_INTERVENTION) {
+                self.hands[other as usize] &= !bit(zingers::DIVINE_INTERVENTION);
+                return false
+            }
+        }
+
+        true
+    }
+
+    fn try_dead_scuba_diver(&mut self, id: HandId) {
+        if !self.has(id, zingers::DEAD_SCUBA_DIVER) {
+            return
+        }
+
+        for rank in Rank::ALL {
+            let set = rank_set(rank);
+            if (self.hands[id as usize] & set).count_ones() != Suit::COUNT as u32 - 1 {
+                continue
+            }
+
+            if self.play(id, zingers::DEAD_SCUBA_DIVER) {
+                self.hands[id as usize] &= !set;
+                self.points[id as usize] += u32::from(rank.points());
+            }
+            return
+        }
+    }
+
+    /// Returns whether `target` stopped an ask for a card they have.
+    fn said_no_fishing(&mut self, target: HandId) -> bool {
+        self.has(target, zingers::NO_FISHING)
+        && self.play(target, zingers::NO_FISHING)
+    }
+
+    /// Returns whether `asker` gets to go again.
+    pub fn ask(&mut self, asker: HandId, ask: Ask) -> bool {
+        let card = fish_card(ask.rank, ask.suit);
+
+        if self.has(ask.target, card) && !self.said_no_fishing(ask.target) {
+            self.hands[ask.target as usize] &= !bit(card);
+            self.give(asker, card);
+            true
+        } else if self.has(asker, zingers::TWO_FISTED_FISHERMAN)
+        && self.play(asker, zingers::TWO_FISTED_FISHERMAN) {
+            true
+        } else if let Some(drew) = self.deck.pop() {
+            self.give(asker, drew);
+            drew == card
+        } else {
+            false
+        }
+    }
+
+    /// Plays the zingers that go at the start of a turn, if `current` has
+    /// them. Returns whether that used up the turn.
+    fn start_turn(&mut self, rng: &mut Xs, current: HandId) -> bool {
+        if self.has(current, zingers::THE_GAME_WARDEN) {
+            if let Some(target) = self.random_target(rng, current) {
+                if self.play(current, zingers::THE_GAME_WARDEN) {
+                    let hand = self.hands[target as usize];
+                    if let Some(card) = nth_card_of(hand, xs::range(rng, 0..hand.count_ones())) {
+                        self.hands[target as usize] &= !bit(card);
+                        let i = xs::range(rng, 0..self.deck.len() as u32 + 1);
+                        self.deck.insert(i as usize, card);
+                    }
+                }
+            }
+        }
+
+        // The Net and The Lure both take the whole turn, whether they get
+        // anything or not.
+        for zinger in [zingers::THE_NET, zingers::THE_LURE] {
+            if !self.has(current, zinger) {
+                continue
+            }
+
+            let Some(ask) = self.random_ask(rng, current) else {
+                continue
+            };
+
+            if self.play(current, zinger) {
+                let wanted = if zinger == zingers::THE_NET {
+                    rank_set(ask.rank)
+                } else {
+                    bit(fish_card(ask.rank, ask.suit))
+                };
+                let matching = self.hands[ask.target as usize] & wanted;
+
+                if matching != 0 && !self.said_no_fishing(ask.target) {
+                    if let Some(card) = nth_card_of(matching, xs::range(rng, 0..matching.count_ones())) {
+                        self.hands[ask.target as usize] &= !bit(card);
+                        self.give(current, card);
+                    }
+                } else if let Some(drew) = self.deck.pop() {
+                    self.give(current, drew);
+                }
+            }
+
+            return true
+        }
+
+        false
+    }
+
+    fn random_target(&self, rng: &mut Xs, asker: HandId) -> Option<HandId> {
+        let mut targets = [asker; HAND_COUNT - 1];
+        let mut len = 0;
+        for target in asker.besides() {
+            if self.hands[target as usize] != 0 {
+                targets[len] = target;
+                len += 1;
+            }
+        }
+
+        if len == 0 {
+            return None
+        }
+
+        Some(targets[xs::range(rng, 0..len as u32) as usize])
+    }
+
+    fn random_ask(&self, rng: &mut Xs, asker: HandId) -> Option<Ask> {
+        let hand = self.hands[asker as usize];
+        let fish = hand & FISH;
+
+        let card = nth_card_of(fish, xs::range(rng, 0..fish.count_ones().max(1)))?;
+        let rank = get_rank(card)?;
+
+        let missing = rank_set(rank) & !hand;
+        let asked = nth_card_of(missing, xs::range(rng, 0..missing.count_ones().max(1)))?;
+
+        Some(Ask {
+            rank,
+            suit: models::get_suit(asked)?,
+            target: self.random_target(rng, asker)?,
+        })
+    }
+
+    fn playout(&mut self, rng: &mut Xs, my_id: HandId, first: Ask) {
+        let mut current = my_id;
+        let mut go_again = self.ask(current, first);
+
+        for _ in 0..MAX_PLAYOUT_ASKS {
+            if !go_again {
+                current = current.next_looping();
+
+                if self.start_turn(rng, current) {
+                    continue
+                }
+            }
+
+            match self.random_ask(rng, current) {
+                Some(ask) => {
+                    go_again = self.ask(current, ask);
+                },
+                None => {
+                    // Dead in the water.
+                    match self.deck.pop() {
+                        Some(card) => self.give(current, card),
+                        None if self.hands.iter().all(|hand| hand & FISH == 0) => break,
+                        None => {},
+                    }
+                    go_again = false;
+                },
+            }
+        }
+    }
+
+    /// How many more points we got than the others did, on average.
+    fn reward(&self, my_id: HandId) -> f32 {
+        let mine = self.points[my_id as usize] as f32;
+        let mut others = 0.0;
+        for id in my_id.besides() {
+            others += self.points[id as usize] as f32;
+        }
+
+        mine - others / (HAND_COUNT - 1) as f32
+    }
+}
+
+/// A search that is in progress, which can be spread across multiple frames.
+#[derive(Clone, Debug)]
+pub struct Search {
+    position: Position,
+    memory: Memory,
+    budget: Budget,
+    options: Vec<Ask>,
+    visits: Vec<u32>,
+    totals: Vec<f32>,
+    iterations: u32,
+    #[cfg(not(target_arch = "wasm32"))]
+    elapsed: Duration,
+}
+
+impl Search {
+    /// Returns `None` if there is nothing to ask for.
+    pub fn new(position: Position, memory: &Memory, budget: Budget) -> Option<Self> {
+        let options = position.options();
+
+        if options.is_empty() {
+            return None
+        }
+
+        Some(Self {
+            position,
+            memory: memory.clone(),
+            budget,
+            visits: vec![0; options.len()],
+            totals: vec![0.0; options.len()],
+            options,
+            iterations: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            elapsed: Duration::default(),
+        })
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Does up to a frame's worth of searching. Returns the ask to make once the
+    /// budget for the whole move has been used up.
+    pub fn step(&mut self, rng: &mut Xs) -> Option<Ask> {
+        if self.options.len() == 1 {
+            return Some(self.options[0])
+        }
+
+        match self.budget {
+            Budget::Iterations { per_move, per_frame } => {
+                let stop_at = core::cmp::min(
+                    per_move,
+                    self.iterations.saturating_add(core::cmp::max(per_frame, 1)),
+                );
+
+                while self.iterations < stop_at {
+                    self.iterate(rng);
+                }
+
+                if self.iterations >= per_move {
+                    return Some(self.best())
+                }
+            },
+            #[cfg(not(target_arch = "wasm32"))]
+            Budget::Time { per_move, per_frame } => {
+                let start = Instant::now();
+                loop {
+                    self.iterate(rng);
+
+                    let spent = start.elapsed();
+                    if spent >= per_frame || self.elapsed + spent >= per_move {
+                        self.elapsed += spent;
+                        break
+                    }
+                }
+
+                if self.elapsed >= per_move {
+                    return Some(self.best())
+                }
+            },
+        }
+
+        None
+    }
+
+    /// The ask that looks best so far.
+    pub fn best(&self) -> Ask {
+        let mut best_i = 0;
+        for i in 1..self.options.len() {
+            if (self.visits[i], self.mean(i)) > (self.visits[best_i], self.mean(best_i)) {
+                best_i = i;
+            }
+        }
+
+        self.options[best_i]
+    }
+
+    fn mean(&self, i: usize) -> f32 {
+        if self.visits[i] == 0 {
+            0.0
+        } else {
+            self.totals[i] / self.visits[i] as f32
+        }
+    }
+
+    fn iterate(&mut self, rng: &mut Xs) {
+        let i = self.select();
+
+        let mut sim = self.position.determinize(rng, &self.memory);
+        sim.playout(rng, self.position.my_id, self.options[i]);
+
+        self.visits[i] += 1;
+        self.totals[i] += sim.reward(self.position.my_id);
+        self.iterations += 1;
+    }
+
+    /// Uses UCB1 to pick which ask to try next.
+    fn select(&self) -> usize {
+        if let Some(i) = self.visits.iter().position(|&visits| visits == 0) {
+            return i
+        }
+
+        let ln_n = (self.iterations as f32).ln();
+
+        let mut best = (0, f32::MIN);
+        for i in 0..self.options.len() {
+            let score = self.mean(i)
+                + EXPLORATION * (ln_n / self.visits[i] as f32).sqrt();
+            if score > best.1 {
+                best = (i, score);
+            }
+        }
+
+        best.0
+    }
+}
+
+#[cfg(test)]
+mod search_returns_the_expected_result {
+    use super::*;
+    use models::{NetPredicate, Predicate};
+
+    const R: Rank = Rank::Whale;
+
+    fn almost_basket_position(memory: &mut Memory) -> Position {
+        let my_id = HandId::Cpu1;
+        let other_id = HandId::Cpu2;
+
+        let mut my_hand = Hand::default();
+        for suit in [Suit::Green, Suit::Blue, Suit::Yellow, Suit::Purple] {
+            my_hand.push(fish_card(R, suit));
+        }
+        my_hand.push(fish_card(Rank::Crab, Suit::Red));
+
+        memory.known(other_id, fish_card(R, Suit::Red));
+
+        Position::new(
+            my_id,
+            &my_hand,
+            [8, my_hand.len(), 8, 8],
+            DECK_SIZE - (8 * 3 + my_hand.len()),
+            &[],
+        )
+    }
+
+    #[test]
+    fn on_this_known_last_card_example() {
+        let mut memory = Memory::default();
+        let position = almost_basket_position(&mut memory);
+
+        let mut search = Search::new(
+            position,
+            &memory,
+            Budget::Iterations { per_move: 2048, per_frame: 2048 },
+        ).expect("there should be something to ask for");
+
+        let mut rng = xs::from_seed([42; 16]);
+
+        assert_eq!(
+            search.step(&mut rng),
+            Some(Ask { rank: R, suit: Suit::Red, target: HandId::Cpu2 })
+        );
+    }
+
+    #[test]
+    fn step_spreads_the_iterations_across_frames() {
+        let mut memory = Memory::default();
+        let position = almost_basket_position(&mut memory);
+
+        let mut search = Search::new(
+            position,
+            &memory,
+            Budget::Iterations { per_move: 100, per_frame: 30 },
+        ).expect("there should be something to ask for");
+
+        let mut rng = xs::from_seed([42; 16]);
+
+        for expected in [30, 60, 90] {
+            assert_eq!(search.step(&mut rng), None);
+            assert_eq!(search.iterations(), expected);
+        }
+
+        assert!(search.step(&mut rng).is_some());
+        assert_eq!(search.iterations(), 100);
+    }
+
+    #[test]
+    fn determinize_places_known_cards_and_fills_the_hands() {
+        let mut memory = Memory::default();
+        let position = almost_basket_position(&mut memory);
+
+        let mut rng = xs::from_seed([42; 16]);
+
+        for _ in 0..16 {
+            let sim = position.determinize(&mut rng, &memory);
+
+            assert_ne!(sim.hands[HandId::Cpu2 as usize] & bit(fish_card(R, Suit::Red)), 0);
+            for id in HandId::ALL {
+                assert_eq!(
+                    sim.hands[id as usize].count_ones(),
+                    u32::from(position.hand_lens[id as usize])
+                );
+            }
+            assert_eq!(sim.deck.len(), usize::from(position.deck_len));
+        }
+    }
+
+    #[test]
+    fn determinize_respects_did_not_have() {
+        let mut memory = Memory::default();
+        let position = almost_basket_position(&mut memory);
+
+        let crabs = rank_set(Rank::Crab);
+        memory.did_not_have(
+            HandId::Player,
+            Predicate::Net(NetPredicate::Rank(Rank::Crab)),
+        );
+
+        let mut rng = xs::from_seed([42; 16]);
+
+        for _ in 0..16 {
+            let sim = position.determinize(&mut rng, &memory);
+
+            assert_eq!(sim.hands[HandId::Player as usize] & crabs, 0);
+            assert_eq!(
+                sim.hands[HandId::Player as usize].count_ones(),
+                u32::from(position.hand_lens[HandId::Player as usize])
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod playouts_use_zingers {
+    use super::*;
+
+    const R: Rank = Rank::Whale;
+
+    fn sim_with(hands: [&[Card]; HAND_COUNT], deck: &[Card]) -> Sim {
+        let mut sets = [0; HAND_COUNT];
+        for (set, hand) in sets.iter_mut().zip(hands) {
+            for &card in hand {
+                *set |= bit(card);
+            }
+        }
+
+        Sim {
+            hands: sets,
+            deck: deck.to_vec(),
+            points: [0; HAND_COUNT],
+        }
+    }
+
+    #[test]
+    fn no_fishing_stops_an_ask() {
+        let wanted = fish_card(R, Suit::Red);
+        let drawn = fish_card(Rank::Crab, Suit::Red);
+        let mut sim = sim_with(
+            [&[fish_card(R, Suit::Blue)], &[wanted, zingers::NO_FISHING], &[], &[]],
+            &[drawn],
+        );
+
+        assert!(!sim.ask(HandId::Player, Ask { rank: R, suit: Suit::Red, target: HandId::Cpu1 }));
+        assert!(sim.has(HandId::Cpu1, wanted));
+        assert!(!sim.has(HandId::Cpu1, zingers::NO_FISHING));
+        assert!(sim.has(HandId::Player, drawn));
+    }
+
+    #[test]
+    fn divine_intervention_cancels_no_fishing() {
+        let wanted = fish_card(R, Suit::Red);
+        let mut sim = sim_with(
+            [
+                &[fish_card(R, Suit::Blue), zingers::DIVINE_INTERVENTION],
+                &[wanted, zingers::NO_FISHING],
+                &[],
+                &[],
+            ],
+            &[],
+        );
+
+        assert!(sim.ask(HandId::Player, Ask { rank: R, suit: Suit::Red, target: HandId::Cpu1 }));
+        assert!(sim.has(HandId::Player, wanted));
+        assert!(!sim.has(HandId::Player, zingers::DIVINE_INTERVENTION));
+        assert!(!sim.has(HandId::Cpu1, zingers::NO_FISHING));
+    }
+
+    #[test]
+    fn two_fisted_fisherman_gives_another_turn() {
+        let drawn = fish_card(Rank::Crab, Suit::Red);
+        let mut sim = sim_with(
+            [&[fish_card(R, Suit::Blue), zingers::TWO_FISTED_FISHERMAN], &[drawn], &[], &[]],
+            &[drawn],
+        );
+
+        assert!(sim.ask(HandId::Player, Ask { rank: R, suit: Suit::Red, target: HandId::Cpu1 }));
+        assert!(!sim.has(HandId::Player, zingers::TWO_FISTED_FISHERMAN));
+        assert_eq!(sim.deck.len(), 1);
+    }
+
+    #[test]
+    fn dead_scuba_diver_makes_a_basket_out_of_four() {
+        let mut sim = sim_with(
+            [
+                &[
+                    fish_card(R, Suit::Green),
+                    fish_card(R, Suit::Blue),
+                    fish_card(R, Suit::Yellow),
+                    zingers::DEAD_SCUBA_DIVER,
+                ],
+                &[],
+                &[],
+                &[],
+            ],
+            &[],
+        );
+
+        sim.give(HandId::Player, fish_card(R, Suit::Purple));
+
+        assert_eq!(sim.hands[HandId::Player as usize], 0);
+        assert_eq!(sim.points[HandId::Player as usize], u32::from(R.points()));
+    }
+
+    #[test]
+    fn the_net_takes_the_turn() {
+        let held = fish_card(R, Suit::Blue);
+        let netted = fish_card(R, Suit::Red);
+        let mut sim = sim_with(
+            [&[held, zingers::THE_NET], &[netted], &[], &[]],
+            &[],
+        );
+
+        let mut rng = xs::from_seed([42; 16]);
+
+        assert!(sim.start_turn(&mut rng, HandId::Player));
+        assert!(sim.has(HandId::Player, netted));
+        assert!(!sim.has(HandId::Player, zingers::THE_NET));
+    }
+}
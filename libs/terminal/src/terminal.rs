@@ -0,0 +1,1021 @@
+//! Plays a `State` in a terminal, drawing the table with coloured text and
+//! box-drawing characters, and reading keys from stdin. This allows playing
+//! over SSH, and scripting whole games, on machines without a display.
+//!
+//! The commands only say which part of the sprite sheet to draw, so each one is
+//! drawn as whatever `gfx::sprite_of` says it is, onto a grid of cells with one
+//! cell for each place the game can print a character.
+
+use gfx::{ChevronDir, Highlighted, NineSlice, Sprite, Third, CHAR_ADVANCE_H, CHAR_ADVANCE_W};
+use models::Suit;
+use platform_types::{command, Button, Command, State, StateParams, ARGB, PALETTE, CARD_WIDTH, CARD_HEIGHT};
+use std::io::{self, IsTerminal, Read, Write};
+use std::ops::Range;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+pub type Palette = [ARGB; PALETTE.len()];
+
+const CELL_W: usize = CHAR_ADVANCE_W.get().get() as usize;
+const CELL_H: usize = CHAR_ADVANCE_H.get().get() as usize;
+
+pub const COLUMNS: usize = command::WIDTH as usize / CELL_W;
+pub const ROWS: usize = command::HEIGHT as usize / CELL_H;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub character: char,
+    pub foreground: ARGB,
+    pub background: ARGB,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            character: ' ',
+            foreground: platform_types::WHITE,
+            background: platform_types::BLACK,
+        }
+    }
+}
+
+/// Which characters to draw the outline of a box with.
+struct Lines {
+    horizontal: char,
+    vertical: char,
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+}
+
+impl Lines {
+    const LIGHT: Self = Self {
+        horizontal: '─',
+        vertical: '│',
+        top_left: '┌',
+        top_right: '┐',
+        bottom_left: '└',
+        bottom_right: '┘',
+    };
+
+    const HEAVY: Self = Self {
+        horizontal: '━',
+        vertical: '┃',
+        top_left: '┏',
+        top_right: '┓',
+        bottom_left: '┗',
+        bottom_right: '┛',
+    };
+
+    const ROUNDED: Self = Self {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        ..Self::LIGHT
+    };
+
+    const DOUBLE: Self = Self {
+        horizontal: '═',
+        vertical: '║',
+        top_left: '╔',
+        top_right: '╗',
+        bottom_left: '╚',
+        bottom_right: '╝',
+    };
+
+    fn piece(&self, x: Third, y: Third) -> char {
+        use Third::*;
+
+        match (x, y) {
+            (Middle, Middle) => ' ',
+            (Middle, _) => self.horizontal,
+            (_, Middle) => self.vertical,
+            (Start, Start) => self.top_left,
+            (End, Start) => self.top_right,
+            (Start, End) => self.bottom_left,
+            (End, End) => self.bottom_right,
+        }
+    }
+}
+
+fn suit_colour(suit: Option<Suit>) -> ARGB {
+    use platform_types::*;
+
+    match suit {
+        Some(Suit::Red) => RED,
+        Some(Suit::Green) => GREEN,
+        Some(Suit::Blue) => BLUE,
+        Some(Suit::Yellow) => YELLOW,
+        Some(Suit::Purple) => PURPLE,
+        None => WHITE,
+    }
+}
+
+/// The same shapes as the suit symbols on the cards.
+fn suit_symbol(suit: Suit) -> char {
+    match suit {
+        Suit::Red => '●',
+        Suit::Green => '▲',
+        Suit::Blue => '■',
+        Suit::Yellow => '◆',
+        Suit::Purple => '✚',
+    }
+}
+
+/// The character the game means by `byte`. The accented characters are the
+/// Latin-1 ones, and anything else that cannot be printed is shown as `?`.
+fn printable(byte: u8) -> char {
+    match byte {
+        b' '..=b'~' | 0xA0..=0xFF => char::from(byte),
+        _ => '?',
+    }
+}
+
+/// Blends the colours the way the renderer does, which squares the alpha of
+/// `over` first.
+fn blend(under: ARGB, over: ARGB) -> ARGB {
+    let alpha = over >> 24;
+    let alpha = alpha * alpha / 0xFF;
+
+    let channel = |shift: u32| {
+        let under = (under >> shift) & 0xFF;
+        let over = (over >> shift) & 0xFF;
+
+        ((over * alpha + under * (0xFF - alpha)) / 0xFF) << shift
+    };
+
+    0xFF00_0000 | channel(16) | channel(8) | channel(0)
+}
+
+/// The cells whose middles are inside `rect`.
+fn cells_in(rect: command::Rect) -> (Range<usize>, Range<usize>) {
+    let command::Rect { x_min, y_min, x_max, y_max } = rect;
+    let (x_min, y_min) = (usize::from(x_min.get().get()), usize::from(y_min.get().get()));
+    let (x_max, y_max) = (usize::from(x_max.get().get()), usize::from(y_max.get().get()));
+
+    let half_w = CELL_W / 2;
+    let half_h = CELL_H / 2;
+
+    (
+        ((x_min + CELL_W - 1 - half_w) / CELL_W).min(COLUMNS)
+            ..((x_max + CELL_W - half_w) / CELL_W).min(COLUMNS),
+        ((y_min + CELL_H - 1 - half_h) / CELL_H).min(ROWS)
+            ..((y_max + CELL_H - half_h) / CELL_H).min(ROWS),
+    )
+}
+
+/// The cell the middle of `rect` is in.
+fn cell_at_middle_of(rect: command::Rect) -> (usize, usize) {
+    let command::Rect { x_min, y_min, x_max, y_max } = rect;
+    let x = (usize::from(x_min.get().get()) + usize::from(x_max.get().get())) / 2;
+    let y = (usize::from(y_min.get().get()) + usize::from(y_max.get().get())) / 2;
+
+    ((x / CELL_W).min(COLUMNS - 1), (y / CELL_H).min(ROWS - 1))
+}
+
+/// The cells that any part of `rect` is in.
+fn cells_touching(rect: command::Rect) -> (Range<usize>, Range<usize>) {
+    let command::Rect { x_min, y_min, x_max, y_max } = rect;
+    let end = |max: usize, cell: usize, count: usize| (max / cell + 1).min(count);
+
+    (
+        (usize::from(x_min.get().get()) / CELL_W).min(COLUMNS)
+            ..end(usize::from(x_max.get().get()), CELL_W, COLUMNS),
+        (usize::from(y_min.get().get()) / CELL_H).min(ROWS)
+            ..end(usize::from(y_max.get().get()), CELL_H, ROWS),
+    )
+}
+
+/// Which third of a nine-slice the cell at `index` shows, when a piece from
+/// `third` covers the cells in `range`. The pieces are smaller than the cells,
+/// so only the outermost cell of an edge piece shows the edge.
+fn third_for_cell(third: Third, index: usize, range: &Range<usize>) -> Third {
+    match third {
+        Third::Start if index == range.start => Third::Start,
+        Third::End if index + 1 == range.end => Third::End,
+        _ => Third::Middle,
+    }
+}
+
+/// The alpha at and above which a fill hides the characters under it.
+const HIDING_ALPHA: ARGB = 0xE0;
+
+fn is_card_sized(rect: command::Rect) -> bool {
+    let platform_types::unscaled::Rect { w, h, .. } = rect.unscaled();
+
+    (w.get(), h.get()) == (CARD_WIDTH.get(), CARD_HEIGHT.get())
+    || (w.get(), h.get()) == (CARD_HEIGHT.get(), CARD_WIDTH.get())
+}
+
+/// What is on the screen, as cells of text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid {
+    cells: Vec<Cell>,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            cells: vec![Cell::default(); COLUMNS * ROWS],
+        }
+    }
+}
+
+impl Grid {
+    pub fn cell(&self, column: usize, row: usize) -> Cell {
+        self.cells[row * COLUMNS + column]
+    }
+
+    fn cell_mut(&mut self, column: usize, row: usize) -> &mut Cell {
+        &mut self.cells[row * COLUMNS + column]
+    }
+
+    fn each_cell(
+        &mut self,
+        (columns, rows): (Range<usize>, Range<usize>),
+        mut f: impl FnMut(&mut Cell),
+    ) {
+        for row in rows {
+            for column in columns.clone() {
+                f(self.cell_mut(column, row));
+            }
+        }
+    }
+
+    fn outline(
+        &mut self,
+        (columns, rows): (Range<usize>, Range<usize>),
+        lines: &Lines,
+        foreground: ARGB,
+    ) {
+        let (Some(right), Some(bottom)) = (columns.clone().last(), rows.clone().last()) else {
+            return
+        };
+        let (left, top) = (columns.start, rows.start);
+
+        for row in rows {
+            for column in columns.clone() {
+                let x = if column == left {
+                    Third::Start
+                } else if column == right {
+                    Third::End
+                } else {
+                    Third::Middle
+                };
+                let y = if row == top {
+                    Third::Start
+                } else if row == bottom {
+                    Third::End
+                } else {
+                    Third::Middle
+                };
+
+                if (x, y) != (Third::Middle, Third::Middle) {
+                    let cell = self.cell_mut(column, row);
+                    cell.character = lines.piece(x, y);
+                    cell.foreground = foreground;
+                }
+            }
+        }
+    }
+
+    /// Replaces what is in the grid with what `commands` draw, in the colours
+    /// `palette` swaps in, the same way the renderer does.
+    pub fn draw(&mut self, commands: &[Command], palette: &Palette) {
+        use platform_types::{BLACK, GREEN, GREY, WHITE, YELLOW};
+
+        let colour = |argb: ARGB| render::recolour(argb, palette);
+
+        let table = Cell {
+            foreground: colour(WHITE),
+            background: colour(BLACK),
+            ..Cell::default()
+        };
+        self.cells.fill(table);
+
+        for command in commands {
+            let rect = command.rect;
+
+            match gfx::sprite_of(command) {
+                // The letters and symbols on a turned card each land in their
+                // own cell, spread along the card, where they read as noise
+                // next to the outlines of the cards around them. So turned
+                // cards are only shown by their outlines and colours.
+                Sprite::Char(_) | Sprite::SuitSymbol(_)
+                if command.transform.transposes() => {},
+                Sprite::Char(byte) => {
+                    let (column, row) = cell_at_middle_of(rect);
+                    let cell = self.cell_mut(column, row);
+                    cell.character = printable(byte);
+                    cell.foreground = colour(command.colour_override);
+                },
+                Sprite::CardBacking(suit) => {
+                    let background = colour(suit_colour(suit));
+                    self.each_cell(cells_in(rect), |cell| {
+                        cell.character = ' ';
+                        cell.background = background;
+                    });
+
+                    // Net cards are drawn from slices of the backings.
+                    if is_card_sized(rect) {
+                        self.outline(cells_in(rect), &Lines::ROUNDED, colour(BLACK));
+                    }
+                },
+                Sprite::CardBack => {
+                    let (foreground, background) = (colour(YELLOW), colour(GREY));
+                    self.each_cell(cells_in(rect), |cell| {
+                        cell.character = '░';
+                        cell.foreground = foreground;
+                        cell.background = background;
+                    });
+                    self.outline(cells_in(rect), &Lines::ROUNDED, colour(BLACK));
+                },
+                Sprite::Selectrum => {
+                    self.outline(cells_in(rect), &Lines::DOUBLE, colour(GREEN));
+                },
+                Sprite::SuitSymbol(suit) => {
+                    let (column, row) = cell_at_middle_of(rect);
+                    let cell = self.cell_mut(column, row);
+                    cell.character = suit_symbol(suit);
+                    cell.foreground = colour(BLACK);
+                },
+                Sprite::NineSlice(nine_slice, x, y) => {
+                    let (lines, foreground, background) = match nine_slice {
+                        NineSlice::Window => (&Lines::LIGHT, WHITE, Some(BLACK)),
+                        NineSlice::Button => (&Lines::LIGHT, WHITE, None),
+                        NineSlice::ButtonHot => (&Lines::HEAVY, YELLOW, None),
+                        NineSlice::ButtonPressed => (&Lines::HEAVY, YELLOW, Some(GREY)),
+                    };
+                    let (foreground, background) = (colour(foreground), background.map(colour));
+
+                    let (columns, rows) = cells_touching(rect);
+                    for row in rows.clone() {
+                        for column in columns.clone() {
+                            let cell = self.cell_mut(column, row);
+                            cell.character = lines.piece(
+                                third_for_cell(x, column, &columns),
+                                third_for_cell(y, row, &rows),
+                            );
+                            cell.foreground = foreground;
+                            if let Some(background) = background {
+                                cell.background = background;
+                            }
+                        }
+                    }
+                },
+                Sprite::Chevron(dir, highlighted) => {
+                    let (column, row) = cell_at_middle_of(rect);
+                    let cell = self.cell_mut(column, row);
+                    cell.character = match dir {
+                        ChevronDir::Up => '▲',
+                        ChevronDir::Down => '▼',
+                    };
+                    cell.foreground = colour(match highlighted {
+                        Highlighted::No => GREY,
+                        Highlighted::Yes => YELLOW,
+                    });
+                },
+                Sprite::Fill => {
+                    let over = colour(command.colour_override)
+                        | (command.colour_override & 0xFF00_0000);
+                    // Under fills this strong, the characters would be too
+                    // faint to read, and would only get mixed up with the
+                    // ones drawn on top, in the plain text.
+                    let hides_characters = over >> 24 >= HIDING_ALPHA;
+                    self.each_cell(cells_in(rect), |cell| {
+                        if hides_characters {
+                            cell.character = ' ';
+                        }
+                        cell.foreground = blend(cell.foreground, over);
+                        cell.background = blend(cell.background, over);
+                    });
+                },
+                // The pictures and shadows are too detailed to show with a
+                // handful of cells.
+                Sprite::CardImage | Sprite::CardShadow => {},
+            }
+        }
+    }
+
+    /// The characters in the grid, one line per row, without the colours.
+    pub fn text(&self) -> String {
+        let mut text = String::with_capacity((COLUMNS + 1) * ROWS);
+
+        for row in self.cells.chunks(COLUMNS) {
+            let line: String = row.iter().map(|cell| cell.character).collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+
+        text
+    }
+
+    /// Writes the escape codes that draw the grid, with the top left cell at the
+    /// top left of the terminal. Only the cells that differ from `previous` are
+    /// written, if it is given.
+    pub fn write_ansi(&self, previous: Option<&Grid>, out: &mut impl Write) -> io::Result<()> {
+        let mut colours = None;
+        let mut cursor = None;
+
+        for row in 0..ROWS {
+            for column in 0..COLUMNS {
+                let cell = self.cell(column, row);
+                if previous.is_some_and(|previous| previous.cell(column, row) == cell) {
+                    continue
+                }
+
+                if cursor != Some((column, row)) {
+                    write!(out, "\x1b[{};{}H", row + 1, column + 1)?;
+                }
+
+                if colours != Some((cell.foreground, cell.background)) {
+                    let rgb = |argb: ARGB| ((argb >> 16) & 0xFF, (argb >> 8) & 0xFF, argb & 0xFF);
+                    let (fr, fg, fb) = rgb(cell.foreground);
+                    let (br, bg, bb) = rgb(cell.background);
+                    write!(out, "\x1b[38;2;{fr};{fg};{fb};48;2;{br};{bg};{bb}m")?;
+                    colours = Some((cell.foreground, cell.background));
+                }
+
+                write!(out, "{}", cell.character)?;
+                cursor = Some((column + 1, row));
+            }
+        }
+
+        if colours.is_some() {
+            write!(out, "\x1b[0m")?;
+        }
+        out.flush()
+    }
+}
+
+/// Something read from the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Input {
+    Press(Button),
+    /// Does nothing for a while. Only useful in scripts.
+    Wait,
+    Quit,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+enum Escape {
+    #[default]
+    None,
+    Started,
+    Sequence,
+}
+
+/// Reads keys out of the bytes a terminal sends, including the escape
+/// sequences for the arrow keys. Terminals do not say when a key is released,
+/// or when a lone modifier like shift is pressed, so keys map straight to
+/// buttons, rather than going through the bindings:
+///
+/// | Key                | Button     |
+/// |--------------------|------------|
+/// | Arrows             | Directions |
+/// | `z`, `;`           | A          |
+/// | `x`, `q`           | B          |
+/// | Enter              | Start      |
+/// | Tab, `?`           | Help       |
+/// | `.`                | (Wait)     |
+/// | Ctrl-C, Ctrl-D     | (Quit)     |
+#[derive(Clone, Debug, Default)]
+pub struct Keys {
+    escape: Escape,
+}
+
+impl Keys {
+    pub fn read(&mut self, byte: u8) -> Option<Input> {
+        match self.escape {
+            Escape::None => {},
+            Escape::Started => {
+                if byte == b'[' || byte == b'O' {
+                    self.escape = Escape::Sequence;
+                    return None
+                }
+                // A lone escape, which we ignore, then the next key.
+                self.escape = Escape::None;
+            },
+            Escape::Sequence => {
+                // Parameters, like the modifiers in `ESC [ 1 ; 2 A`, come
+                // before the final byte, and are skipped.
+                if !(0x40..=0x7E).contains(&byte) {
+                    return None
+                }
+                self.escape = Escape::None;
+
+                return match byte {
+                    b'A' => Some(Input::Press(Button::UP)),
+                    b'B' => Some(Input::Press(Button::DOWN)),
+                    b'C' => Some(Input::Press(Button::RIGHT)),
+                    b'D' => Some(Input::Press(Button::LEFT)),
+                    _ => None,
+                }
+            },
+        }
+
+        match byte {
+            0x1B => {
+                self.escape = Escape::Started;
+                None
+            },
+            b'z' | b'Z' | b';' => Some(Input::Press(Button::A)),
+            b'x' | b'X' | b'q' | b'Q' => Some(Input::Press(Button::B)),
+            b'\r' | b'\n' => Some(Input::Press(Button::START)),
+            b'\t' | b'?' => Some(Input::Press(Button::HELP)),
+            b'.' => Some(Input::Wait),
+            // Ctrl-C and Ctrl-D, which arrive as bytes in raw mode.
+            0x03 | 0x04 => Some(Input::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// How many frames each input in a script lasts, including the one where its
+/// button is pressed.
+pub const FRAMES_PER_SCRIPTED_INPUT: u32 = 30;
+
+const FRAME_DURATION: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// Owns a `State`, and draws what it produces into a `Grid`.
+pub struct Terminal<S: State> {
+    pub state: S,
+    grid: Grid,
+    pressed: Vec<Button>,
+}
+
+impl <S: State> Terminal<S> {
+    pub fn new(state: S) -> Self {
+        Self {
+            state,
+            grid: Grid::default(),
+            pressed: Vec::with_capacity(8),
+        }
+    }
+
+    /// Presses `button` for the next frame. It is released after that, since
+    /// terminals do not say when keys are released.
+    pub fn press(&mut self, button: Button) {
+        self.state.press(button);
+        self.pressed.push(button);
+    }
+
+    pub fn step(&mut self) {
+        // Done before `frame`, since the commands borrow the state.
+        let palette = self.state.theme().palette();
+
+        let (commands, _sfx) = self.state.frame();
+        self.grid.draw(commands, &palette);
+
+        for button in self.pressed.drain(..) {
+            self.state.release(button);
+        }
+    }
+
+    /// Runs the frames for a single input of a script.
+    pub fn play(&mut self, input: Input) {
+        match input {
+            Input::Press(button) => self.press(button),
+            Input::Wait | Input::Quit => {},
+        }
+
+        for _ in 0..FRAMES_PER_SCRIPTED_INPUT {
+            self.step();
+        }
+    }
+
+    /// Runs the frames for each input `bytes` has, in order, until a quit.
+    pub fn play_script(&mut self, bytes: &[u8]) {
+        let mut keys = Keys::default();
+
+        for input in bytes.iter().filter_map(|&byte| keys.read(byte)) {
+            if input == Input::Quit {
+                break
+            }
+
+            self.play(input);
+        }
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+}
+
+/// Like the platform's, but seeded with the number after `--seed`, if there is
+/// one, so scripted games can be played again the same way. The logs go to
+/// stderr, to keep them off the screen.
+pub fn get_state_params() -> StateParams {
+    fn logger(s: &str) {
+        eprintln!("{}", s);
+    }
+
+    let mut args = std::env::args().skip_while(|arg| arg != "--seed").skip(1);
+    let seed = args.next()
+        .and_then(|seed| seed.parse::<u128>().ok())
+        .map(u128::to_le_bytes)
+        .unwrap_or_else(new_seed);
+
+    (
+        seed,
+        Some(logger),
+        Some(logger),
+    )
+}
+
+fn new_seed() -> [u8; 16] {
+    let time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let time = time.as_secs_f64();
+
+    unsafe {
+        core::mem::transmute::<[f64; 2], [u8; 16]>([time, 1.0 / time])
+    }
+}
+
+/// Plays the game interactively if stdin is a terminal. Otherwise the input is
+/// played as a script, as quickly as possible, and the final screen is printed
+/// as plain text, unless stdout is a terminal, where each step is shown.
+pub fn run<S: State>(state: S) -> io::Result<()> {
+    let terminal = Terminal::new(state);
+    let input = read_stdin();
+
+    if io::stdin().is_terminal() {
+        interactive(terminal, input)
+    } else {
+        scripted(terminal, input)
+    }
+}
+
+/// Reads stdin on another thread, so the frames can keep going while no keys
+/// are pressed.
+fn read_stdin() -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let mut stdin = io::stdin().lock();
+        let mut buffer = [0; 64];
+
+        while let Ok(count @ 1..) = stdin.read(&mut buffer) {
+            if sender.send(buffer[..count].to_vec()).is_err() {
+                break
+            }
+        }
+    });
+
+    receiver
+}
+
+fn interactive<S: State>(mut terminal: Terminal<S>, input: Receiver<Vec<u8>>) -> io::Result<()> {
+    let _raw_mode = RawMode::enable()?;
+    let mut screen = Screen::enter(io::stdout().lock())?;
+
+    let mut keys = Keys::default();
+    let mut previous: Option<Grid> = None;
+
+    loop {
+        let frame_start = Instant::now();
+
+        loop {
+            let bytes = match input.try_recv() {
+                Ok(bytes) => bytes,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Ok(()),
+            };
+
+            for input in bytes.into_iter().filter_map(|byte| keys.read(byte)) {
+                match input {
+                    Input::Press(button) => terminal.press(button),
+                    Input::Wait => {},
+                    Input::Quit => return Ok(()),
+                }
+            }
+        }
+
+        terminal.step();
+
+        terminal.grid().write_ansi(previous.as_ref(), &mut screen.0)?;
+        match &mut previous {
+            Some(previous) => previous.clone_from(terminal.grid()),
+            None => previous = Some(terminal.grid().clone()),
+        }
+
+        std::thread::sleep(FRAME_DURATION.saturating_sub(frame_start.elapsed()));
+    }
+}
+
+fn scripted<S: State>(mut terminal: Terminal<S>, input: Receiver<Vec<u8>>) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let show_each_step = io::stdout().is_terminal();
+
+    let mut keys = Keys::default();
+    let mut previous: Option<Grid> = None;
+
+    'script: for bytes in input {
+        for input in bytes.into_iter().filter_map(|byte| keys.read(byte)) {
+            if input == Input::Quit {
+                break 'script
+            }
+
+            terminal.play(input);
+
+            if show_each_step {
+                terminal.grid().write_ansi(previous.as_ref(), &mut out)?;
+                previous = Some(terminal.grid().clone());
+            }
+        }
+    }
+
+    if show_each_step {
+        writeln!(out)
+    } else {
+        write!(out, "{}", terminal.grid().text())
+    }
+}
+
+/// Shows the alternate screen, with the cursor hidden, until dropped, so that
+/// what was in the terminal before comes back afterwards.
+struct Screen<W: Write>(W);
+
+impl <W: Write> Screen<W> {
+    fn enter(mut out: W) -> io::Result<Self> {
+        write!(out, "\x1b[?1049h\x1b[?25l\x1b[2J")?;
+        out.flush()?;
+
+        Ok(Self(out))
+    }
+}
+
+impl <W: Write> Drop for Screen<W> {
+    fn drop(&mut self) {
+        // There is nowhere left to report errors to.
+        let _ = write!(self.0, "\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = self.0.flush();
+    }
+}
+
+/// Has the terminal send each key as soon as it is pressed, without echoing it,
+/// until dropped.
+#[cfg(unix)]
+struct RawMode(libc::termios);
+
+#[cfg(unix)]
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        // SAFETY: `termios` is plain data, which `tcgetattr` fills in.
+        unsafe {
+            let mut termios: libc::termios = core::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut termios) != 0 {
+                return Err(io::Error::last_os_error())
+            }
+
+            let original = termios;
+            libc::cfmakeraw(&mut termios);
+
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &termios) != 0 {
+                return Err(io::Error::last_os_error())
+            }
+
+            Ok(Self(original))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: This is the same `termios` that `tcgetattr` filled in.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+        }
+    }
+}
+
+/// Elsewhere, the keys only arrive when enter is pressed.
+#[cfg(not(unix))]
+struct RawMode;
+
+#[cfg(not(unix))]
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod keys_are_read_as_buttons {
+    use super::*;
+
+    fn read_all(bytes: &[u8]) -> Vec<Input> {
+        let mut keys = Keys::default();
+        bytes.iter().filter_map(|&byte| keys.read(byte)).collect()
+    }
+
+    #[test]
+    fn from_letters_and_enter() {
+        assert_eq!(
+            read_all(b"zxq\r\t."),
+            [
+                Input::Press(Button::A),
+                Input::Press(Button::B),
+                Input::Press(Button::B),
+                Input::Press(Button::START),
+                Input::Press(Button::HELP),
+                Input::Wait,
+            ]
+        );
+    }
+
+    #[test]
+    fn from_the_escape_sequences_for_the_arrows() {
+        assert_eq!(
+            read_all(b"\x1b[A\x1b[B\x1bOC\x1b[1;2D"),
+            [
+                Input::Press(Button::UP),
+                Input::Press(Button::DOWN),
+                Input::Press(Button::RIGHT),
+                Input::Press(Button::LEFT),
+            ]
+        );
+    }
+
+    #[test]
+    fn after_a_lone_escape_or_an_unknown_sequence() {
+        assert_eq!(
+            read_all(b"\x1bz\x1b[5~x"),
+            [
+                Input::Press(Button::A),
+                Input::Press(Button::B),
+            ]
+        );
+    }
+
+    #[test]
+    fn except_for_ctrl_c_which_quits() {
+        assert_eq!(read_all(b"\x03"), [Input::Quit]);
+    }
+}
+
+#[cfg(test)]
+mod the_table_is_drawn_in_cells {
+    use super::*;
+    use platform_types::{command::Transform, unscaled};
+
+    fn grid_for(draw: impl FnOnce(&mut gfx::Commands)) -> Grid {
+        let mut commands = gfx::Commands::default();
+        draw(&mut commands);
+
+        let mut grid = Grid::default();
+        grid.draw(commands.slice(), &PALETTE);
+        grid
+    }
+
+    fn cell_xy(column: usize, row: usize) -> unscaled::XY {
+        unscaled::XY {
+            x: unscaled::X((column * CELL_W) as _),
+            y: unscaled::Y((row * CELL_H) as _),
+        }
+    }
+
+    #[test]
+    fn with_each_character_where_the_game_prints_it() {
+        let grid = grid_for(|commands| {
+            commands.print_line(b"Fish \xE9", cell_xy(3, 2), platform_types::RED);
+        });
+
+        assert_eq!(grid.text().lines().nth(2), Some("   Fish é"));
+        assert_eq!(grid.cell(3, 2).foreground, platform_types::RED);
+    }
+
+    #[test]
+    fn with_every_character_in_the_font_telling_which_it_is() {
+        for byte in (b' '..=b'~').chain(0xE1..=0xFF) {
+            let mut commands = gfx::Commands::default();
+            commands.print_char(byte, cell_xy(1, 1), platform_types::WHITE);
+
+            let sprites: Vec<_> = commands.slice().iter().map(gfx::sprite_of).collect();
+            assert_eq!(sprites, [Sprite::Char(byte)], "{byte:#x}");
+        }
+    }
+
+    #[test]
+    fn with_cards_outlined_in_the_colour_of_their_suit() {
+        for transform in [Transform::IDENTITY, Transform::ROTATE_CW] {
+            let grid = grid_for(|commands| {
+                commands.draw_card_turned(0, cell_xy(2, 2), transform);
+            });
+
+            assert_eq!(grid.cell(2, 2).character, '╭', "{transform:?}");
+            assert_eq!(grid.cell(5, 5).background, platform_types::RED, "{transform:?}");
+        }
+    }
+
+    #[test]
+    fn with_the_text_on_upright_cards_only() {
+        let upright = grid_for(|commands| {
+            commands.draw_card(0, cell_xy(2, 2));
+        });
+
+        assert!(upright.text().contains('●'));
+        assert!(upright.text().chars().any(|c| c.is_ascii_alphabetic()));
+
+        for transform in [Transform::ROTATE_CW, Transform::ROTATE_CCW] {
+            let turned = grid_for(|commands| {
+                commands.draw_card_turned(0, cell_xy(2, 2), transform);
+            });
+
+            let text = turned.text();
+            assert!(
+                text.chars().all(|c| c.is_whitespace() || "╭╮╰╯─│".contains(c)),
+                "{transform:?}\n{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn with_windows_outlined_all_the_way_around() {
+        let grid = grid_for(|commands| {
+            commands.draw_nine_slice(
+                NineSlice::Window,
+                unscaled::Rect {
+                    x: unscaled::X(20),
+                    y: unscaled::Y(26),
+                    w: unscaled::W(64),
+                    h: unscaled::H(48),
+                },
+            );
+        });
+
+        let text = grid.text();
+        let lines: Vec<&str> = text.lines().skip(2).take(5).collect();
+
+        assert_eq!(
+            lines,
+            [
+                "  ┌────────┐",
+                "  │        │",
+                "  │        │",
+                "  │        │",
+                "  └────────┘",
+            ],
+        );
+    }
+
+    #[test]
+    fn with_fills_darkening_what_is_under_them() {
+        let grid = grid_for(|commands| {
+            commands.draw_card(0, cell_xy(2, 2));
+            commands.dim_screen();
+        });
+
+        let background = grid.cell(5, 5).background;
+        assert_ne!(background, platform_types::RED);
+        assert!((background >> 16) & 0xFF < (platform_types::RED >> 16) & 0xFF);
+    }
+}
+
+#[cfg(test)]
+mod scripts_play_the_app {
+    use super::*;
+
+    const SEED: [u8; 16] = [42; 16];
+
+    fn text_after(script: &[u8]) -> String {
+        let mut terminal = Terminal::new(app::State::new((SEED, None, None)));
+        terminal.play_script(script);
+        terminal.grid().text()
+    }
+
+    #[test]
+    fn the_same_way_each_time() {
+        assert_eq!(text_after(b"..\x1b[Cz."), text_after(b"..\x1b[Cz."));
+    }
+
+    #[test]
+    fn with_help_shown_over_the_table_and_hidden_again() {
+        let help = text_after(b"..\t");
+
+        assert_ne!(help, text_after(b"..."));
+        assert!(help.contains("Rules"), "{help}");
+        // The table keeps going while the help is shown.
+        assert_eq!(text_after(b"..\t\t"), text_after(b"...."));
+    }
+
+    #[test]
+    fn with_the_table_hidden_behind_the_help() {
+        let table = text_after(b"...");
+        let help = text_after(b"..\t");
+
+        let card_text = table.lines().last().unwrap().trim();
+        assert!(!card_text.is_empty());
+        assert!(!help.contains(card_text), "{help}");
+    }
+
+    #[test]
+    fn until_a_quit() {
+        assert_eq!(text_after(b"..\x03\t"), text_after(b".."));
+    }
+}
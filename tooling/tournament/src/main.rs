@@ -0,0 +1,460 @@
+use game::{CpuStrategy, State, update_and_render};
+use gfx::Commands;
+use models::{CpuId, HandId};
+use platform_types::{Bindings, Button, Input, Speaker};
+use search::Budget;
+
+use std::fmt::Write as _;
+
+const USAGE: &str = "\
+usage: tournament [--rounds N] [--seed N] [--max-frames N] [--stall-frames N] [--out PATH] [--list] [STRATEGY...]
+
+Plays every lineup of three of the given strategies against each other, once
+in each seat rotation per round, then prints a report. With no strategies
+given, all of the registered ones are used.
+
+Games end when no more baskets can be made. Games where nobody has filled a
+basket for --stall-frames frames are stopped, since the Cpus can end up passing
+the same few cards around forever, and so are games that run past --max-frames.
+Stopped games are counted in the report, but left out of the standings.";
+
+/// The strategies that can be entered into a tournament, by name.
+fn registered() -> Vec<(&'static str, CpuStrategy)> {
+    vec![
+        ("heuristic", CpuStrategy::Heuristic),
+        ("search-256", CpuStrategy::Search(Budget::Iterations { per_move: 256, per_frame: 256 })),
+        ("search-1024", CpuStrategy::Search(Budget::Iterations { per_move: 1024, per_frame: 1024 })),
+        // The in-game default, but all at once, since nobody is watching.
+        ("search-2048", CpuStrategy::Search(Budget::Iterations { per_move: 2048, per_frame: 2048 })),
+    ]
+}
+
+struct Args {
+    rounds: u32,
+    seed: u64,
+    max_frames: u32,
+    stall_frames: u32,
+    out: Option<std::path::PathBuf>,
+    strategies: Vec<(&'static str, CpuStrategy)>,
+}
+
+fn parse_args() -> Result<Args, Box<dyn std::error::Error>> {
+    let mut args = Args {
+        rounds: 10,
+        seed: 42,
+        max_frames: 2_000_000,
+        stall_frames: 100_000,
+        out: None,
+        strategies: Vec::new(),
+    };
+
+    let registered = registered();
+
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| iter.next().ok_or(format!("{name} needs a value"));
+
+        match arg.as_str() {
+            "--rounds" => args.rounds = value("--rounds")?.parse()?,
+            "--seed" => args.seed = value("--seed")?.parse()?,
+            "--max-frames" => args.max_frames = value("--max-frames")?.parse()?,
+            "--stall-frames" => args.stall_frames = value("--stall-frames")?.parse()?,
+            "--out" => args.out = Some(value("--out")?.into()),
+            "--list" => {
+                for (name, strategy) in &registered {
+                    println!("{name}: {strategy:?}");
+                }
+                std::process::exit(0);
+            },
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            },
+            name => {
+                let entry = registered.iter()
+                    .find(|(n, _)| *n == name)
+                    .ok_or_else(|| format!("unknown strategy \"{name}\". Try --list\n\n{USAGE}"))?;
+                args.strategies.push(*entry);
+            },
+        }
+    }
+
+    if args.strategies.is_empty() {
+        args.strategies = registered;
+    }
+
+    if args.strategies.len() < 2 {
+        return Err(format!("need at least two strategies to compare\n\n{USAGE}").into());
+    }
+
+    Ok(args)
+}
+
+/// Every multiset of three strategy indexes, except the ones where everyone
+/// is the same strategy, since nothing can be learned from those.
+fn lineups(strategy_count: usize) -> Vec<[usize; 3]> {
+    let mut output = Vec::new();
+    for a in 0..strategy_count {
+        for b in a..strategy_count {
+            for c in b..strategy_count {
+                if a != c {
+                    output.push([a, b, c]);
+                }
+            }
+        }
+    }
+    output
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum End {
+    Finished,
+    Stalled,
+    OutOfFrames,
+}
+
+struct GameResult {
+    points: [u32; CpuId::COUNT as usize],
+    end: End,
+    frames: u32,
+}
+
+const HOUSE_BUTTONS: [Button; 6] = [
+    Button::A,
+    Button::B,
+    Button::UP,
+    Button::DOWN,
+    Button::LEFT,
+    Button::RIGHT,
+];
+
+/// Plays a whole game, with the given strategies sitting in the Cpu seats.
+/// The player's seat is taken by the "house", which mashes buttons at random,
+/// the same way for everyone.
+fn play_game(
+    seed: xs::Seed,
+    strategies: [CpuStrategy; CpuId::COUNT as usize],
+    max_frames: u32,
+    stall_frames: u32,
+) -> GameResult {
+    let mut commands = Commands::default();
+    let mut state = State::new(seed);
+    state.cpu_strategies = strategies;
+    let mut speaker = Speaker::default();
+    let mut input = Input::default();
+
+    let mut house_rng = xs::from_seed(seed);
+
+    let basket_card_count = |state: &State| -> u8 {
+        HandId::ALL.iter().map(|&id| state.cards.baskets(id).len()).sum()
+    };
+
+    let mut frames = 0;
+    let mut last_basket_frame = 0;
+    let mut last_basket_card_count = 0;
+    while frames < max_frames
+    && frames - last_basket_frame < stall_frames
+    && !state.is_over() {
+        input.previous_gamepad = input.gamepad;
+        input.gamepad = <_>::default();
+        // Leave a frame between presses, so each one counts as a fresh press.
+        if frames & 1 == 0 {
+            let i = xs::range(&mut house_rng, 0..HOUSE_BUTTONS.len() as u32);
+            input.gamepad.insert(HOUSE_BUTTONS[i as usize]);
+        }
+
+        commands.clear();
+        speaker.clear();
+        update_and_render(&mut commands, &mut state, input, &Bindings::default(), &mut speaker);
+
+        frames += 1;
+
+        let count = basket_card_count(&state);
+        if count != last_basket_card_count {
+            last_basket_card_count = count;
+            last_basket_frame = frames;
+        }
+    }
+
+    let mut points = [0; CpuId::COUNT as usize];
+    for id in CpuId::ALL {
+        points[id as usize] = state.cards.points(HandId::from(id));
+    }
+
+    let end = if state.is_over() {
+        End::Finished
+    } else if frames < max_frames {
+        End::Stalled
+    } else {
+        End::OutOfFrames
+    };
+
+    GameResult {
+        points,
+        end,
+        frames,
+    }
+}
+
+fn seed_for(base: u64, round: u32) -> xs::Seed {
+    let mut seed = [0; 16];
+    seed[..8].copy_from_slice(&base.to_le_bytes());
+    seed[8..12].copy_from_slice(&round.to_le_bytes());
+    seed
+}
+
+const INITIAL_ELO: f64 = 1500.0;
+const ELO_K: f64 = 16.0;
+/// For a 95% confidence interval.
+const Z: f64 = 1.96;
+
+#[derive(Clone, Debug, Default)]
+struct Standing {
+    elo: f64,
+    games: u32,
+    /// Ties for first split the win between everyone who tied.
+    wins: f64,
+    points: u64,
+    /// Head to head, against seats with a different strategy.
+    pair_score: f64,
+    pair_count: u32,
+}
+
+fn expected_score(rating: f64, opponent_rating: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent_rating - rating) / 400.0))
+}
+
+/// The Wilson score interval for the given success rate.
+fn wilson_interval(successes: f64, trials: u32) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+    let n = f64::from(trials);
+    let p = successes / n;
+    let z2 = Z * Z;
+
+    let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let spread = (Z / (1.0 + z2 / n)) * ((p * (1.0 - p) / n) + z2 / (4.0 * n * n)).sqrt();
+
+    ((centre - spread).max(0.0), (centre + spread).min(1.0))
+}
+
+fn record(standings: &mut [Standing], seats: [usize; 3], result: &GameResult) {
+    let best = result.points.iter().copied().max().unwrap_or(0);
+    let winner_count = result.points.iter().filter(|&&p| p == best).count() as f64;
+
+    for seat in 0..seats.len() {
+        let standing = &mut standings[seats[seat]];
+        standing.games += 1;
+        standing.points += u64::from(result.points[seat]);
+        if result.points[seat] == best {
+            standing.wins += 1.0 / winner_count;
+        }
+    }
+
+    // Pairwise Elo updates, using the ratings from before this game for all
+    // of them, so the seat order doesn't matter.
+    let before: Vec<f64> = standings.iter().map(|s| s.elo).collect();
+    for a in 0..seats.len() {
+        for b in 0..seats.len() {
+            if a == b || seats[a] == seats[b] {
+                continue
+            }
+
+            let score = match result.points[a].cmp(&result.points[b]) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Less => 0.0,
+            };
+
+            let standing = &mut standings[seats[a]];
+            standing.pair_score += score;
+            standing.pair_count += 1;
+            standing.elo += (ELO_K / 2.0)
+                * (score - expected_score(before[seats[a]], before[seats[b]]));
+        }
+    }
+}
+
+/// The games that were stopped before they finished.
+#[derive(Clone, Copy, Debug, Default)]
+struct Stopped {
+    stalled: u32,
+    out_of_frames: u32,
+}
+
+fn report(
+    args: &Args,
+    standings: &[Standing],
+    stopped: Stopped,
+    total_frames: u64,
+) -> String {
+    let mut order: Vec<usize> = (0..standings.len()).collect();
+    order.sort_by(|&a, &b| standings[b].elo.total_cmp(&standings[a].elo));
+
+    let games: u32 = standings.iter().map(|s| s.games).sum::<u32>() / 3;
+
+    let mut output = String::new();
+    let _ = writeln!(output, "# Tournament report");
+    let _ = writeln!(output);
+    let _ = writeln!(
+        output,
+        "{games} finished games, {} rounds, base seed {}, {total_frames} frames total.",
+        args.rounds,
+        args.seed,
+    );
+    let _ = writeln!(output);
+    let _ = writeln!(
+        output,
+        "{} games stalled and {} ran out of frames. Those are left out of the standings below.",
+        stopped.stalled,
+        stopped.out_of_frames,
+    );
+    let _ = writeln!(output);
+    let _ = writeln!(output, "| Strategy | Elo | Games | Win rate | 95% CI | Head to head | Avg. points |");
+    let _ = writeln!(output, "|----------|----:|------:|---------:|--------|-------------:|------------:|");
+    for i in order {
+        let (name, _) = args.strategies[i];
+        let s = &standings[i];
+        let (low, high) = wilson_interval(s.wins, s.games);
+        let _ = writeln!(
+            output,
+            "| {name} | {:.0} | {} | {:.1}% | {:.1}% - {:.1}% | {:.1}% | {:.2} |",
+            s.elo,
+            s.games,
+            100.0 * s.wins / f64::from(s.games.max(1)),
+            100.0 * low,
+            100.0 * high,
+            100.0 * s.pair_score / f64::from(s.pair_count.max(1)),
+            s.points as f64 / f64::from(s.games.max(1)),
+        );
+    }
+    let _ = writeln!(output);
+    let _ = writeln!(
+        output,
+        "Win rate is how often a strategy had the most points of the three Cpu seats. \
+        Head to head is the share of comparisons against seats with a different strategy that it won, \
+        counting ties as half."
+    );
+
+    output
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = parse_args()?;
+
+    let mut standings = vec![
+        Standing { elo: INITIAL_ELO, ..<_>::default() };
+        args.strategies.len()
+    ];
+    let mut stopped = Stopped::default();
+    let mut total_frames = 0;
+
+    let lineups = lineups(args.strategies.len());
+
+    for round in 0..args.rounds {
+        let seed = seed_for(args.seed, round);
+
+        for lineup in &lineups {
+            // Same deal for each rotation, so the seats even each other out.
+            for rotation in 0..lineup.len() {
+                let mut seats = *lineup;
+                seats.rotate_left(rotation);
+
+                let result = play_game(
+                    seed,
+                    seats.map(|i| args.strategies[i].1),
+                    args.max_frames,
+                    args.stall_frames,
+                );
+
+                total_frames += u64::from(result.frames);
+
+                eprintln!(
+                    "round {round}: {} got {:?}{}",
+                    seats.map(|i| args.strategies[i].0).join(" vs "),
+                    result.points,
+                    match result.end {
+                        End::Finished => "",
+                        End::Stalled => " (stalled)",
+                        End::OutOfFrames => " (out of frames)",
+                    },
+                );
+
+                // A stopped game's points say more about where it was cut
+                // off than about how well anyone played.
+                match result.end {
+                    End::Finished => record(&mut standings, seats, &result),
+                    End::Stalled => stopped.stalled += 1,
+                    End::OutOfFrames => stopped.out_of_frames += 1,
+                }
+            }
+        }
+    }
+
+    let report = report(&args, &standings, stopped, total_frames);
+
+    print!("{report}");
+
+    if let Some(path) = &args.out {
+        eprintln!("writing to: {}", path.display());
+        std::fs::write(path, report)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod play_game_returns_the_expected_result {
+    use super::*;
+
+    #[test]
+    fn on_a_seeded_game_played_to_the_finish() {
+        // A deal that ends with the Dead Scuba Diver's basket done, so there
+        // is still a stray fish left in play.
+        let result = play_game(
+            seed_for(42, 2),
+            [CpuStrategy::Heuristic; CpuId::COUNT as usize],
+            2_000_000,
+            100_000,
+        );
+
+        assert_eq!(result.end, End::Finished, "{:?} after {} frames", result.points, result.frames);
+        assert!(result.points.iter().any(|&p| p > 0), "{:?}", result.points);
+    }
+}
+
+#[cfg(test)]
+mod wilson_interval_returns_the_expected_result {
+    use super::*;
+
+    #[test]
+    fn on_half_of_a_hundred() {
+        let (low, high) = wilson_interval(50.0, 100);
+
+        assert!((low - 0.4038).abs() < 0.001, "{low}");
+        assert!((high - 0.5962).abs() < 0.001, "{high}");
+    }
+
+    #[test]
+    fn on_no_trials() {
+        assert_eq!(wilson_interval(0.0, 0), (0.0, 1.0));
+    }
+}
+
+#[cfg(test)]
+mod lineups_returns_the_expected_result {
+    use super::*;
+
+    #[test]
+    fn on_two_strategies() {
+        assert_eq!(lineups(2), vec![[0, 0, 1], [0, 1, 1]]);
+    }
+
+    #[test]
+    fn never_has_everyone_the_same() {
+        for lineup in lineups(4) {
+            assert!(lineup.iter().any(|&i| i != lineup[0]));
+        }
+    }
+}
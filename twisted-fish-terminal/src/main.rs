@@ -0,0 +1,8 @@
+fn main() {
+    let params = terminal::get_state_params();
+    let state = app::State::new(params);
+    if let Err(error) = terminal::run(state) {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+}